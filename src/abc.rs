@@ -0,0 +1,271 @@
+use crate::error::Error;
+use crate::prelude::*;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::Write;
+
+/// The semitone offsets of the natural tones from C, as written in ABC notation.
+const TONES: [(char, i16); 7] = [
+    ('C', 0),
+    ('D', 2),
+    ('E', 4),
+    ('F', 5),
+    ('G', 7),
+    ('A', 9),
+    ('B', 11),
+];
+
+/// Parse a note written in ABC notation, e.g. "^C," or "_B'".
+///
+/// Notes are made of optional accidentals ("^" for sharp, "_" for flat, both of which can be
+/// doubled, or "=" for natural), a tone name (upper case for the octave starting at middle C, and
+/// lower case for the octave above), and any number of "," or "'" for going one octave down or up.
+/// A trailing note length (e.g. "2" or "3/2") is accepted and ignored.
+///
+/// As there is no context, accidentals from the key signature or from previous notes in the same
+/// bar are not applied.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{abc::parse_note, error::Error::*, prelude::Note::*};
+///
+/// assert_eq!(parse_note("C"), Ok(C4));
+/// assert_eq!(parse_note("c"), Ok(C5));
+/// assert_eq!(parse_note("^C,"), Ok(CSharp3));
+/// assert_eq!(parse_note("_B'"), Ok(ASharp5));
+/// assert_eq!(parse_note("__e"), Ok(D5));
+/// assert_eq!(parse_note("=F,,3/2"), Ok(F2));
+///
+/// assert_eq!(parse_note("H"), Err(InvalidNoteName));
+/// assert_eq!(parse_note("C,,,,,,"), Err(OutOfMIDIRange));
+/// assert_eq!(parse_note(&format!("C{}", "'".repeat(3000))), Err(OutOfMIDIRange));
+/// ```
+pub fn parse_note(token: &str) -> Result<Note, Error> {
+    let token = token.trim();
+
+    // Accidentals
+    let rest = token.trim_start_matches(['^', '_', '=']);
+    let accidentals = &token[..token.len() - rest.len()];
+    let alteration = match accidentals {
+        "" | "=" => 0,
+        "^" => 1,
+        "^^" => 2,
+        "_" => -1,
+        "__" => -2,
+        _ => return Err(Error::InvalidNoteName),
+    };
+
+    // Tone name, where lower case goes an octave up
+    let mut chars = rest.chars();
+    let tone = chars.next().ok_or(Error::InvalidNoteName)?;
+    let (_, offset) = TONES
+        .iter()
+        .find(|(name, _)| *name == tone.to_ascii_uppercase())
+        .ok_or(Error::InvalidNoteName)?;
+    let mut octave = if tone.is_ascii_lowercase() { 5 } else { 4 };
+
+    // Octave marks, followed by the optional note length
+    let rest = chars.as_str();
+    let length = rest.trim_start_matches([',', '\'']);
+    if !length.chars().all(|c| c.is_ascii_digit() || c == '/') {
+        return Err(Error::InvalidNoteName);
+    }
+    for mark in rest[..rest.len() - length.len()].chars() {
+        octave += if mark == ',' { -1 } else { 1 };
+        if !(-1..=9).contains(&octave) {
+            return Err(Error::OutOfMIDIRange);
+        }
+    }
+
+    let midi_key_number = (octave + 1) * 12 + offset + alteration;
+    u8::try_from(midi_key_number)
+        .ok()
+        .filter(|midi_key_number| *midi_key_number < 128)
+        .map(Note::from)
+        .ok_or(Error::OutOfMIDIRange)
+}
+
+/// Parse a chord written in ABC notation as notes played together in brackets, e.g. `[CEG]`.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{abc::parse_chord, error::Error::*, prelude::{*, Note::*}};
+///
+/// let chord = parse_chord("[A,^CE]2").unwrap();
+/// assert_eq!(chord, Chord::from_notes(&[A3, CSharp4, E4]));
+///
+/// // Brackets need at least a note in them
+/// assert_eq!(parse_chord("[]"), Err(InvalidNoteName));
+/// ```
+pub fn parse_chord(token: &str) -> Result<Chord, Error> {
+    let token = token.trim();
+    let inner = token
+        .strip_prefix('[')
+        .and_then(|token| token.split_once(']'))
+        .filter(|(_, length)| length.chars().all(|c| c.is_ascii_digit() || c == '/'))
+        .map(|(inner, _)| inner)
+        .filter(|inner| !inner.is_empty())
+        .ok_or(Error::InvalidNoteName)?;
+
+    // Every note starts with its accidentals or its tone name
+    let mut notes = Vec::new();
+    let mut start = 0;
+    for (index, c) in inner.char_indices() {
+        let starts_note = matches!(c, '^' | '_' | '=') || c.is_ascii_alphabetic();
+        let after_accidental = inner[start..index]
+            .chars()
+            .all(|c| matches!(c, '^' | '_' | '='));
+        if index > start && starts_note && !after_accidental {
+            notes.push(parse_note(&inner[start..index])?);
+            start = index;
+        }
+    }
+    if start < inner.len() {
+        notes.push(parse_note(&inner[start..])?);
+    }
+
+    Ok(Chord::from_notes(&notes))
+}
+
+/// Parse a chord symbol written in ABC notation, i.e. in double quotes above the notes, e.g.
+/// "\"Am\"". The symbol itself is parsed as done by `Chord::from_symbol`.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{abc::parse_chord_symbol, error::Error::*, prelude::*};
+///
+/// let chord = parse_chord_symbol("\"Am\"").unwrap();
+/// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "Am");
+///
+/// assert_eq!(parse_chord_symbol("Am"), Err(InvalidChordSymbol));
+/// ```
+pub fn parse_chord_symbol(token: &str) -> Result<Chord, Error> {
+    token
+        .trim()
+        .strip_prefix('"')
+        .and_then(|token| token.strip_suffix('"'))
+        .ok_or(Error::InvalidChordSymbol)
+        .and_then(Chord::from_symbol)
+}
+
+/// Write a note in ABC notation, spelling accidentals as requested.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{abc::{parse_note, write_note}, prelude::{*, Note::*}};
+///
+/// assert_eq!(write_note(C4, FlatOrSharp::Sharp), "C");
+/// assert_eq!(write_note(CSharp3, FlatOrSharp::Sharp), "^C,");
+/// assert_eq!(write_note(ASharp5, FlatOrSharp::Flat), "_b");
+/// assert_eq!(write_note(E7, FlatOrSharp::Flat), "e''");
+///
+/// // Writing is the inverse of parsing
+/// assert_eq!(parse_note(&write_note(FSharp1, FlatOrSharp::Flat)), Ok(FSharp1));
+/// ```
+pub fn write_note(note: Note, accidental: FlatOrSharp) -> String {
+    let mut abc = String::new();
+    // Writing into a `String` never fails
+    write_note_into(&mut abc, note, accidental).unwrap();
+
+    abc
+}
+
+/// Write a chord in ABC notation, as its notes played together in brackets. Silence is written as
+/// a rest.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{abc::write_chord, prelude::{*, Note::*}};
+///
+/// let chord = Chord::from_notes(&[A3, CSharp4, E4]);
+/// assert_eq!(write_chord(&chord, FlatOrSharp::Sharp), "[A,^CE]");
+///
+/// assert_eq!(write_chord(&Chord::default(), FlatOrSharp::Sharp), "z");
+/// ```
+pub fn write_chord(chord: &Chord, accidental: FlatOrSharp) -> String {
+    let mut abc = String::new();
+    // Writing into a `String` never fails
+    write_chord_into(&mut abc, chord, accidental).unwrap();
+
+    abc
+}
+
+/// Write a chord progression in ABC notation, as played in a key, with every chord in a bar of
+/// its own, preceded by its chord symbol. Accidentals are spelled after the key.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{abc::write_progression, prelude::{*, Note::*}};
+///
+/// let progression = "I - IV - V7 - I".parse::<ChordProgression>().unwrap();
+/// assert_eq!(
+///     write_progression(&progression, &Key::major(F3)).unwrap(),
+///     "\"F\"[F,A,C] | \"Bb\"[_B,DF] | \"C7\"[CEG_B] | \"F\"[F,A,C] |"
+/// );
+/// ```
+pub fn write_progression(progression: &ChordProgression, key: &Key) -> Result<String, Error> {
+    let accidental = key.accidental();
+    let mut abc = String::new();
+
+    // Writing into a `String` never fails
+    for (index, chord) in progression.realize(key)?.iter().enumerate() {
+        if index > 0 {
+            abc.push(' ');
+        }
+        if let Some(name) = chord.name(accidental) {
+            write!(abc, "\"{}\"", name).unwrap();
+        }
+        write_chord_into(&mut abc, chord, accidental).unwrap();
+        abc.push_str(" |");
+    }
+
+    Ok(abc)
+}
+
+/// Write a note in ABC notation into any `core::fmt::Write` implementor.
+fn write_note_into<W: Write>(f: &mut W, note: Note, accidental: FlatOrSharp) -> core::fmt::Result {
+    let mut name = String::new();
+    note.write_pitch_name(&mut name, accidental)?;
+    let mut chars = name.chars();
+    let tone = chars.next().ok_or(core::fmt::Error)?;
+
+    match chars.next() {
+        Some('#') => f.write_char('^')?,
+        Some('b') => f.write_char('_')?,
+        _ => (),
+    }
+    match note.octave() {
+        octave if octave >= 5 => {
+            f.write_char(tone.to_ascii_lowercase())?;
+            for _ in 5..octave {
+                f.write_char('\'')?;
+            }
+        }
+        octave => {
+            f.write_char(tone)?;
+            for _ in octave..4 {
+                f.write_char(',')?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a chord in ABC notation into any `core::fmt::Write` implementor.
+fn write_chord_into<W: Write>(
+    f: &mut W,
+    chord: &Chord,
+    accidental: FlatOrSharp,
+) -> core::fmt::Result {
+    if chord.notes().is_empty() {
+        return f.write_char('z');
+    }
+
+    f.write_char('[')?;
+    for note in chord.notes() {
+        write_note_into(f, *note, accidental)?;
+    }
+    f.write_char(']')
+}
@@ -0,0 +1,299 @@
+use crate::prelude::*;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+use libm::{sin, sqrtf};
+
+/// The relative amplitudes of the harmonics that every note is made of, starting with its
+/// fundamental.
+const HARMONICS: [f64; 3] = [1f64, 0.5f64, 0.25f64];
+
+/// How long it takes for a chord to fade in and out, in seconds, so that it does not click.
+const FADE_TIME: f64 = 0.005f64;
+
+/// The threshold under which the pitch detector takes a period as good enough, as suggested by
+/// the authors of YIN.
+const DEFAULT_THRESHOLD: f32 = 0.1f32;
+
+/// The lowest frequency in Hertz that the pitch detector looks for unless told otherwise, which is
+/// a bit below the lowest string of a bass guitar.
+const DEFAULT_MIN_FREQUENCY: f32 = 40f32;
+
+/// The highest frequency in Hertz that the pitch detector looks for unless told otherwise, which
+/// is a bit above the highest note of a piccolo.
+const DEFAULT_MAX_FREQUENCY: f32 = 4500f32;
+
+/// How loud (as the root mean square of its samples) a frame needs to be for a pitch to be looked
+/// for in it.
+const SILENCE_LEVEL: f32 = 0.001f32;
+
+/// Audio rendering of chords, for auditioning them without a synthesizer.
+impl Chord {
+    /// Render a chord as mono PCM audio, i.e. samples going from -1 to 1, for some duration in
+    /// seconds, into a buffer. Every note is rendered as a sine wave plus a couple of quieter
+    /// harmonics, and the chord fades in and out quickly so as to not click.
+    ///
+    /// Rendering stops at the end of the buffer if the duration does not fit in it. The samples
+    /// past the duration are left untouched. The number of samples rendered is returned.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[C4, E4, G4]);
+    /// let mut buffer = [2f32; 1000];
+    ///
+    /// // 100ms at 8kHz
+    /// let rendered = chord.render(8000, 0.1, &mut buffer);
+    /// assert_eq!(rendered, 800);
+    /// assert!(buffer[..800].iter().all(|sample| (-1. ..=1.).contains(sample)));
+    /// assert!(buffer[..800].iter().any(|sample| sample.abs() > 0.5));
+    /// assert_eq!(buffer[800], 2.);
+    ///
+    /// // Silence is silent
+    /// Chord::default().render(8000, 0.1, &mut buffer);
+    /// assert!(buffer[..800].iter().all(|sample| *sample == 0.));
+    /// ```
+    pub fn render(&self, sample_rate: u32, duration: f32, output: &mut [f32]) -> usize {
+        let sample_rate = f64::from(sample_rate);
+        let samples = ((f64::from(duration) * sample_rate) as usize).min(output.len());
+        let fade_samples = FADE_TIME * sample_rate;
+        if self.notes().is_empty() {
+            output[..samples].fill(0f32);
+            return samples;
+        }
+
+        // Keep the sum of all the harmonics of all the notes from going beyond 1
+        let gain = 1f64 / (self.notes().len() as f64 * HARMONICS.iter().sum::<f64>());
+
+        for (index, sample) in output[..samples].iter_mut().enumerate() {
+            let time = index as f64 / sample_rate;
+            let envelope = (index as f64 / fade_samples)
+                .min((samples - index) as f64 / fade_samples)
+                .min(1f64);
+
+            let value = self
+                .notes()
+                .iter()
+                .flat_map(|note| {
+                    HARMONICS
+                        .iter()
+                        .enumerate()
+                        .map(move |(harmonic, amplitude)| {
+                            (note.frequency_f64() * (harmonic + 1) as f64, amplitude)
+                        })
+                })
+                // Harmonics beyond the Nyquist frequency would alias into other pitches
+                .filter(|(frequency, _)| *frequency < sample_rate / 2f64)
+                .map(|(frequency, amplitude)| amplitude * sin(2f64 * PI * frequency * time))
+                .sum::<f64>();
+
+            *sample = (value * gain * envelope) as f32;
+        }
+
+        samples
+    }
+}
+
+/// Detects the pitch of monophonic audio (i.e. a single voice or line, such as singing, a flute or
+/// a guitar played note by note) using the YIN algorithm, frame by frame.
+///
+/// Frames need to be at least twice as long as the period of the lowest frequency looked for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PitchDetector {
+    sample_rate: u32,
+    threshold: f32,
+    min_frequency: f32,
+    max_frequency: f32,
+    last: Option<Note>,
+}
+
+/// Convenient methods for detecting pitches.
+impl PitchDetector {
+    /// Build a pitch detector for audio at some sample rate, looking for frequencies from 40Hz up
+    /// to 4.5kHz.
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            threshold: DEFAULT_THRESHOLD,
+            min_frequency: DEFAULT_MIN_FREQUENCY,
+            max_frequency: DEFAULT_MAX_FREQUENCY,
+            last: None,
+        }
+    }
+
+    /// Set the threshold (from 0 to 1) under which a period is taken as good enough. Lower values
+    /// miss more pitches in noisy audio, while higher ones make octave errors more likely.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+
+        self
+    }
+
+    /// Set the range of frequencies in Hertz to look for. Narrowing it down to the range of the
+    /// instrument makes detection faster and more reliable.
+    pub fn with_range(mut self, min_frequency: f32, max_frequency: f32) -> Self {
+        self.min_frequency = min_frequency;
+        self.max_frequency = max_frequency;
+
+        self
+    }
+
+    /// Detect the frequency in Hertz of the pitch in a frame of audio, if any.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::audio::PitchDetector;
+    ///
+    /// let sine = |frequency: f32| {
+    ///     (0..1024)
+    ///         .map(|index| (2. * std::f32::consts::PI * frequency * index as f32 / 8000.).sin())
+    ///         .collect::<Vec<f32>>()
+    /// };
+    ///
+    /// let detector = PitchDetector::new(8000);
+    /// let frequency = detector.detect_frequency(&sine(440.)).unwrap();
+    /// assert!((frequency - 440.).abs() < 1.);
+    ///
+    /// // Silence has no pitch
+    /// assert_eq!(detector.detect_frequency(&[0.; 1024]), None);
+    /// assert_eq!(detector.detect_frequency(&[]), None);
+    /// ```
+    pub fn detect_frequency(&self, frame: &[f32]) -> Option<f32> {
+        let level = level(frame);
+        if !level.is_finite() || level <= SILENCE_LEVEL {
+            return None;
+        }
+
+        let sample_rate = self.sample_rate as f32;
+        let window = frame.len() / 2;
+        let min_period = ((sample_rate / self.max_frequency) as usize).max(2);
+        let max_period =
+            ((sample_rate / self.min_frequency) as usize).min(window.saturating_sub(1));
+        if min_period >= max_period {
+            return None;
+        }
+
+        // Cumulative mean normalized difference of the frame with itself, delayed by every period
+        let mut differences = Vec::with_capacity(max_period + 1);
+        differences.push(1f32);
+        let mut running_sum = 0f32;
+        for period in 1..=max_period {
+            let difference = (0..window)
+                .map(|index| frame[index] - frame[index + period])
+                .map(|delta| delta * delta)
+                .sum::<f32>();
+            running_sum += difference;
+            differences.push(if running_sum > 0f32 {
+                difference * period as f32 / running_sum
+            } else {
+                1f32
+            });
+        }
+
+        // The first dip under the threshold, down to the bottom of it
+        let mut period =
+            (min_period..max_period).find(|period| differences[*period] < self.threshold)?;
+        while period + 1 < max_period && differences[period + 1] < differences[period] {
+            period += 1;
+        }
+
+        // Parabolic interpolation for a period in between samples
+        let (before, at, after) = (
+            differences[period - 1],
+            differences[period],
+            differences[period + 1],
+        );
+        let curvature = 2f32 * (before - 2f32 * at + after);
+        let shift = if curvature.abs() > f32::EPSILON {
+            (before - after) / curvature
+        } else {
+            0f32
+        };
+
+        Some(sample_rate / (period as f32 + shift))
+    }
+
+    /// Detect the note played in a frame of audio, if any, along with how far apart from it the
+    /// pitch is, in cents.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{audio::PitchDetector, prelude::Note::*};
+    ///
+    /// let frame = (0..2048)
+    ///     .map(|index| (2. * std::f32::consts::PI * 130. * index as f32 / 44100.).sin())
+    ///     .collect::<Vec<f32>>();
+    ///
+    /// let (note, cents) = PitchDetector::new(44100).detect(&frame).unwrap();
+    /// assert_eq!(note, C3);
+    /// assert!((cents + 10.8).abs() < 1.);
+    /// ```
+    pub fn detect(&self, frame: &[f32]) -> Option<(Note, f32)> {
+        self.detect_frequency(frame).map(Note::from_frequency)
+    }
+
+    /// Detect the note played in a frame of audio, and get the MIDI messages telling how it differs
+    /// from the note in the previous frame: a note off message for the previous note if it has
+    /// stopped, followed by a note on message for the new note if it has just started. This
+    /// bridges audio into the chord and arpeggio trackers. Velocity follows the loudness of the
+    /// frame.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{audio::PitchDetector, prelude::{*, Note::*}};
+    ///
+    /// let sine = |note: Note| {
+    ///     let frequency = note.frequency();
+    ///     (0..1024)
+    ///         .map(|index| (2. * std::f32::consts::PI * frequency * index as f32 / 8000.).sin())
+    ///         .collect::<Vec<f32>>()
+    /// };
+    ///
+    /// // A singer going through an A minor arpeggio, two frames per note
+    /// let mut detector = PitchDetector::new(8000);
+    /// let mut tracker = ArpeggioTracker::new(2000);
+    /// let mut chords = Vec::new();
+    /// for (index, note) in [A3, A3, C4, C4, E4, E4, A4, A4].iter().enumerate() {
+    ///     for message in detector.onset(&sine(*note)) {
+    ///         chords.extend(tracker.process_at(&message, index as u64 * 128));
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(chords.len(), 1);
+    /// assert_eq!(chords[0].name(FlatOrSharp::Sharp).unwrap(), "Am");
+    ///
+    /// // Notes are released as soon as they stop sounding, so chord trackers do not hold them
+    /// let mut detector = PitchDetector::new(8000);
+    /// let mut tracker = ChordTracker::new();
+    /// for frame in [sine(A3), sine(C4)].iter() {
+    ///     for message in detector.onset(frame) {
+    ///         tracker.process(&message);
+    ///     }
+    /// }
+    /// assert_eq!(tracker.held_notes(), [C4]);
+    ///
+    /// let messages = detector.onset(&[0.; 1024]).collect::<Vec<_>>();
+    /// assert_eq!(messages, [MidiMessage::note_off(0, C4)]);
+    /// tracker.process(&messages[0]);
+    /// assert_eq!(tracker.held_notes(), []);
+    /// ```
+    pub fn onset(&mut self, frame: &[f32]) -> impl Iterator<Item = MidiMessage> {
+        let detected = self.detect(frame).map(|(note, _)| note);
+        let stopped = self.last.filter(|note| detected != Some(*note));
+        let started = detected.filter(|note| self.last != Some(*note));
+        self.last = detected;
+
+        let level = level(frame);
+        let velocity = (level * 127f32 * 2f32).clamp(1f32, 127f32) as u8;
+
+        stopped
+            .map(|note| MidiMessage::note_off(0, note))
+            .into_iter()
+            .chain(started.map(|note| MidiMessage::note_on(0, note, velocity)))
+    }
+}
+
+/// Get how loud a frame of audio is, as the root mean square of its samples.
+fn level(frame: &[f32]) -> f32 {
+    sqrtf(frame.iter().map(|sample| sample * sample).sum::<f32>() / frame.len() as f32)
+}
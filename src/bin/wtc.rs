@@ -0,0 +1,469 @@
+//! `wtc` ("what the chord") reads chords from the standard input and prints their names, along
+//! with their Roman numerals and the keys that they are in, if asked to.
+//!
+//! By default, every line is a chord, as a whitespace-separated list of notes written either as
+//! MIDI key numbers (0-127) or in scientific pitch notation, e.g. `60 64 67` or `C4 E4 G4`.
+//!
+//! With `--midi`, the input is MIDI instead: either a Standard MIDI File, or a live stream of raw
+//! MIDI messages (e.g. from a keyboard). Notes are tracked as they start and stop sounding, and
+//! every chord is printed once it has been held for a little while, or as soon as some of its
+//! notes stop sounding in raw streams.
+//!
+//! Usage: `wtc [--flats | --sharps] [--midi] [--key KEY | --estimate-key]`
+//!
+//! Accidentals are written as sharps unless `--flats` is given. With `--key` (e.g. `--key Eb` or
+//! `--key F#m`), every chord is followed by its Roman numeral in that key, and notes are spelled
+//! after it. With `--estimate-key`, the key is estimated as chords come in instead, and a line
+//! with the new key is printed whenever it changes.
+
+use std::convert::TryFrom;
+use std::io::{self, Read, StdoutLock, Write};
+use std::process;
+use std::time::Instant;
+use whatthechord::{error::Error, prelude::*};
+
+/// How long, in milliseconds, the notes read from MIDI need to keep sounding to make a chord, so
+/// that rolled or slightly sloppy attacks are not reported note by note.
+const HOLD_TIME: u64 = 30;
+
+/// How much of the key profile is kept on every chord when estimating keys.
+const KEY_DECAY: f32 = 0.8;
+
+/// Microseconds per quarter note in Standard MIDI Files that do not set a tempo (i.e. 120 BPM).
+const DEFAULT_TEMPO: u64 = 500_000;
+
+/// What to read, and what to print about every chord.
+struct Options {
+    accidental: FlatOrSharp,
+    midi: bool,
+    key: Option<Key>,
+    estimate_key: bool,
+}
+
+fn main() {
+    let mut options = Options {
+        accidental: FlatOrSharp::Sharp,
+        midi: false,
+        key: None,
+        estimate_key: false,
+    };
+    let mut arguments = std::env::args().skip(1);
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--flats" => options.accidental = FlatOrSharp::Flat,
+            "--sharps" => options.accidental = FlatOrSharp::Sharp,
+            "--midi" => options.midi = true,
+            "--key" => match arguments.next().as_deref().map(parse_key) {
+                Some(Some(key)) => options.key = Some(key),
+                _ => fail("`--key` needs a key, such as `C`, `Eb` or `F#m`"),
+            },
+            "--estimate-key" => options.estimate_key = true,
+            "-h" | "--help" => {
+                println!("Usage: wtc [--flats | --sharps] [--midi] [--key KEY | --estimate-key]");
+                println!("Reads one chord per line from stdin, as MIDI key numbers or note names");
+                println!("(e.g. `60 64 67` or `C4 E4 G4`), and prints their names.");
+                println!();
+                println!("  --flats         write accidentals as flats (e.g. `Eb`)");
+                println!("  --sharps        write accidentals as sharps (e.g. `D#`), the default");
+                println!("  --midi          read a MIDI file or a raw MIDI stream instead");
+                println!("  --key KEY       print Roman numerals in a key (e.g. `Eb` or `F#m`)");
+                println!("  --estimate-key  estimate the key, printing it whenever it changes");
+                return;
+            }
+            _ => fail(&format!("unknown argument `{}`", argument)),
+        }
+    }
+    if options.key.is_some() && options.estimate_key {
+        fail("`--key` and `--estimate-key` cannot be used together");
+    }
+
+    let stdout = io::stdout();
+    let mut reporter = Reporter {
+        out: stdout.lock(),
+        keys: KeyTracker::new(KEY_DECAY),
+        options,
+    };
+    let result = if reporter.options.midi {
+        run_midi(&mut reporter)
+    } else {
+        run_lines(&mut reporter)
+    };
+    if let Err(error) = result {
+        eprintln!("wtc: {}", error);
+        process::exit(1);
+    }
+}
+
+/// Report a problem with the arguments and exit.
+fn fail(message: &str) -> ! {
+    eprintln!("wtc: {}", message);
+    process::exit(2);
+}
+
+/// Prints the name of every chord, along with its Roman numeral and key if asked to.
+struct Reporter<'a> {
+    out: StdoutLock<'a>,
+    keys: KeyTracker,
+    options: Options,
+}
+
+impl Reporter<'_> {
+    /// Print a line about a chord, preceded by a line about the key if it has just changed.
+    fn report(&mut self, chord: &Chord) -> Result<(), Error> {
+        if self.options.estimate_key {
+            if let Some(key) = self.keys.update_with_chord(chord) {
+                writeln!(self.out, "key: {}", key_name(&key))?;
+            }
+        }
+
+        let key = self.options.key.or_else(|| self.keys.current());
+        let name = match key {
+            Some(key) => chord.name(key),
+            None => chord.name(self.options.accidental),
+        };
+        write!(self.out, "{}", name.as_deref().unwrap_or("?"))?;
+        if let Some(key) = key {
+            match chord.roman_numeral(&key) {
+                Some(numeral) => write!(self.out, "\t{}", numeral)?,
+                None => write!(self.out, "\t?")?,
+            }
+        }
+        writeln!(self.out)?;
+
+        Ok(())
+    }
+}
+
+/// Name every chord read from stdin, one per line, reporting malformed lines without stopping.
+fn run_lines(reporter: &mut Reporter) -> Result<(), Error> {
+    let stdin = io::stdin();
+
+    for (number, line) in io::BufRead::lines(stdin.lock()).enumerate() {
+        let line = line?;
+        match parse_notes(&line) {
+            Ok(notes) => reporter.report(&Chord::from_notes(&notes))?,
+            Err(token) => eprintln!("wtc: line {}: invalid note `{}`", number + 1, token),
+        }
+    }
+
+    Ok(())
+}
+
+/// Name every chord played in the MIDI read from stdin, which is either a Standard MIDI File or a
+/// live stream of raw MIDI messages, timed as they arrive.
+fn run_midi(reporter: &mut Reporter) -> Result<(), Error> {
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    let mut tracker = ChordTracker::new().with_hold_time(HOLD_TIME);
+
+    let mut bytes = vec![0u8; 4096];
+    let mut header = Vec::new();
+    while header.len() < 4 {
+        match stdin.read(&mut bytes)? {
+            0 => break,
+            read => header.extend_from_slice(&bytes[..read]),
+        }
+    }
+
+    if header.starts_with(b"MThd") {
+        stdin.read_to_end(&mut header)?;
+        for (timestamp, message) in read_smf(&header)? {
+            play(reporter, &mut tracker, &message, timestamp)?;
+        }
+    } else {
+        let start = Instant::now();
+        let mut stream = MidiStream::default();
+        let mut chunk = header;
+        while !chunk.is_empty() {
+            let timestamp = start.elapsed().as_millis() as u64;
+            for byte in &chunk {
+                if let Some(message) = stream.push(*byte) {
+                    // Piped streams arrive all at once rather than in time, so chords are also
+                    // taken as complete as soon as some of their notes stop sounding
+                    if silences(&tracker, &message) {
+                        match tracker.poll(u64::MAX) {
+                            Some(chord) if !chord.is_silence() => reporter.report(&chord)?,
+                            _ => {}
+                        }
+                    }
+                    play(reporter, &mut tracker, &message, timestamp)?;
+                }
+            }
+            let read = stdin.read(&mut bytes)?;
+            chunk = bytes[..read].to_vec();
+        }
+    }
+
+    match tracker.poll(u64::MAX) {
+        Some(chord) if !chord.is_silence() => reporter.report(&chord),
+        _ => Ok(()),
+    }
+}
+
+/// Feed a MIDI message into the chord tracker, reporting the chord if it has just changed. Only
+/// the messages understood by the tracker are taken into account.
+fn play(
+    reporter: &mut Reporter,
+    tracker: &mut ChordTracker,
+    message: &[u8],
+    timestamp: u64,
+) -> Result<(), Error> {
+    let chord = if message.first() == Some(&0xF0) {
+        tracker.process_sysex(message).ok().flatten()
+    } else {
+        MidiMessage::try_from(message)
+            .ok()
+            .and_then(|message| tracker.process_at(&message, timestamp))
+    };
+
+    match chord {
+        Some(chord) if !chord.is_silence() => reporter.report(&chord),
+        _ => Ok(()),
+    }
+}
+
+/// Tell whether a MIDI message would make some of the notes sounding in a chord tracker stop.
+fn silences(tracker: &ChordTracker, message: &[u8]) -> bool {
+    let message = match MidiMessage::try_from(message) {
+        Ok(message) => message,
+        Err(_) => return false,
+    };
+    let mut after = tracker.clone();
+    after.process(&message);
+
+    after.sounding_notes().len() < tracker.sounding_notes().len()
+}
+
+/// Splits a raw stream of MIDI bytes into messages, honoring running status.
+#[derive(Default)]
+struct MidiStream {
+    status: Option<u8>,
+    message: Vec<u8>,
+}
+
+impl MidiStream {
+    /// Push the next byte in the stream, and get the message that it completes, if any.
+    fn push(&mut self, byte: u8) -> Option<Vec<u8>> {
+        match byte {
+            // Real time messages can show up anywhere, even inside of other messages
+            0xF8..=0xFF => return None,
+            0xF7 if self.status == Some(0xF0) => {
+                self.message.push(byte);
+                self.status = None;
+                return Some(core::mem::take(&mut self.message));
+            }
+            // Tune requests and undefined messages are of no interest
+            0xF4..=0xF7 => {
+                self.status = None;
+                return None;
+            }
+            0x80..=0xF3 => {
+                self.status = Some(byte);
+                self.message.clear();
+                self.message.push(byte);
+                return None;
+            }
+            _ => {}
+        }
+
+        let status = self.status?;
+        if self.message.is_empty() {
+            self.message.push(status);
+        }
+        self.message.push(byte);
+        if status == 0xF0 || self.message.len() < message_len(status) {
+            return None;
+        }
+
+        // System common messages do not set the running status
+        if status >= 0xF0 {
+            self.status = None;
+        }
+        Some(core::mem::take(&mut self.message))
+    }
+}
+
+/// Get how long a MIDI message is, status byte included, out of its status byte.
+fn message_len(status: u8) -> usize {
+    match status {
+        0xC0..=0xDF | 0xF1 | 0xF3 => 2,
+        _ => 3,
+    }
+}
+
+/// Read the channel and SysEx messages in a Standard MIDI File, merging all of its tracks, along
+/// with the time that they happen at, in milliseconds.
+fn read_smf(bytes: &[u8]) -> Result<Vec<(u64, Vec<u8>)>, Error> {
+    let mut chunks = Chunks(bytes);
+    let header = match chunks.next() {
+        Some((b"MThd", header)) if header.len() >= 6 => header,
+        _ => return Err(Error::InvalidMidiMessage),
+    };
+    let division = u16::from_be_bytes([header[4], header[5]]);
+
+    let mut events = Vec::new();
+    for (kind, track) in chunks {
+        if kind == b"MTrk" {
+            read_track(track, &mut events)?;
+        }
+    }
+    events.sort_by_key(|(tick, _)| *tick);
+
+    // Ticks are either a fraction of a quarter note, whose length follows the tempo, or a fraction
+    // of a SMPTE frame
+    let (ticks_per_quarter, mut tempo) = if division & 0x8000 == 0 {
+        (u64::from(division.max(1)), DEFAULT_TEMPO)
+    } else {
+        // The frame rate is stored negated
+        let frames = u64::from(((division >> 8) as u8).wrapping_neg());
+        let ticks = u64::from(division & 0xFF);
+        ((frames * ticks).max(1), 1_000_000)
+    };
+    let mut messages = Vec::new();
+    let (mut last_tick, mut microseconds) = (0u64, 0u64);
+    for (tick, event) in events {
+        microseconds += (tick - last_tick) * tempo / ticks_per_quarter;
+        last_tick = tick;
+        match event {
+            Event::Tempo(new_tempo) if division & 0x8000 == 0 => tempo = new_tempo,
+            Event::Tempo(_) => {}
+            Event::Message(message) => messages.push((microseconds / 1000, message)),
+        }
+    }
+
+    Ok(messages)
+}
+
+/// The events in Standard MIDI Files that matter for naming chords.
+enum Event {
+    /// The length of a quarter note changes, in microseconds.
+    Tempo(u64),
+    /// A channel or SysEx message, status byte included.
+    Message(Vec<u8>),
+}
+
+/// Read the events in a track of a Standard MIDI File, along with the tick that they happen at.
+fn read_track(mut track: &[u8], events: &mut Vec<(u64, Event)>) -> Result<(), Error> {
+    let mut tick = 0u64;
+    let mut running = None;
+    while !track.is_empty() {
+        tick += read_quantity(&mut track)?;
+        let status = match track.first() {
+            Some(byte) if *byte >= 0x80 => {
+                track = &track[1..];
+                *byte
+            }
+            _ => running.ok_or(Error::InvalidMidiMessage)?,
+        };
+
+        match status {
+            0xFF => {
+                let kind = take(&mut track, 1)?[0];
+                let len = read_quantity(&mut track)? as usize;
+                let data = take(&mut track, len)?;
+                if kind == 0x51 && len == 3 {
+                    let tempo = u32::from_be_bytes([0, data[0], data[1], data[2]]);
+                    events.push((tick, Event::Tempo(u64::from(tempo))));
+                }
+                running = None;
+            }
+            0xF0 | 0xF7 => {
+                let len = read_quantity(&mut track)? as usize;
+                let data = take(&mut track, len)?;
+                if status == 0xF0 {
+                    events.push((tick, Event::Message([&[0xF0], data].concat())));
+                }
+                running = None;
+            }
+            0x80..=0xEF => {
+                let data = take(&mut track, message_len(status) - 1)?;
+                events.push((tick, Event::Message([&[status], data].concat())));
+                running = Some(status);
+            }
+            _ => return Err(Error::InvalidMidiMessage),
+        }
+    }
+
+    Ok(())
+}
+
+/// Iterates over the chunks in a Standard MIDI File, as (kind, contents).
+struct Chunks<'a>(&'a [u8]);
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let kind = self.0.get(..4)?;
+        let len = match *self.0.get(4..8)? {
+            [a, b, c, d] => u32::from_be_bytes([a, b, c, d]) as usize,
+            _ => return None,
+        };
+        let contents = self.0.get(8..)?;
+        let contents = &contents[..len.min(contents.len())];
+        self.0 = &self.0[8 + contents.len()..];
+
+        Some((kind, contents))
+    }
+}
+
+/// Read a variable-length quantity from the beginning of some bytes, as used for times and lengths
+/// in Standard MIDI Files.
+fn read_quantity(bytes: &mut &[u8]) -> Result<u64, Error> {
+    let mut quantity = 0u64;
+    for _ in 0..4 {
+        let byte = take(bytes, 1)?[0];
+        quantity = quantity << 7 | u64::from(byte & 0x7F);
+        if byte < 0x80 {
+            return Ok(quantity);
+        }
+    }
+
+    Err(Error::InvalidMidiMessage)
+}
+
+/// Take some bytes from the beginning of others.
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], Error> {
+    if bytes.len() < len {
+        return Err(Error::InvalidMidiMessage);
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+
+    Ok(taken)
+}
+
+/// Parse a whitespace-separated list of MIDI key numbers or note names, returning the offending
+/// token if any of them is not valid.
+fn parse_notes(line: &str) -> Result<Vec<Note>, &str> {
+    line.split_whitespace()
+        .map(|token| match token.parse::<u8>() {
+            Ok(number) if number < 128 => Ok(Note::from(number)),
+            Ok(_) => Err(token),
+            Err(_) => token.parse::<Note>().map_err(|_| token),
+        })
+        .collect()
+}
+
+/// Parse a key written as its tonic, followed by "m" for minor keys (e.g. "Eb" or "F#m").
+fn parse_key(s: &str) -> Option<Key> {
+    let (tonic, mode) = match s.strip_suffix('m') {
+        Some(tonic) => (tonic, KeyMode::Minor),
+        None => (s, KeyMode::Major),
+    };
+    if tonic.ends_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let tonic = format!("{}4", tonic).parse::<Note>().ok()?;
+
+    Some(Key::new(tonic, mode))
+}
+
+/// Get the name of a key (e.g. "Eb major" or "F# minor"), spelling its tonic after the key itself.
+fn key_name(key: &Key) -> String {
+    let tonic = key.tonic().name(NameOptions::new(key).with_octave(false));
+    match key.mode() {
+        KeyMode::Major => format!("{} major", tonic),
+        KeyMode::Minor => format!("{} minor", tonic),
+    }
+}
@@ -0,0 +1,288 @@
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// Get the pitch classes (0-11, where 0 is C) shared by two chords, regardless of their octaves,
+/// sorted from C upwards.
+///
+/// Keeping common tones in the same voice while moving the rest of them as little as possible is
+/// the basis of smooth voice leading.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// // C major and A minor share C and E
+/// let c_major = Chord::from_notes(&[C4, E4, G4]);
+/// let a_minor = Chord::from_notes(&[A3, C4, E4]);
+/// assert_eq!(common_tones(&c_major, &a_minor), vec![0, 4]);
+///
+/// // C major and D major share nothing
+/// let d_major = Chord::from_notes(&[D4, FSharp4, A4]);
+/// assert!(common_tones(&c_major, &d_major).is_empty());
+/// ```
+pub fn common_tones(a: &Chord, b: &Chord) -> Vec<u8> {
+    a.notes()
+        .iter()
+        .map(|note| note.pitch_class())
+        .filter(|pitch_class| b.contains_pitch_class(*pitch_class))
+        .collect::<BTreeSet<u8>>()
+        .into_iter()
+        .collect()
+}
+
+/// The ways in which a chord can stand in for another one.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SubstitutionKind {
+    /// A major chord replaced by the minor chord a minor third below, with which it shares two
+    /// notes, e.g. Am for C.
+    RelativeMinor,
+    /// A minor chord replaced by the major chord a minor third above, with which it shares two
+    /// notes, e.g. C for Am.
+    RelativeMajor,
+    /// A dominant seventh replaced by the dominant seventh a tritone away, with which it shares
+    /// the third and the seventh, e.g. Db7 for G7.
+    Tritone,
+    /// A dominant seventh replaced by the diminished seventh built on its third, which works as a
+    /// passing chord into the same resolution, e.g. B°7 for G7.
+    DiminishedPassing,
+    /// The dominant of a key replaced by the dominant seventh built on the subtonic, which resolves
+    /// into the tonic through the "back door", e.g. Bb7 for G7 in C.
+    BackdoorDominant,
+}
+
+/// Text representations of the kinds of substitutions, explaining the relationship between the
+/// chords.
+impl Display for SubstitutionKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            SubstitutionKind::RelativeMinor => "relative minor",
+            SubstitutionKind::RelativeMajor => "relative major",
+            SubstitutionKind::Tritone => "tritone substitution",
+            SubstitutionKind::DiminishedPassing => "diminished passing chord",
+            SubstitutionKind::BackdoorDominant => "backdoor dominant",
+        })
+    }
+}
+
+/// A chord that can stand in for another one, together with how they relate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Substitution {
+    kind: SubstitutionKind,
+    chord: Chord,
+}
+
+/// Convenient methods for working with chord substitutions.
+impl Substitution {
+    /// Get how the substitute relates to the chord that it stands in for.
+    pub fn kind(&self) -> SubstitutionKind {
+        self.kind
+    }
+
+    /// Get the substitute chord.
+    pub fn chord(&self) -> &Chord {
+        &self.chord
+    }
+}
+
+/// List the common substitutes for a chord in a key, each of them with how it relates to the
+/// chord. Substitutes are built in root position, around the root of the chord.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let c_major = Key::major(C4);
+/// let names = |chord: &Chord| {
+///     substitutions(chord, &c_major)
+///         .iter()
+///         .map(|substitution| {
+///             let name = substitution.chord().name(FlatOrSharp::Flat).unwrap();
+///             format!("{} ({})", name, substitution.kind())
+///         })
+///         .collect::<Vec<_>>()
+/// };
+///
+/// let chord = Chord::from_notes(&[F3, A3, C4, E4]);
+/// assert_eq!(names(&chord), ["Dm7 (relative minor)"]);
+///
+/// let chord = Chord::from_notes(&[G3, B3, D4, F4]);
+/// assert_eq!(
+///     names(&chord),
+///     [
+///         "Db7 (tritone substitution)",
+///         "Bdim7 (diminished passing chord)",
+///         "Bb7 (backdoor dominant)",
+///     ]
+/// );
+///
+/// // The backdoor dominant only stands in for the dominant of the key
+/// let chord = Chord::from_notes(&[A3, CSharp4, E4, G4]);
+/// assert_eq!(substitutions(&chord, &c_major).len(), 2);
+/// ```
+pub fn substitutions(chord: &Chord, key: &Key) -> Vec<Substitution> {
+    use ChordType::*;
+
+    let root = match chord.root() {
+        Some(root) => root,
+        None => return Vec::new(),
+    };
+    let dominant = (key.tonic().pitch_class() + 7) % 12;
+
+    let candidates: Vec<(SubstitutionKind, i8, ChordType)> = match chord.chord_type() {
+        Triad(TriadQuality::Major) => vec![(
+            SubstitutionKind::RelativeMinor,
+            -3,
+            Triad(TriadQuality::Minor),
+        )],
+        Tetrad(TetradQuality::SeventhMajor) => vec![(
+            SubstitutionKind::RelativeMinor,
+            -3,
+            Tetrad(TetradQuality::SeventhMinor),
+        )],
+        Triad(TriadQuality::Minor) => vec![(
+            SubstitutionKind::RelativeMajor,
+            3,
+            Triad(TriadQuality::Major),
+        )],
+        Tetrad(TetradQuality::SeventhMinor) => vec![(
+            SubstitutionKind::RelativeMajor,
+            3,
+            Tetrad(TetradQuality::SeventhMajor),
+        )],
+        Tetrad(TetradQuality::SeventhDominant) => {
+            let mut candidates = vec![
+                (
+                    SubstitutionKind::Tritone,
+                    -6,
+                    Tetrad(TetradQuality::SeventhDominant),
+                ),
+                (
+                    SubstitutionKind::DiminishedPassing,
+                    4,
+                    Tetrad(TetradQuality::SeventhDiminished),
+                ),
+            ];
+            if root.pitch_class() == dominant {
+                candidates.push((
+                    SubstitutionKind::BackdoorDominant,
+                    3,
+                    Tetrad(TetradQuality::SeventhDominant),
+                ));
+            }
+
+            candidates
+        }
+        _ => Vec::new(),
+    };
+
+    candidates
+        .into_iter()
+        .filter_map(|(kind, half_tones, chord_type)| {
+            let chord = Chord::build(root.transposed(half_tones).ok()?, chord_type).ok()?;
+
+            Some(Substitution { kind, chord })
+        })
+        .collect()
+}
+
+/// The role that each note in a scale plays when the scale is played over a chord.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ToneRole {
+    /// A note that belongs to the chord itself.
+    ChordTone,
+    /// A note that does not belong to the chord, but can be added to it as a color tone (9ths,
+    /// 11ths and 13ths).
+    Tension,
+    /// A note that clashes with the chord, as it lies a half step above one of its chord tones
+    /// (e.g. the 4th over a major chord). The minor 9th over dominant sevenths is the exception,
+    /// as it is a tension.
+    Avoid,
+}
+
+/// The breakdown of the notes in a scale into chord tones, tensions and avoid notes, when the
+/// scale is played over a chord.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChordScaleAnalysis {
+    tones: Vec<(Note, ToneRole)>,
+}
+
+/// Convenient methods for analysing scales over chords.
+impl ChordScaleAnalysis {
+    /// Analyse the role of each of the notes in a scale when played over a chord. Notes are
+    /// compared by their pitch class, no matter their octaves.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[C3, E3, G3, B3]);
+    /// let analysis = ChordScaleAnalysis::new(&chord, &Scale::new(C4, ScaleKind::Ionian));
+    /// assert_eq!(analysis.chord_tones(), [C4, E4, G4, B4]);
+    /// assert_eq!(analysis.tensions(), [D4, A4]);
+    /// assert_eq!(analysis.avoid_notes(), [F4]);
+    ///
+    /// // The minor 9th is a tension over dominant sevenths, but the 4th is still avoided
+    /// let chord = Chord::from_notes(&[G3, B3, D4, F4]);
+    /// let analysis = ChordScaleAnalysis::new(&chord, &Scale::new(G3, ScaleKind::HalfWholeDiminished));
+    /// assert_eq!(analysis.tensions(), [GSharp3, ASharp3, CSharp4, E4]);
+    /// assert!(analysis.avoid_notes().is_empty());
+    /// ```
+    pub fn new(chord: &Chord, scale: &Scale) -> Self {
+        let has = |root: u8, half_tones: u8| chord.contains_pitch_class((root + half_tones) % 12);
+        let minor_ninth = chord
+            .root()
+            .map(|root| root.pitch_class())
+            .filter(|root| has(*root, 4) && has(*root, 10))
+            .map(|root| (root + 1) % 12);
+
+        let tones = scale
+            .notes()
+            .into_iter()
+            .map(|note| {
+                let pitch_class = note.pitch_class();
+                let role = if chord.contains_pitch_class(pitch_class) {
+                    ToneRole::ChordTone
+                } else if Some(pitch_class) != minor_ninth && has(pitch_class, 11) {
+                    ToneRole::Avoid
+                } else {
+                    ToneRole::Tension
+                };
+
+                (note, role)
+            })
+            .collect();
+
+        Self { tones }
+    }
+
+    /// Get every note in the scale, from lowest to highest, together with its role.
+    pub fn tones(&self) -> &[(Note, ToneRole)] {
+        &self.tones
+    }
+
+    /// Get the notes in the scale that belong to the chord.
+    pub fn chord_tones(&self) -> Vec<Note> {
+        self.with_role(ToneRole::ChordTone)
+    }
+
+    /// Get the notes in the scale that can be added to the chord as tensions.
+    pub fn tensions(&self) -> Vec<Note> {
+        self.with_role(ToneRole::Tension)
+    }
+
+    /// Get the notes in the scale that clash with the chord.
+    pub fn avoid_notes(&self) -> Vec<Note> {
+        self.with_role(ToneRole::Avoid)
+    }
+
+    /// Get the notes in the scale that play some role.
+    fn with_role(&self, role: ToneRole) -> Vec<Note> {
+        self.tones
+            .iter()
+            .filter(|(_, other)| *other == role)
+            .map(|(note, _)| *note)
+            .collect()
+    }
+}
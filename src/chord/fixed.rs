@@ -0,0 +1,217 @@
+use crate::error::Error;
+use crate::prelude::*;
+use core::fmt::{Display, Formatter, Write};
+
+/// A chord backed by an array of up to `N` notes, which can be identified and named without any
+/// allocation. This is convenient for embedded targets whose maximum polyphony is known at compile
+/// time.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FixedChord<const N: usize> {
+    notes: [Note; N],
+    len: usize,
+    chord_type: FixedChordType,
+    root: Option<Note>,
+}
+
+/// Convenient methods for working with fixed-size chords.
+impl<const N: usize> FixedChord<N> {
+    /// Build a fixed-size chord from a set of notes, failing if there are more than `N` distinct
+    /// notes in the set.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // Repeated notes only count once
+    /// let chord = FixedChord::<3>::from_notes(&[G1, C1, E1, C1]).unwrap();
+    /// assert_eq!(chord.notes(), &[C1, E1, G1]);
+    /// assert_eq!(chord.chord_type(), FixedChordType::Triad(TriadQuality::Major));
+    /// assert_eq!(chord.root(), Some(C1));
+    ///
+    /// // There is no room for a tetrad in a 3-notes chord
+    /// let chord = FixedChord::<3>::from_notes(&[C1, E1, G1, B1]);
+    /// assert_eq!(chord, Err(TooManyNotes));
+    /// ```
+    pub fn from_notes(notes: &[Note]) -> Result<Self, Error> {
+        // Insert the notes one by one, keeping them sorted and unique.
+        let mut sorted = [Note::CMinus1; N];
+        let mut len = 0;
+        for note in notes {
+            let position = match sorted[..len].binary_search(note) {
+                Ok(_) => continue,
+                Err(position) => position,
+            };
+            if len == N {
+                return Err(Error::TooManyNotes);
+            }
+            sorted.copy_within(position..len, position + 1);
+            sorted[position] = *note;
+            len += 1;
+        }
+
+        let notes = &sorted[..len];
+        let interval = |i: usize| notes[i + 1].midi_key_number() - notes[i].midi_key_number();
+
+        let (chord_type, root) = match len {
+            0 => (FixedChordType::Silence, None),
+            1 => (FixedChordType::SingleNote, Some(notes[0])),
+            2 => (
+                FixedChordType::Dyad(guess::dyad_quality(interval(0))),
+                Some(notes[0]),
+            ),
+            3 => {
+                let (quality, root) = guess::triad_quality([interval(0), interval(1)]);
+                (
+                    FixedChordType::Triad(quality),
+                    root.map(|position| notes[position]),
+                )
+            }
+            4 => {
+                let intervals = [interval(0), interval(1), interval(2)];
+                match guess::triad_in_tetrad(intervals) {
+                    // A triad plus some additional note
+                    Some([a, b, c]) => {
+                        let triad_intervals = [
+                            notes[b].midi_key_number() - notes[a].midi_key_number(),
+                            notes[c].midi_key_number() - notes[b].midi_key_number(),
+                        ];
+                        let (quality, root) = guess::triad_quality(triad_intervals);
+                        (
+                            FixedChordType::Triad(quality),
+                            root.map(|position| notes[[a, b, c][position]]),
+                        )
+                    }
+                    None => {
+                        let (quality, root) = guess::tetrad_quality(intervals);
+                        (
+                            FixedChordType::Tetrad(quality),
+                            root.map(|position| notes[position]),
+                        )
+                    }
+                }
+            }
+            5 => match (guess::pentad_quality(notes), guess::heptad_quality(notes)) {
+                ((quality, Some(root)), _) => (FixedChordType::Pentad(quality), Some(notes[root])),
+                (_, (quality, Some(root))) => (FixedChordType::Heptad(quality), Some(notes[root])),
+                ((quality, None), _) => (FixedChordType::Pentad(quality), None),
+            },
+            6 => match (guess::hexad_quality(notes), guess::heptad_quality(notes)) {
+                ((quality, Some(root)), _) => (FixedChordType::Hexad(quality), Some(notes[root])),
+                (_, (quality, Some(root))) => (FixedChordType::Heptad(quality), Some(notes[root])),
+                ((quality, None), _) => (FixedChordType::Hexad(quality), None),
+            },
+            7 => {
+                let (quality, root) = guess::heptad_quality(notes);
+                (
+                    FixedChordType::Heptad(quality),
+                    root.map(|position| notes[position]),
+                )
+            }
+            _ => (FixedChordType::Unknown, None),
+        };
+
+        Ok(Self {
+            notes: sorted,
+            len,
+            chord_type,
+            root,
+        })
+    }
+
+    /// Retrieve the notes in a chord, sorted from lowest to highest.
+    pub fn notes(&self) -> &[Note] {
+        &self.notes[..self.len]
+    }
+
+    /// Retrieve the type of a chord.
+    pub fn chord_type(&self) -> FixedChordType {
+        self.chord_type
+    }
+
+    /// Retrieve the root of a chord, if it could be identified.
+    pub fn root(&self) -> Option<Note> {
+        self.root
+    }
+
+    /// Write the musician-friendly name of a chord into any `core::fmt::Write` implementor (a
+    /// display buffer, a serial port, etc.), without allocating.
+    ///
+    /// Nothing gets written if the chord has no identifiable root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = FixedChord::<4>::from_notes(&[CSharp1, E1, GSharp1]).unwrap();
+    /// let mut name = String::new();
+    /// chord.write_name(&mut name, FlatOrSharp::Flat).unwrap();
+    /// assert_eq!(name, "Dbm");
+    ///
+    /// let options = NameOptions::new(FlatOrSharp::Flat)
+    ///     .with_convention(&Solfege)
+    ///     .with_accidental_style(AccidentalStyle::Unicode);
+    /// let mut name = String::new();
+    /// chord.write_name(&mut name, options).unwrap();
+    /// assert_eq!(name, "Re♭m");
+    /// ```
+    pub fn write_name<'a, W: Write>(
+        &self,
+        f: &mut W,
+        options: impl Into<NameOptions<'a>>,
+    ) -> core::fmt::Result {
+        match self.root {
+            Some(root) => super::write_name(f, root, &self.chord_type.into(), &options.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A default, empty chord with no notes, aka "silence"
+impl<const N: usize> Default for FixedChord<N> {
+    fn default() -> Self {
+        Self {
+            notes: [Note::CMinus1; N],
+            len: 0,
+            chord_type: FixedChordType::Silence,
+            root: None,
+        }
+    }
+}
+
+/// Text representation of fixed-size chords, using sharps for accidentals.
+impl<const N: usize> Display for FixedChord<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_name(f, NameOptions::default())
+    }
+}
+
+/// The types of chords that can be identified without allocating.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FixedChordType {
+    Dyad(DyadQuality),
+    Heptad(HeptadQuality),
+    Hexad(HexadQuality),
+    Pentad(PentadQuality),
+    Silence,
+    SingleNote,
+    Tetrad(TetradQuality),
+    Triad(TriadQuality),
+    Unknown,
+}
+
+/// Support for converting fixed-size chord types into regular chord types.
+impl From<FixedChordType> for ChordType {
+    fn from(chord_type: FixedChordType) -> Self {
+        match chord_type {
+            FixedChordType::Dyad(quality) => ChordType::Dyad(quality),
+            FixedChordType::Heptad(quality) => ChordType::Heptad(quality),
+            FixedChordType::Hexad(quality) => ChordType::Hexad(quality),
+            FixedChordType::Pentad(quality) => ChordType::Pentad(quality),
+            FixedChordType::Silence => ChordType::Silence,
+            FixedChordType::SingleNote => ChordType::SingleNote,
+            FixedChordType::Tetrad(quality) => ChordType::Tetrad(quality),
+            FixedChordType::Triad(quality) => ChordType::Triad(quality),
+            FixedChordType::Unknown => ChordType::Unknown,
+        }
+    }
+}
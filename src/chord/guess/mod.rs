@@ -1,3 +1,4 @@
+use crate::chord::{added_offsets, TENSIONS};
 use crate::prelude::*;
 use alloc::collections::BTreeSet;
 use alloc::vec::Vec;
@@ -104,13 +105,13 @@ pub fn intervals(notes: &[Note]) -> Vec<u8> {
     intervals
 }
 
-/// Extract information about a dyad (a set of two notes).
-pub(crate) fn dyad(notes: Vec<Note>, intervals: Vec<u8>) -> Chord {
+/// Find out the quality of a dyad from the interval between its two notes.
+pub(crate) fn dyad_quality(interval: u8) -> DyadQuality {
     use DyadQuality::*;
 
-    let dyad_type = match intervals[1] {
+    match interval {
         0 => Perfect(0),      // P1  d2
-        1 => Augmented(1),    // A1  m2
+        1 => Minor(2),        // m2  A1
         2 => Major(2),        // M2  d3
         3 => Minor(3),        // m3  A2
         4 => Major(3),        // M3  d4
@@ -121,7 +122,7 @@ pub(crate) fn dyad(notes: Vec<Note>, intervals: Vec<u8>) -> Chord {
         9 => Major(6),        // M6  d7
         10 => Minor(7),       // m7  A6
         11 => Major(7),       // M7  d8
-        12 => Diminished(9),  // d9
+        12 => Perfect(8),     // P8  d9
         13 => Minor(9),       // m9  A8
         14 => Major(9),       // M9  d10
         15 => Minor(10),      // m10 A9
@@ -136,36 +137,27 @@ pub(crate) fn dyad(notes: Vec<Note>, intervals: Vec<u8>) -> Chord {
         24 => Perfect(15),    // P15 A14
         25 => Augmented(15),  // A15
         _ => Indeterminate,
-    };
-
-    let root = notes.get(0).cloned();
-
-    Chord {
-        intervals,
-        chord_type: ChordType::Dyad(dyad_type),
-        notes,
-        root,
-        additions: None,
     }
 }
 
-/// Extract information about a triad (a set of three notes).
-pub(crate) fn triad(notes: &[Note], intervals: &[u8]) -> Chord {
-    use super::TriadQuality::*;
+/// Find out the quality of a triad from its two intervals, together with the position of its root
+/// among its three notes.
+pub(crate) fn triad_quality(intervals: [u8; 2]) -> (TriadQuality, Option<usize>) {
+    use TriadQuality::*;
 
     // Interval between the topmost note and the first inversion of the root
-    let complementary_interval = 12 - intervals[0] - intervals[1];
+    let complementary_interval = 12u8.saturating_sub(intervals[0] + intervals[1]);
 
     // In each iteration of the loop, we try to match the intervals against different inversions
     // of the intervals associated to each triad quality.
     let mut root_guess = 0;
-    let quality = loop {
+    loop {
         // Try one inversion each time, looking for the natural intervals / root position
         let natural_interval = match root_guess {
             0 => (intervals[0], intervals[1]),           // Root position
             1 => (intervals[1], complementary_interval), // 2nd inversion
             2 => (complementary_interval, intervals[0]), // 1st inversion
-            _ => break Indeterminate,
+            _ => break (Indeterminate, None),
         };
 
         // Find the quality that matches our root position guess
@@ -180,75 +172,25 @@ pub(crate) fn triad(notes: &[Note], intervals: &[u8]) -> Chord {
         };
 
         if quality != Indeterminate {
-            break quality;
+            break (quality, Some(root_guess));
         } else {
             root_guess += 1;
         }
-    };
-
-    let root = Some(notes[root_guess]);
-
-    Chord {
-        intervals: Vec::from(intervals),
-        chord_type: ChordType::Triad(quality),
-        notes: Vec::from(notes),
-        root,
-        additions: None,
     }
 }
 
-/// Extract information about a triad (a set of four notes).
-pub(crate) fn tetrad(notes: &[Note], intervals: &[u8]) -> Chord {
-    use super::TetradQuality::*;
-
-    // Try to identify tetrads that are actually a triad plus additions or a couple of dyads.
-    match intervals[0..3] {
-        // Additional bass tone
-        [bass, third, fifth] if bass % 12 == 0 => {
-            let intervals = vec![third, fifth];
-            let mut chord = triad(&notes[1..], &intervals);
-            chord.additions = Some(guess::additions(&notes, &intervals));
-            chord.notes = Vec::from(notes);
-
-            return chord;
-        }
-        // Additional overtone
-        [third, fifth, add] if (third + fifth + add) % 12 == 0 => {
-            let intervals = vec![third, fifth];
-            let mut chord = triad(&notes[..2], &intervals);
-            chord.additions = Some(guess::additions(&notes, &intervals));
-            chord.notes = Vec::from(notes);
-
-            return chord;
-        }
-        // Additional second: an added second breaks the third interval into two seconds.
-        [second, third, fifth] if second + third == 3 || second + third == 4 => {
-            let intervals = vec![second + third, fifth];
-            let mut chord = triad(&notes, &intervals);
-            chord.additions = Some(guess::additions(&notes, &intervals));
-            chord.notes = Vec::from(notes);
-
-            return chord;
-        }
-        // Additional fourth: an added fourth breaks the fifth interval into two seconds.
-        [third, fourth, fifth] if fifth + fourth == 3 || fifth + fourth == 4 => {
-            let intervals = vec![third, fourth + fifth];
-            let mut chord = triad(&notes, &intervals);
-            chord.additions = Some(guess::additions(&notes, &intervals));
-            chord.notes = Vec::from(notes);
-
-            return chord;
-        }
-        _ => {}
-    }
+/// Find out the quality of a tetrad from its three intervals, together with the position of its
+/// root among its four notes.
+pub(crate) fn tetrad_quality(intervals: [u8; 3]) -> (TetradQuality, Option<usize>) {
+    use TetradQuality::*;
 
     // Interval between the topmost note and the first inversion of the root
-    let complementary_interval = 12 - intervals[0] - intervals[1] - intervals[2];
+    let complementary_interval = 12u8.saturating_sub(intervals[0] + intervals[1] + intervals[2]);
 
     // In each iteration of the loop, we try to match the intervals against different inversions
     // of the intervals associated to each tetrad quality.
     let mut root_guess = 0;
-    let (quality, root_position) = loop {
+    loop {
         // Try one inversion each time, looking for the natural intervals / root position
         let natural_interval = match root_guess {
             0 => (intervals[0], intervals[1], intervals[2]), // Root position
@@ -271,6 +213,12 @@ pub(crate) fn tetrad(notes: &[Note], intervals: &[u8]) -> Chord {
             (3, 3, 5) => SeventhDiminishedMajor,
             (4, 2, 4) => SeventhDominantFlatFive,
             (4, 2, 5) => SeventhMajorFlatFive,
+            (5, 2, 3) => SeventhSuspendedFourth,
+            (2, 5, 3) => SeventhSuspendedSecond,
+            // Sixth chords share their notes with the relative minor seventh or half diminished
+            // seventh chords, so they are only preferred when the bass is the root
+            (4, 3, 2) if root_guess == 0 => Sixth,
+            (3, 4, 2) if root_guess == 0 => SixthMinor,
             _ => Indeterminate,
         };
 
@@ -279,15 +227,550 @@ pub(crate) fn tetrad(notes: &[Note], intervals: &[u8]) -> Chord {
         } else {
             root_guess += 1;
         }
+    }
+}
+
+/// Find out whether the intervals of a tetrad actually describe a triad plus an additional note,
+/// returning the positions of the three notes that make up the triad.
+pub(crate) fn triad_in_tetrad(intervals: [u8; 3]) -> Option<[usize; 3]> {
+    match intervals {
+        // Additional bass tone
+        [bass, _, _] if bass % 12 == 0 => Some([1, 2, 3]),
+        // Additional overtone
+        [third, fifth, add] if (third + fifth + add) % 12 == 0 => Some([0, 1, 2]),
+        // Additional second: an added second breaks the third interval into two seconds.
+        [second, third, _] if second + third == 3 || second + third == 4 => Some([0, 2, 3]),
+        // Additional fourth: an added fourth breaks the fifth interval into two seconds.
+        [_, fourth, fifth] if fifth + fourth == 3 || fifth + fourth == 4 => Some([0, 1, 3]),
+        _ => None,
+    }
+}
+
+/// Every pentad quality that can be recognized.
+const PENTAD_QUALITIES: [PentadQuality; 7] = [
+    PentadQuality::Ninth,
+    PentadQuality::NinthMajor,
+    PentadQuality::NinthMinor,
+    PentadQuality::SeventhFlatNinth,
+    PentadQuality::SeventhSharpNinth,
+    PentadQuality::SixNinth,
+    PentadQuality::SixNinthMinor,
+];
+
+/// Find out the quality of a pentad from its notes, together with the position of its root among
+/// them.
+pub(crate) fn pentad_quality(notes: &[Note]) -> (PentadQuality, Option<usize>) {
+    match match_pitch_classes(notes, &PENTAD_QUALITIES, PentadQuality::semitones_from_root) {
+        Some((quality, root)) => (quality, Some(root)),
+        None => (PentadQuality::Indeterminate, None),
+    }
+}
+
+/// Every hexad quality that can be recognized.
+const HEXAD_QUALITIES: [HexadQuality; 4] = [
+    HexadQuality::Eleventh,
+    HexadQuality::EleventhMajor,
+    HexadQuality::EleventhMinor,
+    HexadQuality::SeventhMajorSharpEleventh,
+];
+
+/// Find out the quality of a hexad from its notes, together with the position of its root among
+/// them.
+pub(crate) fn hexad_quality(notes: &[Note]) -> (HexadQuality, Option<usize>) {
+    match match_pitch_classes(notes, &HEXAD_QUALITIES, HexadQuality::semitones_from_root) {
+        Some((quality, root)) => (quality, Some(root)),
+        None => (HexadQuality::Indeterminate, None),
+    }
+}
+
+/// Every heptad quality that can be recognized.
+const HEPTAD_QUALITIES: [HeptadQuality; 3] = [
+    HeptadQuality::Thirteenth,
+    HeptadQuality::ThirteenthMajor,
+    HeptadQuality::ThirteenthMinor,
+];
+
+/// Semitone offsets of the tones that are commonly left out when voicing thirteenth chords: the 5th
+/// and the 11th.
+const HEPTAD_OMITTABLE: [u8; 2] = [7, 17];
+
+/// Find out the quality of a heptad from its notes, together with the position of its root among
+/// them. The 5th and the 11th may be missing, so this also applies to five and six notes.
+pub(crate) fn heptad_quality(notes: &[Note]) -> (HeptadQuality, Option<usize>) {
+    match match_pitch_classes_omitting(
+        notes,
+        &HEPTAD_QUALITIES,
+        HeptadQuality::semitones_from_root,
+        &HEPTAD_OMITTABLE,
+    ) {
+        Some((quality, root)) => (quality, Some(root)),
+        None => (HeptadQuality::Indeterminate, None),
+    }
+}
+
+/// Match the pitch classes in a set of notes against those of a list of qualities, trying each of
+/// the notes as the root (starting from the bass) and returning the first matching quality together
+/// with the position of its root.
+///
+/// This is octave-agnostic, so it is useful for extended chords, whose tones are spread across
+/// more than one octave.
+pub(crate) fn match_pitch_classes<Q, F>(
+    notes: &[Note],
+    qualities: &[Q],
+    semitones: F,
+) -> Option<(Q, usize)>
+where
+    Q: Copy,
+    F: Fn(&Q) -> &'static [u8],
+{
+    match_pitch_classes_omitting(notes, qualities, semitones, &[])
+}
+
+/// Same as `match_pitch_classes`, but tolerating the absence of any of the tones whose semitone
+/// offsets from the root are listed as omittable.
+pub(crate) fn match_pitch_classes_omitting<Q, F>(
+    notes: &[Note],
+    qualities: &[Q],
+    semitones: F,
+    omittable: &[u8],
+) -> Option<(Q, usize)>
+where
+    Q: Copy,
+    F: Fn(&Q) -> &'static [u8],
+{
+    // Pitch class sets are represented as 12-bit masks, relative to the root
+    let mask = |offsets: &mut dyn Iterator<Item = u8>| {
+        offsets.fold(0u16, |mask, offset| mask | 1 << (offset % 12))
     };
+    let omittable = mask(&mut omittable.iter().cloned());
+
+    notes.iter().enumerate().find_map(|(position, root)| {
+        let root = root.pitch_class();
+        let notes_mask = mask(&mut notes.iter().map(|note| note.pitch_class() + 12 - root));
+
+        qualities
+            .iter()
+            .find(|quality| {
+                let quality_mask = mask(&mut semitones(quality).iter().cloned());
+
+                notes_mask & !quality_mask == 0 && quality_mask & !omittable & !notes_mask == 0
+            })
+            .map(|quality| (*quality, position))
+    })
+}
+
+/// Extract information about a dyad (a set of two notes).
+pub(crate) fn dyad(notes: Vec<Note>, intervals: Vec<u8>) -> Chord {
+    let dyad_type = dyad_quality(intervals[0]);
+    let root = notes.first().cloned();
+
+    Chord {
+        intervals,
+        chord_type: ChordType::Dyad(dyad_type),
+        notes,
+        root,
+        additions: None,
+        omissions: None,
+    }
+}
 
+/// Extract information about a triad (a set of three notes).
+pub(crate) fn triad(notes: &[Note], intervals: &[u8]) -> Chord {
+    let (quality, root_position) = triad_quality([intervals[0], intervals[1]]);
     let root = root_position.map(|position| notes[position]);
 
     Chord {
         intervals: Vec::from(intervals),
+        chord_type: ChordType::Triad(quality),
+        notes: Vec::from(notes),
+        root,
+        additions: None,
+        omissions: None,
+    }
+}
+
+/// Extract information about a tetrad (a set of four notes).
+pub(crate) fn tetrad(notes: &[Note], intervals: &[u8]) -> Chord {
+    let chord = closed_tetrad(notes, intervals);
+
+    match chord.chord_type {
+        ChordType::Tetrad(TetradQuality::Indeterminate)
+        | ChordType::Triad(TriadQuality::Indeterminate) => spread_tetrad(notes).unwrap_or(chord),
+        _ => chord,
+    }
+}
+
+/// Recognize tetrads in spread voicings, that is, spanning more than an octave, by collapsing
+/// their notes into closed position right above their bass.
+///
+/// The resulting chord keeps the original notes, and its root and additions are mapped back into
+/// them.
+fn spread_tetrad(notes: &[Note]) -> Option<Chord> {
+    let bass = *notes.first()?;
+    let top = *notes.last()?;
+    if bass.interval_to(top).octaves() == 0 {
+        return None;
+    }
+
+    // Distance of every note in the chord above the bass, once octaves are taken out
+    let distances = notes
+        .iter()
+        .map(|note| bass.interval_to(*note).simple().semitones())
+        .collect::<BTreeSet<u8>>();
+    if distances.len() != notes.len() {
+        return None;
+    }
+
+    let closed_notes = distances
+        .iter()
+        .map(|distance| bass.transposed(*distance as i8).ok())
+        .collect::<Option<Vec<Note>>>()?;
+    let closed_intervals = guess::intervals(&closed_notes);
+    let mut chord = closed_tetrad(&closed_notes, &closed_intervals);
+
+    match chord.chord_type {
+        ChordType::Tetrad(TetradQuality::Indeterminate)
+        | ChordType::Triad(TriadQuality::Indeterminate) => None,
+        _ => {
+            let original = |closed: Note| {
+                notes
+                    .iter()
+                    .find(|note| note.pitch_class() == closed.pitch_class())
+                    .cloned()
+            };
+            chord.root = chord.root.and_then(original);
+            chord.additions = chord
+                .additions
+                .map(|additions| additions.into_iter().filter_map(original).collect());
+            chord.notes = Vec::from(notes);
+
+            Some(chord)
+        }
+    }
+}
+
+/// Extract information about a tetrad whose notes are in closed position.
+fn closed_tetrad(notes: &[Note], intervals: &[u8]) -> Chord {
+    let intervals = [intervals[0], intervals[1], intervals[2]];
+
+    // Try to identify tetrads that are actually a triad plus additions. Only tensions can be
+    // added, so if the note left out of the triad is not one, any other triad is tried instead.
+    if let Some(positions) = triad_in_tetrad(intervals) {
+        let others = [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]];
+        let added_triad = core::iter::once(positions)
+            .chain(others.iter().copied().filter(|other| *other != positions))
+            .find_map(|positions| {
+                let triad_notes = positions
+                    .iter()
+                    .map(|position| notes[*position])
+                    .collect::<Vec<_>>();
+                let triad_intervals = guess::intervals(&triad_notes);
+                let mut chord = triad(&triad_notes, &triad_intervals);
+                chord.additions = Some(guess::additions(notes, &triad_intervals));
+                chord.notes = Vec::from(notes);
+
+                let is_known = chord.chord_type != ChordType::Triad(TriadQuality::Indeterminate);
+                Some(chord).filter(|chord| is_known && adds_tensions_only(chord))
+            });
+        if let Some(chord) = added_triad {
+            return chord;
+        }
+    }
+
+    let (quality, root_position) = tetrad_quality(intervals);
+    let root = root_position.map(|position| notes[position]);
+
+    Chord {
+        intervals: Vec::from(intervals.as_ref()),
         chord_type: ChordType::Tetrad(quality),
         notes: Vec::from(notes),
         root,
         additions: None,
+        omissions: None,
     }
 }
+
+/// Extract information about a pentad (a set of five notes).
+pub(crate) fn pentad(notes: &[Note], intervals: &[u8]) -> Chord {
+    let (chord_type, root_position) = match (pentad_quality(notes), heptad_quality(notes)) {
+        ((quality, Some(root)), _) => (ChordType::Pentad(quality), Some(root)),
+        // A thirteenth chord without its 5th nor its 11th
+        (_, (quality, Some(root))) => (ChordType::Heptad(quality), Some(root)),
+        ((quality, None), _) => (ChordType::Pentad(quality), None),
+    };
+    let root = root_position.map(|position| notes[position]);
+
+    Chord {
+        intervals: Vec::from(intervals),
+        chord_type,
+        notes: Vec::from(notes),
+        root,
+        additions: None,
+        omissions: None,
+    }
+}
+
+/// Extract information about a hexad (a set of six notes).
+pub(crate) fn hexad(notes: &[Note], intervals: &[u8]) -> Chord {
+    let (chord_type, root_position) = match (hexad_quality(notes), heptad_quality(notes)) {
+        ((quality, Some(root)), _) => (ChordType::Hexad(quality), Some(root)),
+        // A thirteenth chord without its 5th or its 11th
+        (_, (quality, Some(root))) => (ChordType::Heptad(quality), Some(root)),
+        ((quality, None), _) => (ChordType::Hexad(quality), None),
+    };
+    let root = root_position.map(|position| notes[position]);
+
+    Chord {
+        intervals: Vec::from(intervals),
+        chord_type,
+        notes: Vec::from(notes),
+        root,
+        additions: None,
+        omissions: None,
+    }
+}
+
+/// Extract information about a heptad (a set of seven notes).
+pub(crate) fn heptad(notes: &[Note], intervals: &[u8]) -> Chord {
+    let (quality, root_position) = heptad_quality(notes);
+    let root = root_position.map(|position| notes[position]);
+
+    Chord {
+        intervals: Vec::from(intervals),
+        chord_type: ChordType::Heptad(quality),
+        notes: Vec::from(notes),
+        root,
+        additions: None,
+        omissions: None,
+    }
+}
+
+/// Try to split a set of six to eight notes into two stacked chords (a polychord), each of them a
+/// complete triad or tetrad of a known quality, and with no pitch classes in common.
+pub(crate) fn polychord(notes: &[Note], intervals: &[u8]) -> Option<Chord> {
+    if !(6..=8).contains(&notes.len()) {
+        return None;
+    }
+
+    let is_stackable = |chord: &Chord| {
+        chord.additions.is_none()
+            && chord.omissions.is_none()
+            && match chord.chord_type {
+                ChordType::Triad(quality) => quality != TriadQuality::Indeterminate,
+                ChordType::Tetrad(quality) => quality != TetradQuality::Indeterminate,
+                _ => false,
+            }
+    };
+
+    (3..=notes.len() - 3).find_map(|split| {
+        let lower = Chord::from_notes(&notes[..split]);
+        let upper = Chord::from_notes(&notes[split..]);
+        let shares_tones = upper
+            .notes
+            .iter()
+            .any(|note| lower.contains_pitch_class(note.pitch_class()));
+
+        if is_stackable(&lower) && is_stackable(&upper) && !shares_tones {
+            Some(Chord {
+                intervals: Vec::from(intervals),
+                root: lower.root,
+                chord_type: ChordType::Complex(vec![lower, upper]),
+                notes: Vec::from(notes),
+                additions: None,
+                omissions: None,
+            })
+        } else {
+            None
+        }
+    })
+}
+
+/// Try to read a set of notes as a voicing of a chord whose root is not played, by adding the
+/// root (and, for shell voicings, the fifth) right below the bass and looking for a chord of a
+/// known quality built on that root.
+pub(crate) fn rootless(notes: &[Note], intervals: &[u8], root: Note) -> Option<Chord> {
+    let bass = notes.first()?;
+    let below = (bass.pitch_class() + 12 - root.pitch_class()) % 12;
+    let root = match below {
+        0 => *bass,
+        below => bass.transposed(-(below as i8)).ok()?,
+    };
+    let fifth = root.transposed(7).ok()?;
+
+    [vec![root], vec![root, fifth]].iter().find_map(|missing| {
+        let mut extended = Vec::from(notes);
+        extended.extend_from_slice(missing);
+        let guess = Chord::from_notes(&extended);
+
+        match (guess.root, guess.chord_type) {
+            (_, ChordType::Dyad(_)) | (_, ChordType::Complex(_)) => None,
+            (Some(guessed), chord_type)
+                if guessed.pitch_class() == root.pitch_class() && guess.additions.is_none() =>
+            {
+                Some(Chord {
+                    intervals: Vec::from(intervals),
+                    chord_type,
+                    root: Some(root),
+                    notes: Vec::from(notes),
+                    additions: None,
+                    omissions: None,
+                })
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Try to read a set of two to four notes as a chord that is missing its perfect fifth, by adding
+/// the fifth above every note in turn and looking for a chord of a known quality built on that
+/// note. Lower notes are preferred as roots.
+pub(crate) fn omitted_fifth(notes: &[Note], intervals: &[u8]) -> Option<Chord> {
+    if !(2..=4).contains(&notes.len()) {
+        return None;
+    }
+
+    notes.iter().find_map(|root| {
+        let fifth = root.transposed(7).ok()?;
+        if notes
+            .iter()
+            .any(|note| note.pitch_class() == fifth.pitch_class())
+        {
+            return None;
+        }
+
+        let mut extended = Vec::from(notes);
+        extended.push(fifth);
+        let guess = Chord::from_notes(&extended);
+
+        match (guess.root, guess.chord_type) {
+            (_, ChordType::Dyad(_)) | (_, ChordType::Complex(_)) => None,
+            (Some(guessed), chord_type)
+                if guessed.pitch_class() == root.pitch_class() && guess.additions.is_none() =>
+            {
+                Some(Chord {
+                    intervals: Vec::from(intervals),
+                    chord_type,
+                    root: Some(*root),
+                    notes: Vec::from(notes),
+                    additions: None,
+                    omissions: Some(vec![7]),
+                })
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Tell whether every note added to a chord, other than those doubling a chord tone, is a tension.
+fn adds_tensions_only(chord: &Chord) -> bool {
+    added_offsets(chord) & !TENSIONS == 0
+}
+
+/// Every chord type of a known quality that is considered when scoring how well a set of notes
+/// matches each possible chord.
+const SCORED_CHORD_TYPES: [ChordType; 35] = [
+    ChordType::Triad(TriadQuality::Major),
+    ChordType::Triad(TriadQuality::Minor),
+    ChordType::Triad(TriadQuality::Diminished),
+    ChordType::Triad(TriadQuality::Augmented),
+    ChordType::Triad(TriadQuality::Suspended(4)),
+    ChordType::Triad(TriadQuality::Suspended(2)),
+    ChordType::Tetrad(TetradQuality::SeventhDominant),
+    ChordType::Tetrad(TetradQuality::SeventhMajor),
+    ChordType::Tetrad(TetradQuality::SeventhMinor),
+    ChordType::Tetrad(TetradQuality::SeventhHalfDiminished),
+    ChordType::Tetrad(TetradQuality::SeventhDiminished),
+    ChordType::Tetrad(TetradQuality::SeventhMinorMajor),
+    ChordType::Tetrad(TetradQuality::SeventhAugmented),
+    ChordType::Tetrad(TetradQuality::SeventhAugmentedMajor),
+    ChordType::Tetrad(TetradQuality::SeventhDominantFlatFive),
+    ChordType::Tetrad(TetradQuality::SeventhMajorFlatFive),
+    ChordType::Tetrad(TetradQuality::SeventhDiminishedMajor),
+    ChordType::Tetrad(TetradQuality::Sixth),
+    ChordType::Tetrad(TetradQuality::SixthMinor),
+    ChordType::Tetrad(TetradQuality::SeventhSuspendedFourth),
+    ChordType::Tetrad(TetradQuality::SeventhSuspendedSecond),
+    ChordType::Pentad(PentadQuality::Ninth),
+    ChordType::Pentad(PentadQuality::NinthMajor),
+    ChordType::Pentad(PentadQuality::NinthMinor),
+    ChordType::Pentad(PentadQuality::SeventhFlatNinth),
+    ChordType::Pentad(PentadQuality::SeventhSharpNinth),
+    ChordType::Pentad(PentadQuality::SixNinth),
+    ChordType::Pentad(PentadQuality::SixNinthMinor),
+    ChordType::Hexad(HexadQuality::Eleventh),
+    ChordType::Hexad(HexadQuality::EleventhMajor),
+    ChordType::Hexad(HexadQuality::EleventhMinor),
+    ChordType::Hexad(HexadQuality::SeventhMajorSharpEleventh),
+    ChordType::Heptad(HeptadQuality::Thirteenth),
+    ChordType::Heptad(HeptadQuality::ThirteenthMajor),
+    ChordType::Heptad(HeptadQuality::ThirteenthMinor),
+];
+
+/// Score how well a set of sorted, unique notes matches every chord of a known quality built on
+/// any of its notes, returning the candidate chords together with their confidence, from the
+/// most to the least likely. Extra notes become additions, and missing chord tones become
+/// omissions. Candidates rooted on the bass win ties.
+pub(crate) fn scored(notes: &[Note], intervals: &[u8]) -> Vec<(Chord, f32)> {
+    let mut roots = Vec::<Note>::new();
+    for note in notes {
+        if !roots
+            .iter()
+            .any(|root| root.pitch_class() == note.pitch_class())
+        {
+            roots.push(*note);
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for root in roots {
+        for chord_type in SCORED_CHORD_TYPES.iter() {
+            let tones = chord_type.semitones_from_root();
+            let offset = |note: &Note| (note.pitch_class() + 12 - root.pitch_class()) % 12;
+            let additions = notes
+                .iter()
+                .filter(|note| !tones.iter().any(|tone| tone % 12 == offset(note)))
+                .cloned()
+                .collect::<Vec<Note>>();
+            let omissions = tones
+                .iter()
+                .filter(|tone| !notes.iter().any(|note| offset(note) == *tone % 12))
+                .cloned()
+                .collect::<Vec<u8>>();
+
+            // At least a couple of chord tones need to be there for the chord to be heard at all
+            if tones.len() < omissions.len() + 2 {
+                continue;
+            }
+
+            let chord = Chord {
+                intervals: Vec::from(intervals),
+                chord_type: chord_type.clone(),
+                root: Some(root),
+                notes: Vec::from(notes),
+                additions: Some(additions).filter(|additions| !additions.is_empty()),
+                omissions: Some(omissions).filter(|omissions| !omissions.is_empty()),
+            };
+            // Chord tones are better read as such by some other chord type
+            if !adds_tensions_only(&chord) {
+                continue;
+            }
+            let confidence = chord.confidence();
+            candidates.push((chord, confidence));
+        }
+    }
+
+    // Sorting is stable, so candidates keep the order of the chord types above when tied
+    let bass = notes.first().map(|note| note.pitch_class());
+    candidates.sort_by(|(a, a_confidence), (b, b_confidence)| {
+        b_confidence
+            .partial_cmp(a_confidence)
+            .unwrap_or(core::cmp::Ordering::Equal)
+            .then_with(|| {
+                let a_on_bass = a.root.map(|root| root.pitch_class()) == bass;
+                let b_on_bass = b.root.map(|root| root.pitch_class()) == bass;
+                b_on_bass.cmp(&a_on_bass)
+            })
+    });
+
+    candidates
+}
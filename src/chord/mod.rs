@@ -1,21 +1,85 @@
+use crate::error::Error;
+use crate::key::correlation;
+use crate::note::write_symbol;
 use crate::prelude::*;
+use crate::roman;
+use crate::scale::CHORD_SCALE_KINDS;
 use alloc::collections::BTreeSet;
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Write};
 
+/// Functions for analyzing the relationships between chords.
+pub mod analysis;
+/// An array-backed chord type for working with chords without allocating.
+pub mod fixed;
 /// Separate functions for extracting information about intervals and different chord sizes.
 pub mod guess;
 /// Definitions for each chord type's own qualities (major, minor, augmented, etc).
 pub mod qualities;
+/// Parsing of textual chord symbols such as "Cmaj7" or "G/B".
+mod symbol;
+/// Tracking of the chords and arpeggios played live on MIDI instruments.
+pub mod tracker;
+/// Generation of the different ways of laying out the notes of a chord.
+pub mod voicing;
+
+/// The maximum number of readings of a set of notes listed by `Chord::candidates`.
+pub const MAX_CANDIDATES: usize = 8;
+
+/// LilyPond note names for each pitch class, spelling black keys the way chords are usually
+/// named after them.
+const LILYPOND_NAMES: [&str; 12] = [
+    "c", "des", "d", "es", "e", "f", "fis", "g", "as", "a", "bes", "b",
+];
+
+/// The chord types that chroma vectors are matched against by `Chord::from_chroma`.
+const CHROMA_TEMPLATES: [ChordType; 9] = [
+    ChordType::Triad(TriadQuality::Major),
+    ChordType::Triad(TriadQuality::Minor),
+    ChordType::Triad(TriadQuality::Diminished),
+    ChordType::Triad(TriadQuality::Augmented),
+    ChordType::Tetrad(TetradQuality::SeventhDominant),
+    ChordType::Tetrad(TetradQuality::SeventhMajor),
+    ChordType::Tetrad(TetradQuality::SeventhMinor),
+    ChordType::Tetrad(TetradQuality::SeventhHalfDiminished),
+    ChordType::Tetrad(TetradQuality::SeventhDiminished),
+];
+
+/// Figured bass symbols for each inversion of triads, from root position onwards.
+const TRIAD_FIGURES: [&str; 3] = ["", "⁶", "⁶₄"];
+
+/// Figured bass symbols for each inversion of seventh chords, from root position onwards.
+const SEVENTH_FIGURES: [&str; 4] = ["⁷", "⁶₅", "⁴₃", "₂"];
 
 /// From Wikipedia: A set of notes that are heard as if sounding simultaneously.
-#[derive(Debug, Eq, PartialEq)]
+///
+/// Chords are ordered by their notes, from the lowest to the highest.
+///
+/// # Examples
+/// ```rust
+/// use std::collections::BTreeSet;
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let chords = [
+///     Chord::from_notes(&[G1, B1, D2]),
+///     Chord::from_notes(&[C1, E1, G1]),
+///     Chord::from_notes(&[G1, B1, D2]),
+/// ];
+///
+/// // Chords can be deduplicated and sorted
+/// let unique = chords.iter().cloned().collect::<BTreeSet<Chord>>();
+/// assert_eq!(unique.len(), 2);
+/// assert_eq!(unique.iter().next(), Some(&chords[1]));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Chord {
+    notes: Vec<Note>,
     intervals: Vec<u8>,
     chord_type: ChordType,
-    notes: Vec<Note>,
     root: Option<Note>,
     additions: Option<Vec<Note>>,
+    omissions: Option<Vec<u8>>,
 }
 
 /// Convenient methods for working with musical chords.
@@ -29,6 +93,10 @@ impl Chord {
     /// // A chord can be constructed from any set of notes
     /// let c_major_notes = [C1, E1, G1];
     /// let c_major_chord = Chord::from_notes(&c_major_notes);
+    ///
+    /// // Chords with up to seven notes are recognized
+    /// let c_ninth = Chord::from_notes(&[C3, E3, G3, ASharp3, D4]);
+    /// assert_eq!(c_ninth.name(FlatOrSharp::Flat).unwrap(), "C9");
     /// ```
     pub fn from_notes(notes: &[Note]) -> Self {
         // Go `&[Note]` -> `BTreeSet<&Note>` -> `Vec<Note>` to ensure note uniqueness.
@@ -44,12 +112,12 @@ impl Chord {
         let intervals = guess::intervals(&notes);
 
         // Handle each chord size separately
-        match &notes.len() {
+        let chord = match &notes.len() {
             // No notes, only silence
-            0 => Chord::default(),
+            0 => return Chord::default(),
             // Single note
             1 => {
-                let root = notes.get(0).cloned();
+                let root = notes.first().cloned();
 
                 Chord {
                     intervals,
@@ -57,6 +125,7 @@ impl Chord {
                     notes,
                     root,
                     additions: None,
+                    omissions: None,
                 }
             }
             2 => guess::dyad(notes, intervals),
@@ -64,6 +133,12 @@ impl Chord {
             3 => guess::triad(&notes, &intervals),
             // Tetrad
             4 => guess::tetrad(&notes, &intervals),
+            // Pentad
+            5 => guess::pentad(&notes, &intervals),
+            // Hexad
+            6 => guess::hexad(&notes, &intervals),
+            // Heptad
+            7 => guess::heptad(&notes, &intervals),
             // Anything else not looking like a proper chord that is worth naming
             _ => Chord {
                 intervals,
@@ -71,8 +146,431 @@ impl Chord {
                 notes,
                 root: None,
                 additions: None,
+                omissions: None,
             },
+        };
+
+        // Notes that make no single chord, not even an inverted one, may be two stacked chords
+        let chord = match chord.root {
+            Some(_) => chord,
+            None => guess::polychord(&chord.notes, &chord.intervals).unwrap_or(chord),
+        };
+
+        // Notes that still make no chord, as well as lone major thirds, may be chords missing
+        // their fifth
+        match (&chord.chord_type, chord.root) {
+            (ChordType::Dyad(DyadQuality::Major(3)), _) | (_, None) => {
+                guess::omitted_fifth(&chord.notes, &chord.intervals).unwrap_or(chord)
+            }
+            _ => chord,
+        }
+    }
+
+    /// Build a chord from a set of notes, tolerating wrong, extra or missing notes such as those
+    /// found in MIDI input coming from real playing. Together with the chord, its confidence is
+    /// returned, as given by `confidence()`.
+    ///
+    /// Every chord of a known quality built on any of the notes is scored, and the best one wins,
+    /// unless the regular recognition of `from_notes` does at least as well.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // Exact matches are as good as it gets
+    /// let (chord, confidence) = Chord::from_notes_fuzzy(&[C3, E3, G3, B3]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "CM7");
+    /// assert_eq!(confidence, 1.0);
+    ///
+    /// // A C7 with a fumbled Db on top still sounds mostly like C7
+    /// let (chord, confidence) = Chord::from_notes_fuzzy(&[C3, E3, G3, ASharp3, D4, CSharp5]);
+    /// assert_eq!(chord.chord_type(), &ChordType::Pentad(PentadQuality::Ninth));
+    /// assert!(confidence < 1.0 && confidence > 0.8);
+    /// ```
+    pub fn from_notes_fuzzy(notes: &[Note]) -> (Self, f32) {
+        let exact = Self::from_notes(notes);
+        let exact_confidence = exact.confidence();
+
+        match guess::scored(&exact.notes, &exact.intervals)
+            .into_iter()
+            .next()
+        {
+            Some((best, confidence)) if confidence > exact_confidence => (best, confidence),
+            _ => (exact, exact_confidence),
+        }
+    }
+
+    /// List the most plausible readings of a set of notes as chords, from the most to the least
+    /// confident, together with their confidence as given by `confidence()`. This allows
+    /// applications to let users pick the right reading, or to use some context to disambiguate.
+    ///
+    /// At most `MAX_CANDIDATES` readings are listed, leaving out those whose confidence is below
+    /// one half, those that leave out the third (or the suspended note that stands for it), and
+    /// those that merely add or omit notes compared to a simpler reading on the same root (e.g.
+    /// "Cadd13" or "C6/9(omit 9)" next to "C6"). The reading of `from_notes` is usually among
+    /// them, but not necessarily first, as another one may be more confident.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // C6 and Am7 have the very same notes
+    /// let candidates = Chord::candidates(&[C3, E3, G3, A3]);
+    /// let names = candidates
+    ///     .iter()
+    ///     .map(|(chord, _)| chord.name(FlatOrSharp::Sharp).unwrap())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["C6", "Am7/C"]);
+    /// assert_eq!(candidates[1].1, 1.0);
+    ///
+    /// // Any note of a diminished seventh chord can be its root
+    /// let candidates = Chord::candidates(&[C3, DSharp3, FSharp3, A3]);
+    /// let exact = candidates.iter().filter(|(_, confidence)| *confidence == 1.0).count();
+    /// assert_eq!(exact, 4);
+    /// ```
+    pub fn candidates(notes: &[Note]) -> Vec<(Self, f32)> {
+        let exact = Self::from_notes(notes);
+        let exact_confidence = exact.confidence();
+        let scored = guess::scored(&exact.notes, &exact.intervals);
+
+        let readings = core::iter::once((exact, exact_confidence))
+            .chain(scored)
+            .filter(|(chord, confidence)| *confidence >= 0.5 && !omits_third(chord))
+            .collect::<Vec<_>>();
+        let root = |chord: &Chord| chord.root.map(|root| root.pitch_class());
+
+        let mut candidates = Vec::<(Self, f32)>::new();
+        for (chord, confidence) in readings.iter() {
+            let is_repeated = candidates.iter().any(|(candidate, _)| {
+                candidate.chord_type == chord.chord_type && root(candidate) == root(chord)
+            });
+            let is_padded = readings
+                .iter()
+                .any(|(other, _)| root(other) == root(chord) && strays(other) < strays(chord));
+            if !is_repeated && !is_padded {
+                candidates.push((chord.clone(), *confidence));
+            }
+        }
+
+        // The reading of `from_notes` may not be the most confident one
+        candidates.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(core::cmp::Ordering::Equal));
+        candidates.truncate(MAX_CANDIDATES);
+
+        candidates
+    }
+
+    /// Match a chroma vector (i.e. how much of each pitch class, starting from C, is heard in some
+    /// audio, as computed by any FFT-based pipeline) against the templates of the most common
+    /// chords on every root, and list them from the best match to the worst, together with their
+    /// score. Scores are the correlation of the chroma vector with the templates, from -1 to 1.
+    ///
+    /// Chords are built in root position from the octave starting at middle C, as chroma vectors
+    /// do not tell octaves apart.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// // Mostly A, C, E and G, with some leakage into other bins
+    /// let chroma = [0.8, 0.05, 0.1, 0., 0.7, 0.1, 0., 0.6, 0.05, 1., 0., 0.1];
+    /// let matches = Chord::from_chroma(&chroma);
+    /// assert_eq!(matches[0].0.name(FlatOrSharp::Sharp).unwrap(), "Am7");
+    /// assert!(matches[0].1 > 0.9);
+    ///
+    /// // Without the G, it is just A minor
+    /// let chroma = [0.8, 0.05, 0.1, 0., 0.7, 0.1, 0., 0., 0.05, 1., 0., 0.1];
+    /// let (chord, _) = &Chord::from_chroma(&chroma)[0];
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "Am");
+    /// ```
+    pub fn from_chroma(chroma: &[f32; 12]) -> Vec<(Self, f32)> {
+        let mut matches = Vec::with_capacity(12 * CHROMA_TEMPLATES.len());
+        for root in 0..12u8 {
+            for chord_type in CHROMA_TEMPLATES.iter() {
+                // Unwrap is OK because all the templates tell their notes, which fit above C4
+                let chord = Self::build(Note::from(60 + root), chord_type.clone()).unwrap();
+                let mut template = [0f32; 12];
+                for note in chord.notes() {
+                    template[usize::from(note.pitch_class())] = 1f32;
+                }
+                let score = correlation(chroma, &template);
+                matches.push((chord, score));
+            }
+        }
+        matches.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(core::cmp::Ordering::Equal));
+
+        matches
+    }
+
+    /// Build a chord from a set of notes, taking into account some context about the music that the
+    /// notes are part of, such as the root that is expected to be heard (e.g. because the bass
+    /// player is playing it).
+    ///
+    /// With an expected root, rootless voicings and shell voicings (3rd and 7th only) are
+    /// recognized as the chord that they stand for instead of as the literal stack of notes. Such
+    /// chords have a root that is not one of their notes. If the notes cannot make a chord with
+    /// the expected root, they are read as if there was no context at all.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // Without context, E-G-B-D is just Em7
+    /// let chord = Chord::from_notes(&[E3, G3, B3, D4]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "Em7");
+    ///
+    /// // But over a C in the bass, it is a rootless voicing of CM9
+    /// let context = Context::with_root(C2);
+    /// let chord = Chord::from_notes_with(&[E3, G3, B3, D4], &context);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "CM9");
+    /// assert_eq!(chord.notes(), &[E3, G3, B3, D4]);
+    ///
+    /// // The 3rd and the 7th alone are enough for telling a dominant seventh
+    /// let chord = Chord::from_notes_with(&[E3, ASharp3], &context);
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "C7");
+    /// ```
+    pub fn from_notes_with(notes: &[Note], context: &Context) -> Self {
+        let chord = Self::from_notes(notes);
+
+        match context.root {
+            Some(root) if chord.root.map(|r| r.pitch_class()) != Some(root.pitch_class()) => {
+                guess::rootless(&chord.notes, &chord.intervals, root).unwrap_or(chord)
+            }
+            _ => chord,
+        }
+    }
+
+    /// Build a chord from a set of notes regardless of their octaves, by collapsing all of them
+    /// into the octave right above the lowest note before trying to recognize the chord. This
+    /// allows recognizing wide voicings and doublings that would otherwise produce odd intervals.
+    ///
+    /// The resulting chord holds the collapsed notes instead of the original ones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // A wide voicing of C major in first inversion
+    /// let chord = Chord::from_notes_any_octave(&[E3, C4, G5]);
+    /// assert_eq!(chord, Chord::from_notes(&[E3, G3, C4]));
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C/E");
+    ///
+    /// // Doublings in other octaves are merged
+    /// let chord = Chord::from_notes_any_octave(&[C2, G2, C3, E3, G3, C4]);
+    /// assert_eq!(chord, Chord::from_notes(&[C2, E2, G2]));
+    /// ```
+    pub fn from_notes_any_octave(notes: &[Note]) -> Self {
+        let bass = match notes.iter().min() {
+            Some(bass) => *bass,
+            None => return Chord::default(),
+        };
+
+        // Distance of every pitch class in the chord above the pitch class of the bass
+        let distances = notes
+            .iter()
+            .map(|note| (note.pitch_class() + 12 - bass.pitch_class()) % 12)
+            .collect::<BTreeSet<u8>>();
+
+        // Go down an octave if the collapsed notes would not fit on top of the bass
+        let highest = distances.iter().last().cloned().unwrap_or_default();
+        let base = match bass.midi_key_number() {
+            number if number + highest > 127 => number - 12,
+            number => number,
+        };
+
+        let notes = distances
+            .iter()
+            .map(|distance| Note::from(base + distance))
+            .collect::<Vec<Note>>();
+
+        Self::from_notes(&notes)
+    }
+
+    /// Build a chord from its lowest note and the intervals (in semitones) between each of its
+    /// notes and the next one, as given by `intervals()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // A major third and a minor third on top of C make C major
+    /// let chord = Chord::from_intervals(C4, &[4, 3]).unwrap();
+    /// assert_eq!(chord, Chord::from_notes(&[C4, E4, G4]));
+    ///
+    /// // The intervals of any chord can be used to build it again somewhere else
+    /// let d_minor_seventh = Chord::from_symbol("Dm7").unwrap();
+    /// let e_minor_seventh = Chord::from_intervals(E4, &d_minor_seventh.intervals()).unwrap();
+    /// assert_eq!(e_minor_seventh.name(FlatOrSharp::Sharp).unwrap(), "Em7");
+    ///
+    /// // Chords cannot go beyond the MIDI range
+    /// assert_eq!(Chord::from_intervals(C9, &[4, 3, 4]), Err(OutOfMIDIRange));
+    /// ```
+    pub fn from_intervals(root: Note, intervals: &[u8]) -> Result<Self, Error> {
+        let mut notes = vec![root];
+        for interval in intervals {
+            let previous = notes[notes.len() - 1].midi_key_number();
+            let next = previous
+                .checked_add(*interval)
+                .filter(|number| *number < 128)
+                .ok_or(Error::OutOfMIDIRange)?;
+            notes.push(Note::from(next));
+        }
+
+        Ok(Self::from_notes(&notes))
+    }
+
+    /// Build a chord of a given type (or quality) on top of a root, with its notes in root
+    /// position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// let chord = Chord::build(C4, TriadQuality::Major).unwrap();
+    /// assert_eq!(chord.notes(), &[C4, E4, G4]);
+    ///
+    /// let chord = Chord::build(D3, TetradQuality::SeventhMinor).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "Dm7");
+    ///
+    /// // Chords cannot go beyond the MIDI range
+    /// assert_eq!(Chord::build(G9, TriadQuality::Major), Err(OutOfMIDIRange));
+    ///
+    /// // Indeterminate qualities do not tell which notes to use
+    /// assert_eq!(Chord::build(C4, TriadQuality::Indeterminate), Err(UnbuildableChord));
+    ///
+    /// // Nor do unisons, as chords cannot repeat notes
+    /// assert_eq!(Chord::build(E2, DyadQuality::Perfect(0)), Err(UnbuildableChord));
+    /// ```
+    pub fn build(root: Note, chord_type: impl Into<ChordType>) -> Result<Self, Error> {
+        let chord_type = chord_type.into();
+        let semitones = chord_type.semitones_from_root();
+        if semitones.is_empty() || semitones.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(Error::UnbuildableChord);
         }
+
+        let notes = semitones
+            .iter()
+            .map(|semitones| root.transposed(*semitones as i8))
+            .collect::<Result<Vec<Note>, Error>>()?;
+
+        Ok(Chord {
+            intervals: guess::intervals(&notes),
+            chord_type,
+            notes,
+            root: Some(root),
+            additions: None,
+            omissions: None,
+        })
+    }
+
+    /// Build a major triad in root position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::major(C4).unwrap(), Chord::from_notes(&[C4, E4, G4]));
+    /// ```
+    pub fn major(root: Note) -> Result<Self, Error> {
+        Self::build(root, TriadQuality::Major)
+    }
+
+    /// Build a minor triad in root position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::minor(A3).unwrap(), Chord::from_notes(&[A3, C4, E4]));
+    /// ```
+    pub fn minor(root: Note) -> Result<Self, Error> {
+        Self::build(root, TriadQuality::Minor)
+    }
+
+    /// Build a diminished triad in root position.
+    pub fn diminished(root: Note) -> Result<Self, Error> {
+        Self::build(root, TriadQuality::Diminished)
+    }
+
+    /// Build an augmented triad in root position.
+    pub fn augmented(root: Note) -> Result<Self, Error> {
+        Self::build(root, TriadQuality::Augmented)
+    }
+
+    /// Build a dominant seventh chord in root position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::dominant7(G3).unwrap(), Chord::from_notes(&[G3, B3, D4, F4]));
+    /// ```
+    pub fn dominant7(root: Note) -> Result<Self, Error> {
+        Self::build(root, TetradQuality::SeventhDominant)
+    }
+
+    /// Build a major seventh chord in root position.
+    pub fn major7(root: Note) -> Result<Self, Error> {
+        Self::build(root, TetradQuality::SeventhMajor)
+    }
+
+    /// Build a minor seventh chord in root position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::minor7(A3).unwrap(), Chord::from_notes(&[A3, C4, E4, G4]));
+    /// ```
+    pub fn minor7(root: Note) -> Result<Self, Error> {
+        Self::build(root, TetradQuality::SeventhMinor)
+    }
+
+    /// Build a diminished seventh chord in root position.
+    pub fn diminished7(root: Note) -> Result<Self, Error> {
+        Self::build(root, TetradQuality::SeventhDiminished)
+    }
+
+    /// Build a half diminished seventh chord in root position.
+    pub fn half_diminished7(root: Note) -> Result<Self, Error> {
+        Self::build(root, TetradQuality::SeventhHalfDiminished)
+    }
+
+    /// Build a chord from its textual symbol, such as "C", "F#m7b5" or "G/B".
+    ///
+    /// The resulting chord is voiced in closed position, with its root in the 4th octave. The bass
+    /// note of slash chords goes in the 3rd octave, and it is considered an addition if it does
+    /// not belong to the chord.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // Plain triads
+    /// let chord = Chord::from_symbol("C").unwrap();
+    /// assert_eq!(chord, Chord::from_notes(&[C4, E4, G4]));
+    ///
+    /// // Tetrads, with accidentals in their roots
+    /// let chord = Chord::from_symbol("F#m7b5").unwrap();
+    /// assert_eq!(chord, Chord::from_notes(&[FSharp4, A4, C5, E5]));
+    ///
+    /// // Slash chords where the bass belongs to the chord are inversions
+    /// let chord = Chord::from_symbol("G/B").unwrap();
+    /// assert_eq!(chord, Chord::from_notes(&[B3, D4, G4]));
+    ///
+    /// // Chords can also be parsed through `str::parse`
+    /// let chord = "Ebmaj7".parse::<Chord>().unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "EbM7");
+    ///
+    /// // Double accidentals are accepted too, both in roots and in basses
+    /// let chord = Chord::from_symbol("D#7/Fx").unwrap();
+    /// assert_eq!(chord.name(&Key::major(E4)).unwrap(), "D#7/Fx");
+    ///
+    /// // Garbage is rejected
+    /// assert_eq!(Chord::from_symbol("H7"), Err(InvalidChordSymbol));
+    /// assert_eq!(Chord::from_symbol("Cfoo"), Err(InvalidChordSymbol));
+    /// ```
+    pub fn from_symbol(symbol: &str) -> Result<Self, Error> {
+        symbol::parse(symbol)
     }
 
     /// Retrieve the intervals in a chord.
@@ -99,116 +597,1208 @@ impl Chord {
         self.intervals.clone()
     }
 
-    /// Tells whether the chord is actually a silence (has no notes in it)
+    /// Retrieve the notes in a chord, from the lowest to the highest.
     ///
     /// # Examples
     /// ```rust
     /// use whatthechord::prelude::{*, Note::*};
     ///
-    /// // The default chord is a silence (has no notes in it)
-    /// let default_is_silence = Chord::default().is_silence();
-    /// assert_eq!(default_is_silence, true);
+    /// // Notes are sorted and deduplicated
+    /// let chord = Chord::from_notes(&[G1, C1, E1, C1]);
+    /// assert_eq!(chord.notes(), &[C1, E1, G1]);
+    /// ```
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Get the frequencies in Hertz of the notes of a chord in some tuning, from lowest to highest.
     ///
-    /// // C1 is not a silence
-    /// let c1_is_silence = Chord::from_notes(&[C1]).is_silence();
-    /// assert_eq!(c1_is_silence, false);
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
     ///
-    /// // C Major is not silence
-    /// let c_major_notes = [C1, E1, G1];
-    /// let c_major_is_silence = Chord::from_notes(&c_major_notes).is_silence();
-    /// assert_eq!(c_major_is_silence, false);
+    /// let chord = Chord::from_notes(&[C4, E4, G4]);
+    ///
+    /// // In just intonation, the notes of a major triad make the pure 4:5:6 ratio
+    /// let frequencies = chord.frequencies(&Tuning::Just { tonic: C4 });
+    /// assert_eq!(frequencies, [261.62555, 327.03192, 392.43832]);
+    ///
+    /// // While equal temperament makes the third quite sharp
+    /// let frequencies = chord.frequencies(&Tuning::default());
+    /// assert_eq!(frequencies, [261.62555, 329.62756, 391.99542]);
     /// ```
-    pub fn is_silence(&self) -> bool {
-        self.notes.is_empty()
+    pub fn frequencies(&self, tuning: &Tuning) -> Vec<f32> {
+        self.notes
+            .iter()
+            .map(|note| note.frequency_with(tuning))
+            .collect()
     }
 
-    /// Tells whether the chord is actually not an harmony but a single note.
+    /// Get the names of the notes of a chord, from lowest to highest. When spelled after a key,
+    /// notes take the letters that match their role in the chord.
     ///
     /// # Examples
     /// ```rust
     /// use whatthechord::prelude::{*, Note::*};
     ///
-    /// // The default chord is a silence (has no notes in it), so it is not a single note
-    /// let default_is_single_note = Chord::default().is_single_note();
-    /// assert_eq!(default_is_single_note, false);
+    /// // The third of Eb major is G, and its fifth is Bb
+    /// let chord = Chord::from_notes(&[DSharp4, G4, ASharp4]);
+    /// assert_eq!(chord.note_names(&Key::major(DSharp4)), ["Eb4", "G4", "Bb4"]);
     ///
-    /// // C1 is a single note
-    /// let c1_is_single_note = Chord::from_notes(&[C1]).is_single_note();
-    /// assert_eq!(c1_is_single_note, true);
+    /// // The seventh of F#7 is E, even in a flat key
+    /// let chord = Chord::from_notes(&[FSharp3, ASharp3, CSharp4, E4]);
+    /// assert_eq!(chord.note_names(&Key::major(B3)), ["F#3", "A#3", "C#4", "E4"]);
     ///
-    /// // C Major is not a single note but a triad
-    /// let c_major_notes = [C1, E1, G1];
-    /// let c_major_is_single_note = Chord::from_notes(&c_major_notes).is_single_note();
-    /// assert_eq!(c_major_is_single_note, false);
+    /// // Double accidentals are used where needed, as for the third of D#7 in E major
+    /// let chord = Chord::from_notes(&[DSharp3, G3, ASharp3, CSharp4]);
+    /// assert_eq!(chord.note_names(&Key::major(E3)), ["D#3", "Fx3", "A#3", "C#4"]);
+    ///
+    /// // The seventh of a diminished seventh chord is spelled as such
+    /// let chord = Chord::from_notes(&[GSharp3, B3, D4, F4]);
+    /// assert_eq!(chord.note_names(&Key::minor(A3)), ["G#3", "B3", "D4", "F4"]);
+    ///
+    /// // Fixed accidentals spell every note in the same way
+    /// assert_eq!(chord.note_names(FlatOrSharp::Flat), ["Ab3", "B3", "D4", "F4"]);
     /// ```
-    pub fn is_single_note(&self) -> bool {
-        self.notes.len() == 1
+    pub fn note_names(&self, spelling: impl Into<Spelling>) -> Vec<String> {
+        self.spelled_notes(spelling)
+            .iter()
+            .map(SpelledNote::to_string)
+            .collect()
     }
 
-    /// Tells whether the chord is a triad.
+    /// Spell the notes of a chord, from lowest to highest, as done by `Chord::note_names`.
     ///
     /// # Examples
     /// ```rust
     /// use whatthechord::prelude::{*, Note::*};
     ///
-    /// // The default chord is a silence (has no notes in it), so it is not a triad
-    /// let default_is_triad = Chord::default().is_triad();
-    /// assert_eq!(default_is_triad, false);
+    /// // In Ab major, the fifth of the chord on its tonic is Eb rather than D#
+    /// let chord = Chord::from_notes(&[GSharp3, C4, DSharp4, FSharp4]);
+    /// let spelled = chord.spelled_notes(&Key::major(GSharp3));
+    /// assert_eq!(spelled[2].letter(), 'E');
+    /// assert_eq!(spelled[2].accidental(), Accidental::Flat);
+    /// ```
+    pub fn spelled_notes(&self, spelling: impl Into<Spelling>) -> Vec<SpelledNote> {
+        let spelling = spelling.into();
+
+        self.notes
+            .iter()
+            .map(|note| spelling.spell_in_chord(self, *note).with_octave(*note))
+            .collect()
+    }
+
+    /// Retrieve the root of a chord, if it could be identified.
     ///
-    /// // C1 is a single note, so it is not a triad
-    /// let c1_is_triad = Chord::from_notes(&[C1]).is_triad();
-    /// assert_eq!(c1_is_triad, false);
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
     ///
-    /// // Cmaj is a triad
-    /// let c_maj_notes = [C1, E1, G1];
-    /// let c_maj_is_triad = Chord::from_notes(&c_maj_notes).is_triad();
-    /// assert_eq!(c_maj_is_triad, true);
+    /// // The root of C major in first inversion is still C
+    /// let chord = Chord::from_notes(&[E1, G1, C2]);
+    /// assert_eq!(chord.root(), Some(C2));
+    ///
+    /// // Silence has no root
+    /// assert_eq!(Chord::default().root(), None);
+    /// ```
+    pub fn root(&self) -> Option<Note> {
+        self.root
+    }
+
+    /// Retrieve the bass of a chord, that is, its lowest note.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[E1, G1, C2]);
+    /// assert_eq!(chord.bass(), Some(E1));
+    ///
+    /// // Silence has no bass
+    /// assert_eq!(Chord::default().bass(), None);
+    /// ```
+    pub fn bass(&self) -> Option<Note> {
+        self.notes.first().cloned()
+    }
+
+    /// Retrieve the type of a chord, which holds its quality.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[A1, C2, E2, G2]);
+    /// assert_eq!(chord.chord_type(), &ChordType::Tetrad(TetradQuality::SeventhMinor));
+    /// ```
+    pub fn chord_type(&self) -> &ChordType {
+        &self.chord_type
+    }
+
+    /// Retrieve the notes that were found on top of a recognizable chord, if any.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // Cadd9 is C major plus a D
+    /// let chord = Chord::from_notes(&[C1, D1, E1, G1]);
+    /// assert_eq!(chord.chord_type(), &ChordType::Triad(TriadQuality::Major));
+    /// assert_eq!(chord.additions(), Some(&[D1][..]));
+    ///
+    /// // Plain C major has no additions
+    /// assert_eq!(Chord::from_notes(&[C1, E1, G1]).additions(), None);
+    /// ```
+    pub fn additions(&self) -> Option<&[Note]> {
+        self.additions.as_deref()
+    }
+
+    /// Retrieve the chord tones that are missing from a recognizable chord, if any, as their
+    /// distances in semitones to the root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // C7 is still recognizable without its fifth
+    /// let chord = Chord::from_notes(&[C1, E1, ASharp1]);
+    /// assert_eq!(chord.chord_type(), &ChordType::Tetrad(TetradQuality::SeventhDominant));
+    /// assert_eq!(chord.omissions(), Some(&[7][..]));
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "C7(omit 5)");
+    ///
+    /// // Complete chords have no omissions
+    /// assert_eq!(Chord::from_notes(&[C1, E1, G1, ASharp1]).omissions(), None);
+    /// ```
+    pub fn omissions(&self) -> Option<&[u8]> {
+        self.omissions.as_deref()
+    }
+
+    /// Compute the normal form of the pitch classes in a chord, as given by `normal_form()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // G major in first inversion
+    /// let chord = Chord::from_notes(&[B1, D2, G2]);
+    /// assert_eq!(chord.normal_form(), vec![7, 11, 2]);
+    /// ```
+    pub fn normal_form(&self) -> Vec<u8> {
+        normal_form(&self.pitch_classes())
+    }
+
+    /// Compute the prime form of the pitch classes in a chord, as given by `prime_form()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // Every major and minor triad has the same prime form
+    /// assert_eq!(Chord::from_notes(&[B1, D2, G2]).prime_form(), vec![0, 3, 7]);
+    /// assert_eq!(Chord::from_notes(&[A1, C2, E2]).prime_form(), vec![0, 3, 7]);
+    /// ```
+    pub fn prime_form(&self) -> Vec<u8> {
+        prime_form(&self.pitch_classes())
+    }
+
+    /// Get the Forte number of the set class of the pitch classes in a chord, as given by
+    /// `forte_number()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::from_notes(&[C1, E1, G1]).forte_number(), Some("3-11"));
+    /// assert_eq!(Chord::from_notes(&[C1, E1, G1, ASharp1]).forte_number(), Some("4-27"));
+    /// ```
+    pub fn forte_number(&self) -> Option<&'static str> {
+        forte_number(&self.pitch_classes())
+    }
+
+    /// Compute the interval vector of the pitch classes in a chord, as given by
+    /// `interval_vector()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[C1, E1, G1, ASharp1]);
+    /// assert_eq!(chord.interval_vector(), [0, 1, 2, 1, 1, 1]);
+    /// ```
+    pub fn interval_vector(&self) -> [u8; 6] {
+        interval_vector(&self.pitch_classes())
+    }
+
+    /// Tells whether the pitch classes in two chords are Z-related, as told by `z_related()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let a = Chord::from_notes(&[C1, CSharp1, E1, FSharp1]);
+    /// let b = Chord::from_notes(&[C1, CSharp1, DSharp1, G1]);
+    /// assert!(a.is_z_related_to(&b));
+    ///
+    /// // Transpositions are not Z-related
+    /// assert!(!a.is_z_related_to(&a.transposed(2).unwrap()));
+    /// ```
+    pub fn is_z_related_to(&self, other: &Chord) -> bool {
+        z_related(&self.pitch_classes(), &other.pitch_classes())
+    }
+
+    /// Get the set class of the pitch classes in a chord, as given by `set_class()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // Every major triad, in any inversion, belongs to the same set class
+    /// let chord = Chord::from_notes(&[FSharp1, A1, D2]);
+    /// assert_eq!(chord.set_class(Equivalence::Transposition), vec![0, 4, 7]);
+    /// ```
+    pub fn set_class(&self, equivalence: Equivalence) -> Vec<u8> {
+        set_class(&self.pitch_classes(), equivalence)
+    }
+
+    /// Tells whether the pitch classes in two chords belong to the same set class, that is,
+    /// whether they are the same chord regardless of transposition (and optionally of inversion)
+    /// and voicing.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Chord::from_notes(&[C1, E1, G1]);
+    /// let g_major = Chord::from_notes(&[B1, D2, G2]);
+    /// let a_minor = Chord::from_notes(&[A1, C2, E2]);
+    ///
+    /// assert!(c_major.is_equivalent_to(&g_major, Equivalence::Transposition));
+    /// assert!(!c_major.is_equivalent_to(&a_minor, Equivalence::Transposition));
+    /// assert!(c_major.is_equivalent_to(&a_minor, Equivalence::TranspositionInversion));
+    /// ```
+    pub fn is_equivalent_to(&self, other: &Chord, equivalence: Equivalence) -> bool {
+        self.set_class(equivalence) == other.set_class(equivalence)
+    }
+
+    /// Get the pitch classes of the notes in a chord, from the lowest note to the highest.
+    fn pitch_classes(&self) -> Vec<u8> {
+        self.notes.iter().map(|note| note.pitch_class()).collect()
+    }
+
+    /// Tell how confidently a chord was recognized, from 0 to 1. The confidence is the number of
+    /// chord tones that are present, out of all the notes that are present or missing: exact
+    /// matches have a confidence of 1, and every extra or missing note lowers it. Polychords are as
+    /// confident as their least confident part, and chords with no identifiable root have no
+    /// confidence at all.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::from_notes(&[C1, E1, G1]).confidence(), 1.0);
+    ///
+    /// // One extra note out of four
+    /// assert_eq!(Chord::from_notes(&[C1, D1, E1, G1]).confidence(), 0.75);
+    ///
+    /// // One missing note out of four
+    /// assert_eq!(Chord::from_notes(&[C1, E1, ASharp1]).confidence(), 0.75);
+    ///
+    /// assert_eq!(Chord::default().confidence(), 0.0);
+    /// ```
+    pub fn confidence(&self) -> f32 {
+        let root = match self.root {
+            Some(root) => root,
+            None => return 0.0,
+        };
+
+        if let ChordType::Complex(chords) = &self.chord_type {
+            return chords
+                .iter()
+                .map(Chord::confidence)
+                .fold(1.0, |least, confidence| least.min(confidence));
+        }
+
+        // Pitch classes relative to the root, as bitmasks
+        let tones = self
+            .chord_type
+            .semitones_from_root()
+            .iter()
+            .fold(0u16, |mask, semitones| mask | 1 << (semitones % 12));
+        let played = self.notes.iter().fold(0u16, |mask, note| {
+            mask | 1 << ((note.pitch_class() + 12 - root.pitch_class()) % 12)
+        });
+
+        match (tones | played).count_ones() {
+            0 => 0.0,
+            all => (tones & played).count_ones() as f32 / all as f32,
+        }
+    }
+
+    /// Tells whether the chord is actually a silence (has no notes in it)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // The default chord is a silence (has no notes in it)
+    /// let default_is_silence = Chord::default().is_silence();
+    /// assert_eq!(default_is_silence, true);
+    ///
+    /// // C1 is not a silence
+    /// let c1_is_silence = Chord::from_notes(&[C1]).is_silence();
+    /// assert_eq!(c1_is_silence, false);
+    ///
+    /// // C Major is not silence
+    /// let c_major_notes = [C1, E1, G1];
+    /// let c_major_is_silence = Chord::from_notes(&c_major_notes).is_silence();
+    /// assert_eq!(c_major_is_silence, false);
+    /// ```
+    pub fn is_silence(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Tells whether the chord is actually not an harmony but a single note.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // The default chord is a silence (has no notes in it), so it is not a single note
+    /// let default_is_single_note = Chord::default().is_single_note();
+    /// assert_eq!(default_is_single_note, false);
+    ///
+    /// // C1 is a single note
+    /// let c1_is_single_note = Chord::from_notes(&[C1]).is_single_note();
+    /// assert_eq!(c1_is_single_note, true);
+    ///
+    /// // C Major is not a single note but a triad
+    /// let c_major_notes = [C1, E1, G1];
+    /// let c_major_is_single_note = Chord::from_notes(&c_major_notes).is_single_note();
+    /// assert_eq!(c_major_is_single_note, false);
+    /// ```
+    pub fn is_single_note(&self) -> bool {
+        self.notes.len() == 1
+    }
+
+    /// Tells whether the chord is a triad.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // The default chord is a silence (has no notes in it), so it is not a triad
+    /// let default_is_triad = Chord::default().is_triad();
+    /// assert_eq!(default_is_triad, false);
+    ///
+    /// // C1 is a single note, so it is not a triad
+    /// let c1_is_triad = Chord::from_notes(&[C1]).is_triad();
+    /// assert_eq!(c1_is_triad, false);
+    ///
+    /// // Cmaj is a triad
+    /// let c_maj_notes = [C1, E1, G1];
+    /// let c_maj_is_triad = Chord::from_notes(&c_maj_notes).is_triad();
+    /// assert_eq!(c_maj_is_triad, true);
+    ///
+    /// // Cmaj/E is a triad
+    /// let c_maj_e_notes = [E1, G1, C2];
+    /// let c_maj_e_is_triad = Chord::from_notes(&c_maj_e_notes).is_triad();
+    /// assert_eq!(c_maj_e_is_triad, true);
+    ///
+    /// // Cmaj/G is a triad
+    /// let c_maj_g_notes = [G1, C2, E2];
+    /// let c_maj_g_is_triad = Chord::from_notes(&c_maj_g_notes).is_triad();
+    /// assert_eq!(c_maj_g_is_triad, true);
+    ///
+    /// // Cm is a triad
+    /// let c_m_notes = [C1, DSharp1, G1];
+    /// let c_m_is_triad = Chord::from_notes(&c_m_notes).is_triad();
+    /// assert_eq!(c_m_is_triad, true);
+    ///
+    /// // Cm/D# is a triad
+    /// let c_m_d_sharp_notes = [DSharp1, G1, C2];
+    /// let c_m_d_sharp_is_triad = Chord::from_notes(&c_m_d_sharp_notes).is_triad();
+    /// assert_eq!(c_m_d_sharp_is_triad, true);
+    ///
+    /// // Cm/G is a triad
+    /// let c_m_g_notes = [G1, C2, DSharp2];
+    /// let c_m_g_is_triad = Chord::from_notes(&c_m_g_notes).is_triad();
+    /// assert_eq!(c_m_g_is_triad, true);
+    ///
+    /// // Cdim is a triad
+    /// let c_dim_notes = [C1, DSharp1, FSharp1];
+    /// let c_dim_is_triad = Chord::from_notes(&c_dim_notes).is_triad();
+    /// assert_eq!(c_m_is_triad, true);
+    ///
+    /// // Cdim/D# is a triad
+    /// let c_dim_d_sharp_notes = [DSharp1, FSharp1, C2];
+    /// let c_dim_d_sharp_is_triad = Chord::from_notes(&c_dim_d_sharp_notes).is_triad();
+    /// assert_eq!(c_dim_d_sharp_is_triad, true);
+    ///
+    /// // Cdim/F# is a triad
+    /// let c_dim_f_sharp_notes = [FSharp1, C2, DSharp2];
+    /// let c_dim_f_sharp_is_triad = Chord::from_notes(&c_dim_f_sharp_notes).is_triad();
+    /// assert_eq!(c_dim_f_sharp_is_triad, true);
+    /// ```
+    pub fn is_triad(&self) -> bool {
+        self.notes.len() == 3
+    }
+
+    /// For chords whose notes can also be read as a different chord with another root, get the
+    /// note acting as the root in that other reading.
+    ///
+    /// This is the case of sixth chords and their relative minor seventh chords (e.g. C6 and Am7),
+    /// as well as minor sixth chords and their relative half diminished seventh chords (e.g. Cm6
+    /// and Am7b5). Sixth chords are only recognized as such when the bass is their root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // C6 has the same notes as Am7
+    /// let c_sixth = Chord::from_notes(&[C4, E4, G4, A4]);
+    /// assert_eq!(c_sixth, Chord::from_symbol("C6").unwrap());
+    /// assert_eq!(c_sixth.alternative_root(), Some(A4));
+    ///
+    /// // Am7 has the same notes as C6
+    /// let a_minor_seventh = Chord::from_notes(&[A2, C3, E3, G3]);
+    /// assert_eq!(a_minor_seventh.alternative_root(), Some(C3));
+    ///
+    /// // C major seventh is not ambiguous
+    /// let c_major_seventh = Chord::from_notes(&[C3, E3, G3, B3]);
+    /// assert_eq!(c_major_seventh.alternative_root(), None);
+    /// ```
+    pub fn alternative_root(&self) -> Option<Note> {
+        use TetradQuality::*;
+
+        let root = self.root?;
+        let offset = match self.chord_type {
+            ChordType::Tetrad(Sixth) | ChordType::Tetrad(SixthMinor) => 9,
+            ChordType::Tetrad(SeventhMinor) | ChordType::Tetrad(SeventhHalfDiminished) => 3,
+            _ => return None,
+        };
+        let pitch_class = (root.pitch_class() + offset) % 12;
+
+        self.notes
+            .iter()
+            .find(|note| note.pitch_class() == pitch_class)
+            .cloned()
+    }
+
+    /// Transpose every note in a chord by a number of half tones, keeping its type as is instead of
+    /// recognizing the chord again.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // C major seventh becomes D major seventh
+    /// let chord = Chord::from_notes(&[C4, E4, G4, B4]).transposed(2).unwrap();
+    /// assert_eq!(chord, Chord::from_notes(&[D4, FSharp4, A4, CSharp5]));
+    ///
+    /// // Notes cannot go beyond the MIDI range
+    /// assert_eq!(Chord::from_notes(&[C9, E9, G9]).transposed(12), Err(OutOfMIDIRange));
+    /// ```
+    pub fn transposed(&self, half_tones: i8) -> Result<Self, Error> {
+        let transpose = |notes: &[Note]| {
+            notes
+                .iter()
+                .map(|note| note.transposed(half_tones))
+                .collect::<Result<Vec<Note>, Error>>()
+        };
+
+        let chord_type = match &self.chord_type {
+            ChordType::Complex(chords) => ChordType::Complex(
+                chords
+                    .iter()
+                    .map(|chord| chord.transposed(half_tones))
+                    .collect::<Result<Vec<Chord>, Error>>()?,
+            ),
+            chord_type => chord_type.clone(),
+        };
+
+        Ok(Chord {
+            notes: transpose(&self.notes)?,
+            intervals: self.intervals.clone(),
+            chord_type,
+            root: self
+                .root
+                .map(|root| root.transposed(half_tones))
+                .transpose()?,
+            additions: self.additions.as_deref().map(transpose).transpose()?,
+            omissions: self.omissions.clone(),
+        })
+    }
+
+    /// Mirror every note in a chord around an axis note, so that notes a number of semitones above
+    /// the axis end up that same number of semitones below it, and the other way around. This is
+    /// also known as chromatic inversion.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // Mirroring a major triad gives a minor triad
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// let mirrored = c_major.mirrored_around(E4).unwrap();
+    /// assert_eq!(mirrored.notes(), &[CSharp4, E4, GSharp4]);
+    /// assert_eq!(mirrored.name(FlatOrSharp::Sharp).unwrap(), "C#m");
+    ///
+    /// // Notes cannot go beyond the MIDI range
+    /// assert_eq!(c_major.mirrored_around(C0), Err(OutOfMIDIRange));
+    /// ```
+    pub fn mirrored_around(&self, axis: Note) -> Result<Self, Error> {
+        let notes = self
+            .notes
+            .iter()
+            .map(|note| axis.transposed(axis - *note))
+            .collect::<Result<Vec<Note>, Error>>()?;
+
+        Ok(Self::from_notes(&notes))
+    }
+
+    /// Write a chord as a LilyPond `\chordmode` token, such as "c:maj7" or "a:m7/e", so that it can
+    /// be engraved. Black keys are spelled the way chords are usually named after them (D flat, E
+    /// flat, F sharp, A flat and B flat), and the bass is written after a slash if it is not the
+    /// root ("/+" if it is not a chord tone). Added tensions and omitted chord tones are written as
+    /// added (".9") and removed ("^5") steps.
+    ///
+    /// Nothing is returned for chords with no identifiable root, or whose type has no LilyPond
+    /// counterpart.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::from_notes(&[C3, E3, G3, B3]).to_lilypond().unwrap(), "c:maj7");
+    /// assert_eq!(Chord::from_notes(&[E3, G3, A3, C4]).to_lilypond().unwrap(), "a:m7/e");
+    /// assert_eq!(Chord::from_notes(&[ASharp2, D3, F3]).to_lilypond().unwrap(), "bes");
+    /// assert_eq!(Chord::from_symbol("C/D").unwrap().to_lilypond().unwrap(), "c/+d");
+    ///
+    /// // Power chords are made of the root and the fifth alone
+    /// assert_eq!(Chord::from_notes(&[C3, G3]).to_lilypond().unwrap(), "c:1.5");
+    ///
+    /// // Additions and omissions are written as steps
+    /// assert_eq!(Chord::from_notes(&[C3, D3, E3, G3]).to_lilypond().unwrap(), "c:5.9");
+    /// assert_eq!(Chord::from_notes(&[A2, B2, C3, E3]).to_lilypond().unwrap(), "a:m5.9");
+    /// assert_eq!(Chord::from_notes(&[C3, E3]).to_lilypond().unwrap(), "c:^5");
+    /// assert_eq!(Chord::from_notes(&[C3, E3, ASharp3, D4]).to_lilypond().unwrap(), "c:9^5");
+    ///
+    /// assert_eq!(Chord::default().to_lilypond(), None);
+    /// ```
+    pub fn to_lilypond(&self) -> Option<String> {
+        let root = self.root?.pitch_class();
+        let modifiers = lilypond_modifiers(&self.chord_type)?;
+        let mut lilypond = String::from(LILYPOND_NAMES[usize::from(root)]);
+        lilypond.push_str(modifiers);
+
+        // A bass that is not a chord tone is already written after the slash
+        let bass_addition = match (self.bass(), self.inversion()) {
+            (Some(bass), None) => 1 << ((bass.pitch_class() + 12 - root) % 12),
+            _ => 0,
+        };
+        let additions = added_offsets(self) & !bass_addition;
+        if additions != 0 {
+            // Suspended triads take no steps after their modifier
+            if let ChordType::Triad(TriadQuality::Suspended(_)) = self.chord_type {
+                return None;
+            }
+            if !modifiers.ends_with(|c: char| c.is_ascii_digit()) {
+                lilypond.push_str(if modifiers.is_empty() { ":5" } else { "5" });
+            }
+            LILYPOND_TENSIONS
+                .iter()
+                .enumerate()
+                .filter(|(offset, _)| additions & 1 << offset != 0)
+                .for_each(|(_, step)| {
+                    lilypond.push('.');
+                    lilypond.push_str(step);
+                });
+        }
+
+        if let Some(omissions) = &self.omissions {
+            if lilypond.len() == LILYPOND_NAMES[usize::from(root)].len() {
+                lilypond.push(':');
+            }
+            lilypond.push('^');
+            for (index, omission) in omissions.iter().enumerate() {
+                if index > 0 {
+                    lilypond.push('.');
+                }
+                // Writing into a `String` never fails
+                write!(lilypond, "{}", lilypond_step(&self.chord_type, *omission)?).unwrap();
+            }
+        }
+
+        if let Some(bass) = self.bass().map(Note::pitch_class) {
+            if bass != root {
+                lilypond.push_str(match self.inversion() {
+                    Some(_) => "/",
+                    None => "/+",
+                });
+                lilypond.push_str(LILYPOND_NAMES[usize::from(bass)]);
+            }
+        }
+
+        Some(lilypond)
+    }
+
+    /// Get the figured bass symbols that tell the inversion of a chord, as used in classical
+    /// harmony: nothing for triads in root position, "⁶" and "⁶₄" for their inversions, and "⁷",
+    /// "⁶₅", "⁴₃" and "₂" for seventh chords.
+    ///
+    /// Nothing is returned for chords that are not tertian triads or seventh chords (such as
+    /// suspended or sixth chords), or that have no inversion.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::from_notes(&[C4, E4, G4]).figured_bass(), Some(""));
+    /// assert_eq!(Chord::from_notes(&[E4, G4, C5]).figured_bass(), Some("⁶"));
+    /// assert_eq!(Chord::from_notes(&[G3, C4, E4]).figured_bass(), Some("⁶₄"));
+    /// assert_eq!(Chord::from_notes(&[G3, B3, D4, F4]).figured_bass(), Some("⁷"));
+    /// assert_eq!(Chord::from_notes(&[F3, G3, B3, D4]).figured_bass(), Some("₂"));
+    ///
+    /// assert_eq!(Chord::from_notes(&[C4, G4]).figured_bass(), None);
+    /// assert_eq!(Chord::from_notes(&[C4, E4, G4, A4]).figured_bass(), None);
+    /// assert_eq!(Chord::from_notes(&[C4, F4, G4]).figured_bass(), None);
+    /// ```
+    pub fn figured_bass(&self) -> Option<&'static str> {
+        use TetradQuality::*;
+
+        let figures: &[&str] = match self.chord_type {
+            ChordType::Triad(
+                TriadQuality::Major
+                | TriadQuality::Minor
+                | TriadQuality::Diminished
+                | TriadQuality::Augmented,
+            ) => &TRIAD_FIGURES,
+            ChordType::Tetrad(
+                SeventhDiminished
+                | SeventhDominant
+                | SeventhMajor
+                | SeventhMinor
+                | SeventhMinorMajor
+                | SeventhHalfDiminished
+                | SeventhAugmentedMajor,
+            ) => &SEVENTH_FIGURES,
+            _ => return None,
+        };
+
+        figures.get(usize::from(self.inversion()?)).copied()
+    }
+
+    /// Get the guide tones of a chord, that is, the notes that define its quality: its third and
+    /// its seventh. Every note in the chord that is one of them is returned, from lowest to
+    /// highest.
+    ///
+    /// Suspended chords have their suspended note taken instead of the third, and chords with no
+    /// seventh have their sixth taken instead, or their fifth if there is no sixth either. Chords
+    /// with no identifiable root have no guide tones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[G2, F3, B3, D4]);
+    /// assert_eq!(chord.guide_tones(), [F3, B3]);
+    ///
+    /// let chord = Chord::from_notes(&[C3, E3, G3, A3]);
+    /// assert_eq!(chord.guide_tones(), [E3, A3]);
+    ///
+    /// let chord = Chord::from_notes(&[D3, G3, A3, C4]);
+    /// assert_eq!(chord.guide_tones(), [G3, C4]);
+    ///
+    /// let chord = Chord::from_notes(&[C3, E3, G3]);
+    /// assert_eq!(chord.guide_tones(), [E3, G3]);
+    /// ```
+    pub fn guide_tones(&self) -> Vec<Note> {
+        let root = match self.root {
+            Some(root) => root.pitch_class(),
+            None => return Vec::new(),
+        };
+        let intervals = |candidates: &[&[u8]]| {
+            candidates
+                .iter()
+                .map(|candidate| {
+                    candidate
+                        .iter()
+                        .map(|half_tones| (root + half_tones) % 12)
+                        .filter(|pitch_class| self.contains_pitch_class(*pitch_class))
+                        .collect::<Vec<u8>>()
+                })
+                .find(|pitch_classes| !pitch_classes.is_empty())
+                .unwrap_or_default()
+        };
+        let third = intervals(&[&[3, 4], &[2, 5]]);
+        let seventh = intervals(&[&[10, 11], &[9], &[6, 7, 8]]);
+
+        self.notes
+            .iter()
+            .filter(|note| {
+                let pitch_class = note.pitch_class();
+                third.contains(&pitch_class) || seventh.contains(&pitch_class)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Tell which inversion a chord is in, that is, which of its chord tones (counting from the
+    /// root) is in the bass: 0 for root position, 1 for first inversion, 2 for second inversion and
+    /// so on.
+    ///
+    /// There is no inversion if the chord has no identifiable root, or if its bass is not one of
+    /// its chord tones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Chord::from_notes(&[C4, E4, G4]).inversion(), Some(0));
+    /// assert_eq!(Chord::from_notes(&[E4, G4, C5]).inversion(), Some(1));
+    /// assert_eq!(Chord::from_notes(&[G3, C4, E4]).inversion(), Some(2));
+    ///
+    /// // Third inversion of G7
+    /// assert_eq!(Chord::from_notes(&[F3, G3, B3, D4]).inversion(), Some(3));
+    ///
+    /// // The bass of C/D is not part of C major
+    /// assert_eq!(Chord::from_symbol("C/D").unwrap().inversion(), None);
+    /// ```
+    pub fn inversion(&self) -> Option<u8> {
+        let root = self.root?;
+        let bass = self.bass()?;
+        let offset = (bass.pitch_class() + 12 - root.pitch_class()) % 12;
+
+        self.chord_type
+            .semitones_from_root()
+            .iter()
+            .position(|semitones| semitones % 12 == offset)
+            .map(|position| position as u8)
+    }
+
+    /// Get the next inversion of a chord, by moving its lowest note an octave up.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // C major in root position becomes C/E
+    /// let chord = Chord::from_notes(&[C4, E4, G4]).invert_up().unwrap();
+    /// assert_eq!(chord.notes(), &[E4, G4, C5]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C/E");
+    ///
+    /// // Notes cannot go beyond the MIDI range
+    /// assert_eq!(Chord::from_notes(&[C9, E9, G9]).invert_up(), Err(OutOfMIDIRange));
+    /// ```
+    pub fn invert_up(&self) -> Result<Self, Error> {
+        let mut notes = self.notes.clone();
+        if let Some(lowest) = notes.first_mut() {
+            *lowest = lowest.transposed(12)?;
+        }
+
+        Ok(Self::from_notes(&notes))
+    }
+
+    /// Get the previous inversion of a chord, by moving its highest note an octave down.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // C major in root position becomes C/G
+    /// let chord = Chord::from_notes(&[C4, E4, G4]).invert_down().unwrap();
+    /// assert_eq!(chord.notes(), &[G3, C4, E4]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C/G");
+    ///
+    /// // Notes cannot go beyond the MIDI range
+    /// let chord = Chord::from_notes(&[CMinus1, EMinus1, GMinus1]);
+    /// assert_eq!(chord.invert_down(), Err(OutOfMIDIRange));
+    /// ```
+    pub fn invert_down(&self) -> Result<Self, Error> {
+        let mut notes = self.notes.clone();
+        if let Some(highest) = notes.last_mut() {
+            *highest = highest.transposed(-12)?;
+        }
+
+        Ok(Self::from_notes(&notes))
+    }
+
+    /// Tells whether a chord contains a note, in that very same octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// assert_eq!(c_major.contains(E4), true);
+    /// assert_eq!(c_major.contains(E5), false);
+    /// ```
+    pub fn contains(&self, note: Note) -> bool {
+        self.notes.contains(&note)
+    }
+
+    /// Tells whether a chord contains a pitch class (0-11, where 0 is C) in any octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// assert_eq!(c_major.contains_pitch_class(E5.pitch_class()), true);
+    /// assert_eq!(c_major.contains_pitch_class(2), false);
+    /// ```
+    pub fn contains_pitch_class(&self, pitch_class: u8) -> bool {
+        self.notes
+            .iter()
+            .any(|note| note.pitch_class() == pitch_class)
+    }
+
+    /// Tells whether every pitch class in a chord is also in another chord, regardless of octaves.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // C major is part of C major seventh, in whatever octave
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// let c_major_seventh = Chord::from_notes(&[E2, G2, B2, C3]);
+    /// assert_eq!(c_major.is_subchord_of(&c_major_seventh), true);
+    /// assert_eq!(c_major_seventh.is_subchord_of(&c_major), false);
+    /// ```
+    pub fn is_subchord_of(&self, other: &Chord) -> bool {
+        self.notes
+            .iter()
+            .all(|note| other.contains_pitch_class(note.pitch_class()))
+    }
+
+    /// Build a chord with all the notes in this chord and another one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // D major over C major
+    /// let c_major = Chord::from_notes(&[C3, E3, G3]);
+    /// let d_major = Chord::from_notes(&[D4, FSharp4, A4]);
+    /// let union = c_major.union(&d_major);
+    /// assert_eq!(union.notes(), &[C3, E3, G3, D4, FSharp4, A4]);
+    /// ```
+    pub fn union(&self, other: &Chord) -> Self {
+        let notes = self
+            .notes
+            .iter()
+            .chain(other.notes.iter())
+            .cloned()
+            .collect::<Vec<Note>>();
+
+        Self::from_notes(&notes)
+    }
+
+    /// Build a chord with the notes that are both in this chord and another one, in the very same
+    /// octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// let e_minor = Chord::from_notes(&[E4, G4, B4]);
+    /// assert_eq!(c_major.intersection(&e_minor).notes(), &[E4, G4]);
+    /// ```
+    pub fn intersection(&self, other: &Chord) -> Self {
+        self.filtered(|note| other.contains(note))
+    }
+
+    /// Build a chord with the notes in this chord that are not in another one, in the very same
+    /// octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// let e_minor = Chord::from_notes(&[E4, G4, B4]);
+    /// assert_eq!(c_major.difference(&e_minor).notes(), &[C4]);
+    /// ```
+    pub fn difference(&self, other: &Chord) -> Self {
+        self.filtered(|note| !other.contains(note))
+    }
+
+    /// Build a chord with all the notes in this chord, plus the notes in another one whose pitch
+    /// classes are not in this one yet.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // The E5 and G5 are already in C major, so only the B5 is added
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// let e_minor = Chord::from_notes(&[E5, G5, B5]);
+    /// assert_eq!(c_major.pitch_class_union(&e_minor).notes(), &[C4, E4, G4, B5]);
+    /// ```
+    pub fn pitch_class_union(&self, other: &Chord) -> Self {
+        let notes = self
+            .notes
+            .iter()
+            .chain(
+                other
+                    .notes
+                    .iter()
+                    .filter(|note| !self.contains_pitch_class(note.pitch_class())),
+            )
+            .cloned()
+            .collect::<Vec<Note>>();
+
+        Self::from_notes(&notes)
+    }
+
+    /// Build a chord with the notes in this chord whose pitch classes are also in another one, in
+    /// any octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// let e_minor = Chord::from_notes(&[E5, G5, B5]);
+    /// assert_eq!(c_major.pitch_class_intersection(&e_minor).notes(), &[E4, G4]);
+    /// ```
+    pub fn pitch_class_intersection(&self, other: &Chord) -> Self {
+        self.filtered(|note| other.contains_pitch_class(note.pitch_class()))
+    }
+
+    /// Build a chord with the notes in this chord whose pitch classes are not in another one, in
+    /// any octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Chord::from_notes(&[C4, E4, G4]);
+    /// let e_minor = Chord::from_notes(&[E5, G5, B5]);
+    /// assert_eq!(c_major.pitch_class_difference(&e_minor).notes(), &[C4]);
+    /// ```
+    pub fn pitch_class_difference(&self, other: &Chord) -> Self {
+        self.filtered(|note| !other.contains_pitch_class(note.pitch_class()))
+    }
+
+    /// Build a chord with the notes in this chord that satisfy some condition.
+    fn filtered<F: Fn(Note) -> bool>(&self, condition: F) -> Self {
+        let notes = self
+            .notes
+            .iter()
+            .cloned()
+            .filter(|note| condition(*note))
+            .collect::<Vec<Note>>();
+
+        Self::from_notes(&notes)
+    }
+
+    /// Tells whether all the notes in a chord belong to a key.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Key::major(C4);
+    ///
+    /// // G major is diatonic to C major
+    /// let g_major = Chord::from_notes(&[G3, B3, D4]);
+    /// assert_eq!(g_major.is_diatonic(&c_major), true);
+    ///
+    /// // D major is chromatic to C major, because of the F#
+    /// let d_major = Chord::from_notes(&[D3, FSharp3, A3]);
+    /// assert_eq!(d_major.is_diatonic(&c_major), false);
+    /// ```
+    pub fn is_diatonic(&self, key: &Key) -> bool {
+        self.notes.iter().all(|note| key.contains(*note))
+    }
+
+    /// Describe a chord as a roman numeral in the context of a key, telling the degree of the key
+    /// that it is built upon, its quality and its inversion.
+    ///
+    /// Nothing is returned if the chord has no identifiable root, or if its type cannot be written
+    /// as a roman numeral (only triads and seventh chords can). Chords whose bass is not one of
+    /// their chord tones are described as if they were in root position.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Key::major(C4);
+    ///
+    /// let chord = Chord::from_notes(&[D3, F3, A3, C4]);
+    /// assert_eq!(chord.roman_numeral(&c_major).unwrap().to_string(), "ii7");
+    ///
+    /// let chord = Chord::from_notes(&[B2, D3, F3, G3]);
+    /// assert_eq!(chord.roman_numeral(&c_major).unwrap().to_string(), "V65");
+    ///
+    /// // Bb major is built on the lowered seventh degree
+    /// let chord = Chord::from_notes(&[ASharp2, D3, F3]);
+    /// assert_eq!(chord.roman_numeral(&c_major).unwrap().to_string(), "bVII");
+    ///
+    /// // The leading tone is raised in minor keys
+    /// let chord = Chord::from_notes(&[GSharp2, B2, D3, F3]);
+    /// assert_eq!(chord.roman_numeral(&Key::minor(A4)).unwrap().to_string(), "#vii°7");
+    /// ```
+    pub fn roman_numeral(&self, key: &Key) -> Option<RomanNumeral> {
+        let root = self.root?;
+        let (degree, accidental) = roman::degree_of(key, root.pitch_class());
+
+        RomanNumeral::new(
+            degree,
+            accidental,
+            self.chord_type.clone(),
+            self.inversion().unwrap_or_default(),
+        )
+    }
+
+    /// Recognize a chord as one of the chromatic chords that have their own labels in harmonic
+    /// analysis (the Neapolitan sixth and the augmented sixth chords), in the context of a key.
+    ///
+    /// Augmented sixth chords are only recognized with the lowered submediant in the bass, while
+    /// the Neapolitan is recognized in any inversion.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_minor = Key::minor(C4);
+    ///
+    /// let chord = Chord::from_notes(&[F3, GSharp3, CSharp4]);
+    /// assert_eq!(chord.chromatic_chord(&c_minor), Some(ChromaticChord::Neapolitan));
+    ///
+    /// // Otherwise read as Ab7
+    /// let chord = Chord::from_notes(&[GSharp2, C4, DSharp4, FSharp4]);
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "Ab7");
+    /// assert_eq!(chord.chromatic_chord(&c_minor), Some(ChromaticChord::GermanSixth));
+    ///
+    /// let chord = Chord::from_notes(&[GSharp2, C4, D4, FSharp4]);
+    /// assert_eq!(chord.chromatic_chord(&c_minor), Some(ChromaticChord::FrenchSixth));
+    ///
+    /// let chord = Chord::from_notes(&[GSharp2, C4, FSharp4]);
+    /// assert_eq!(chord.chromatic_chord(&c_minor), Some(ChromaticChord::ItalianSixth));
+    ///
+    /// // The dominant is not a chromatic chord
+    /// let chord = Chord::from_notes(&[G3, B3, D4, F4]);
+    /// assert_eq!(chord.chromatic_chord(&c_minor), None);
+    /// ```
+    pub fn chromatic_chord(&self, key: &Key) -> Option<ChromaticChord> {
+        ChromaticChord::recognize(self, key)
+    }
+
+    /// Tell the harmonic function of a chord in a key (tonic, subdominant or dominant), which is a
+    /// coarser view than its roman numeral.
+    ///
+    /// Chords borrowed from the parallel key take the function that they would have in there.
+    /// Nothing is returned for chords with no identifiable root, or chords that are chromatic to
+    /// the key otherwise (e.g. secondary dominants, whose function depends on what comes next).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Key::major(C4);
+    ///
+    /// let chord = Chord::from_notes(&[E3, G3, C4]);
+    /// assert_eq!(chord.function(&c_major), Some(HarmonicFunction::Tonic));
+    ///
+    /// let chord = Chord::from_notes(&[A3, C4, E4]);
+    /// assert_eq!(chord.function(&c_major), Some(HarmonicFunction::TonicSubstitute));
+    ///
+    /// let chord = Chord::from_notes(&[D3, F3, A3, C4]);
+    /// assert_eq!(chord.function(&c_major), Some(HarmonicFunction::Subdominant));
+    ///
+    /// let chord = Chord::from_notes(&[B2, D3, F3]);
+    /// assert_eq!(chord.function(&c_major), Some(HarmonicFunction::Dominant));
+    ///
+    /// // The minor subdominant is borrowed from C minor
+    /// let chord = Chord::from_notes(&[F3, GSharp3, C4]);
+    /// assert_eq!(chord.function(&c_major), Some(HarmonicFunction::Subdominant));
     ///
-    /// // Cmaj/E is a triad
-    /// let c_maj_e_notes = [E1, G1, C2];
-    /// let c_maj_e_is_triad = Chord::from_notes(&c_maj_e_notes).is_triad();
-    /// assert_eq!(c_maj_e_is_triad, true);
+    /// // D major is chromatic to C major
+    /// let chord = Chord::from_notes(&[D3, FSharp3, A3]);
+    /// assert_eq!(chord.function(&c_major), None);
+    /// ```
+    pub fn function(&self, key: &Key) -> Option<HarmonicFunction> {
+        HarmonicFunction::of(self, key)
+    }
+
+    /// List the scales built on the root of a chord that contain all of its notes, i.e. the
+    /// scales that can be played over it when improvising.
     ///
-    /// // Cmaj/G is a triad
-    /// let c_maj_g_notes = [G1, C2, E2];
-    /// let c_maj_g_is_triad = Chord::from_notes(&c_maj_g_notes).is_triad();
-    /// assert_eq!(c_maj_g_is_triad, true);
+    /// The perfect fifth of chords with four or more notes is not required to be in the scales, as
+    /// it is usually left out of their voicings anyway.
     ///
-    /// // Cm is a triad
-    /// let c_m_notes = [C1, DSharp1, G1];
-    /// let c_m_is_triad = Chord::from_notes(&c_m_notes).is_triad();
-    /// assert_eq!(c_m_is_triad, true);
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
     ///
-    /// // Cm/D# is a triad
-    /// let c_m_d_sharp_notes = [DSharp1, G1, C2];
-    /// let c_m_d_sharp_is_triad = Chord::from_notes(&c_m_d_sharp_notes).is_triad();
-    /// assert_eq!(c_m_d_sharp_is_triad, true);
+    /// let chord = Chord::from_notes(&[C3, E3, G3, ASharp3]);
+    /// let scales = chord
+    ///     .compatible_scales()
+    ///     .iter()
+    ///     .map(|scale| scale.kind())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     scales,
+    ///     [
+    ///         ScaleKind::Mixolydian,
+    ///         ScaleKind::LydianDominant,
+    ///         ScaleKind::Altered,
+    ///         ScaleKind::HalfWholeDiminished,
+    ///         ScaleKind::WholeTone,
+    ///     ]
+    /// );
     ///
-    /// // Cm/G is a triad
-    /// let c_m_g_notes = [G1, C2, DSharp2];
-    /// let c_m_g_is_triad = Chord::from_notes(&c_m_g_notes).is_triad();
-    /// assert_eq!(c_m_g_is_triad, true);
+    /// let chord = Chord::from_notes(&[D3, F3, A3, C4]);
+    /// assert_eq!(chord.compatible_scales()[0], Scale::new(D3, ScaleKind::Dorian));
+    /// ```
+    pub fn compatible_scales(&self) -> Vec<Scale> {
+        let root = match self.root {
+            Some(root) => root,
+            None => return Vec::new(),
+        };
+        let fifth = (root.pitch_class() + 7) % 12;
+        let mut pitch_classes = self.pitch_classes();
+        pitch_classes.sort_unstable();
+        pitch_classes.dedup();
+        if pitch_classes.len() >= 4 {
+            pitch_classes.retain(|pitch_class| *pitch_class != fifth);
+        }
+
+        CHORD_SCALE_KINDS
+            .iter()
+            .map(|kind| Scale::new(root, *kind))
+            .filter(|scale| {
+                let scale = scale.pitch_classes();
+                pitch_classes
+                    .iter()
+                    .all(|pitch_class| scale.contains(pitch_class))
+            })
+            .collect()
+    }
+
+    /// Tell for each of the notes in a chord, from lowest to highest, whether it belongs to a key.
     ///
-    /// // Cdim is a triad
-    /// let c_dim_notes = [C1, DSharp1, FSharp1];
-    /// let c_dim_is_triad = Chord::from_notes(&c_dim_notes).is_triad();
-    /// assert_eq!(c_m_is_triad, true);
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
     ///
-    /// // Cdim/D# is a triad
-    /// let c_dim_d_sharp_notes = [DSharp1, FSharp1, C2];
-    /// let c_dim_d_sharp_is_triad = Chord::from_notes(&c_dim_d_sharp_notes).is_triad();
-    /// assert_eq!(c_dim_d_sharp_is_triad, true);
+    /// let c_major = Key::major(C4);
     ///
-    /// // Cdim/F# is a triad
-    /// let c_dim_f_sharp_notes = [FSharp1, C2, DSharp2];
-    /// let c_dim_f_sharp_is_triad = Chord::from_notes(&c_dim_f_sharp_notes).is_triad();
-    /// assert_eq!(c_dim_f_sharp_is_triad, true);
+    /// // Only the F# in D major is out of C major
+    /// let d_major = Chord::from_notes(&[D3, FSharp3, A3]);
+    /// assert_eq!(d_major.in_key_mask(&c_major), vec![true, false, true]);
     /// ```
-    pub fn is_triad(&self) -> bool {
-        self.notes.len() == 3
+    pub fn in_key_mask(&self, key: &Key) -> Vec<bool> {
+        self.notes.iter().map(|note| key.contains(*note)).collect()
     }
 
-    /// Get the musician-friendly name of a chord.
+    /// Get the musician-friendly name of a chord, as told by some naming options, or merely by a
+    /// spelling for the default ones.
+    ///
+    /// By default, inversions are named using slash notation, that is, followed by a slash and
+    /// their bass. Notes added to a chord are named after their distance to the root, e.g. "add9".
     ///
     /// # Examples
     /// ```rust
@@ -231,22 +1821,342 @@ impl Chord {
     ///
     /// let chord = Chord::from_notes(&[CSharp1, E1, GSharp1]);
     /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "Dbm");
+    ///
+    /// let chord = Chord::from_notes(&[E1, G1, C2]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C/E");
+    ///
+    /// let chord = Chord::from_notes(&[C1, D1, E1, G1]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "Cadd9");
+    ///
+    /// let chord = Chord::from_notes(&[C1, E1, G1, B1]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "CM7");
+    ///
+    /// let chord = Chord::from_notes(&[G1, B1, C2, E2]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "CM7/G");
+    ///
+    /// let chord = Chord::from_notes(&[FSharp1, A1, C2, E2]);
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "Gbm7b5");
+    ///
+    /// // Incomplete chords are named too
+    /// let chord = Chord::from_notes(&[C1, G1]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C P5");
+    ///
+    /// let chord = Chord::from_notes(&[A1, C2]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "A m3");
+    ///
+    /// // A lone major third implies a major chord
+    /// let chord = Chord::from_notes(&[C1, E1]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C(omit 5)");
+    ///
+    /// let chord = Chord::from_notes(&[DSharp1]);
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "Eb");
+    ///
+    /// // Notes can be spelled after a key
+    /// let chord = Chord::from_notes(&[ASharp1, D2, F2]);
+    /// assert_eq!(chord.name(&Key::major(F4)).unwrap(), "Bb");
+    ///
+    /// // Which spells chords out of the key as well
+    /// let chord = Chord::from_notes(&[ASharp1, D2, F2]);
+    /// assert_eq!(chord.name(&Key::major(C4)).unwrap(), "Bb");
+    ///
+    /// let chord = Chord::from_notes(&[ASharp1, D2, F2]);
+    /// assert_eq!(chord.name(&Key::major(D4)).unwrap(), "Bb");
+    ///
+    /// let chord = Chord::from_notes(&[E1, GSharp1, B1, D2]);
+    /// assert_eq!(chord.name(&Key::major(ASharp4)).unwrap(), "E7");
+    ///
+    /// // Even when that requires double accidentals
+    /// let chord = Chord::from_notes(&[G1, ASharp1, CSharp2, DSharp2]);
+    /// assert_eq!(chord.name(&Key::major(E4)).unwrap(), "D#7/Fx");
+    ///
+    /// // Other options tell how to write every part of the name
+    /// let chord = Chord::from_notes(&[G1, C2, E2, B2]);
+    /// let options = NameOptions::new(FlatOrSharp::Sharp).with_style(NameStyle::Jazz);
+    /// assert_eq!(chord.name(options).unwrap(), "C△7/G");
+    /// assert_eq!(chord.name(options.with_slash(false)).unwrap(), "C△7");
+    ///
+    /// // Silence has no name
+    /// assert_eq!(Chord::from_notes(&[]).name(FlatOrSharp::Flat), None);
     /// ```
-    pub fn name(&self, accidental: FlatOrSharp) -> Option<String> {
-        let root = String::from(
-            self.root?
-                .name(accidental)
-                .trim_end_matches(char::is_numeric),
-        );
-        let quality = match &self.chord_type {
-            ChordType::Triad(quality) => format!("{}", quality),
-            _ => String::new(),
+    pub fn name<'a>(&self, options: impl Into<NameOptions<'a>>) -> Option<String> {
+        let options = options.into();
+        let mut name = String::new();
+        // Writing into a `String` never fails
+        self.write_name(&mut name, &options).unwrap();
+
+        self.root.map(|_| name)
+    }
+
+    /// Write the musician-friendly name of a chord into any `core::fmt::Write` implementor, without
+    /// allocating.
+    ///
+    /// Nothing gets written if the chord has no identifiable root.
+    pub(crate) fn write_name<W: Write>(
+        &self,
+        f: &mut W,
+        options: &NameOptions,
+    ) -> core::fmt::Result {
+        let root = match self.root {
+            Some(root) => root,
+            None => return Ok(()),
         };
 
-        Some(format!("{}{}", root, quality))
+        // Polychords are named after their stacked chords, from the highest to the lowest
+        if let ChordType::Complex(chords) = &self.chord_type {
+            for (index, chord) in chords.iter().rev().enumerate() {
+                if index > 0 {
+                    f.write_char(match options.polychord_style {
+                        PolychordStyle::Slash => '/',
+                        PolychordStyle::Bar => '|',
+                    })?;
+                }
+                chord.write_name(f, &options.with_slash(false))?;
+            }
+
+            return Ok(());
+        }
+
+        write_name(f, root, &self.chord_type, options)?;
+
+        if let Some(omissions) = &self.omissions {
+            let mut names = omissions
+                .iter()
+                .map(|semitones| ADDITION_NAMES[usize::from(semitones % 12)]);
+            f.write_str("(omit ")?;
+            if let Some(name) = names.next() {
+                write_symbol(f, name, options.accidentals)?;
+            }
+            names.try_for_each(|name| {
+                f.write_str(", ")?;
+                write_symbol(f, name, options.accidentals)
+            })?;
+            f.write_char(')')?;
+        }
+
+        // The bass is the lowest note, and it only needs to be named if it is not the root, unless
+        // the root is not even played (as in rootless voicings)
+        let bass = self.notes.first().filter(|bass| {
+            options.slash
+                && bass.pitch_class() != root.pitch_class()
+                && self.contains_pitch_class(root.pitch_class())
+        });
+
+        // Added notes are named after their distance to the root, leaving out any of them that is
+        // already named as the bass, or that merely doubles a chord tone
+        let chord_tones = self
+            .chord_type
+            .semitones_from_root()
+            .iter()
+            .fold(0u16, |mask, semitones| mask | 1 << (semitones % 12));
+        let additions = self
+            .additions
+            .iter()
+            .flatten()
+            .filter(|addition| Some(*addition) != bass)
+            .map(|addition| (addition.pitch_class() + 12 - root.pitch_class()) % 12)
+            .fold(0u16, |mask, offset| mask | 1 << offset)
+            & !chord_tones;
+        if options.additions && additions != 0 {
+            write_additions(f, additions, options.addition_style, options.accidentals)?;
+        }
+
+        match bass {
+            Some(bass) => {
+                f.write_char('/')?;
+                options.spelling.spell_in_chord(self, *bass).write(
+                    f,
+                    options.accidentals,
+                    options.convention,
+                )
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Tell whether a reading of some notes as a chord leaves out its third, or the suspended note
+/// that stands for it.
+fn omits_third(chord: &Chord) -> bool {
+    let third = chord.chord_type.semitones_from_root().get(1);
+
+    chord
+        .omissions
+        .iter()
+        .flatten()
+        .any(|omission| Some(omission) == third)
+}
+
+/// Count how far a reading of some notes as a chord strays from its chord tones, as the number of
+/// pitch classes that it adds to them or omits from them, together with the number of omissions
+/// alone, as omitting notes that are not heard is a greater stretch than adding those that are.
+fn strays(chord: &Chord) -> (u32, u32) {
+    let additions = added_offsets(chord);
+    let omissions = chord.omissions.as_ref().map_or(0, Vec::len) as u32;
+
+    (additions.count_ones() + omissions, omissions)
+}
+
+/// Get the distances in semitones to the root of the notes added to a chord, as a mask, leaving
+/// out those that merely double a chord tone.
+pub(crate) fn added_offsets(chord: &Chord) -> u16 {
+    let root = match chord.root {
+        Some(root) => root.pitch_class(),
+        None => return 0,
+    };
+    let chord_tones = chord
+        .chord_type
+        .semitones_from_root()
+        .iter()
+        .fold(0u16, |mask, semitones| mask | 1 << (semitones % 12));
+
+    chord
+        .additions
+        .iter()
+        .flatten()
+        .map(|addition| (addition.pitch_class() + 12 - root) % 12)
+        .fold(0u16, |mask, offset| mask | 1 << offset)
+        & !chord_tones
+}
+
+/// The distances in semitones to the root of the notes that can be added to a chord, as a mask:
+/// the tensions b9, 9, #9, 11, #11, b13 and 13. Any other note is the third, fifth, seventh or
+/// sixth of some chord, which makes a better reading of it.
+pub(crate) const TENSIONS: u16 = 0b0011_0110_1110;
+
+/// Names of the notes added to (or omitted from) a chord, indexed by their distance in semitones
+/// to its root. Only tensions are ever added, while any of them may be omitted.
+const ADDITION_NAMES: [&str; 12] = [
+    "", "b9", "9", "#9", "3", "11", "#11", "5", "b13", "13", "7", "M7",
+];
+
+/// Write the names of the notes added to a chord, given as a mask of their distances in semitones
+/// to the root.
+fn write_additions<W: Write>(
+    f: &mut W,
+    additions: u16,
+    style: AdditionStyle,
+    accidentals: AccidentalStyle,
+) -> core::fmt::Result {
+    let mut names = ADDITION_NAMES
+        .iter()
+        .enumerate()
+        .filter(|(offset, _)| additions & 1 << offset != 0)
+        .map(|(_, name)| name);
+
+    match style {
+        AdditionStyle::Plain => names.try_for_each(|name| {
+            f.write_str("add")?;
+            write_symbol(f, name, accidentals)
+        }),
+        AdditionStyle::Parenthesized => {
+            f.write_str("(add ")?;
+            if let Some(name) = names.next() {
+                write_symbol(f, name, accidentals)?;
+            }
+            names.try_for_each(|name| {
+                f.write_str(", ")?;
+                write_symbol(f, name, accidentals)
+            })?;
+            f.write_char(')')
+        }
+    }
+}
+
+/// Write the name of a chord with a known root and type into any `core::fmt::Write` implementor,
+/// without allocating.
+pub(crate) fn write_name<W: Write>(
+    f: &mut W,
+    root: Note,
+    chord_type: &ChordType,
+    options: &NameOptions,
+) -> core::fmt::Result {
+    options.spelling.spell_root(root, chord_type).write(
+        f,
+        options.accidentals,
+        options.convention,
+    )?;
+
+    match chord_type {
+        // Dyads are named after the interval between their notes, e.g. "C P5"
+        ChordType::Dyad(quality) => write!(f, " {}", quality),
+        // Single notes are named after their pitch alone
+        ChordType::SingleNote => Ok(()),
+        ChordType::Triad(quality) => quality.write_name(f, options.style),
+        ChordType::Tetrad(quality) => quality.write_name(f, options.style, options.accidentals),
+        ChordType::Pentad(quality) => quality.write_name(f, options.style, options.accidentals),
+        ChordType::Hexad(quality) => quality.write_name(f, options.style, options.accidentals),
+        ChordType::Heptad(quality) => quality.write_name(f, options.style, options.accidentals),
+        _ => Ok(()),
     }
 }
 
+/// LilyPond `\chordmode` steps for the tensions that can be added to a chord, indexed by their
+/// distance in semitones to its root.
+const LILYPOND_TENSIONS: [&str; 12] = [
+    "", "9-", "9", "9+", "", "11", "11+", "", "13-", "13", "", "",
+];
+
+/// Get the LilyPond `\chordmode` step of a chord tone, given as its distance in semitones to the
+/// root, if it belongs to a type of chord: "3" for the third, "5" for the fifth and so on, except
+/// for the suspended notes of suspended chords and the sixth of sixth chords.
+fn lilypond_step(chord_type: &ChordType, semitones: u8) -> Option<u8> {
+    let position = chord_type
+        .semitones_from_root()
+        .iter()
+        .position(|tone| *tone == semitones)?;
+
+    Some(match (position, semitones) {
+        (1, 2) => 2,
+        (1, 5) => 4,
+        (3, 9) => 6,
+        (position, _) => position as u8 * 2 + 1,
+    })
+}
+
+/// Get the LilyPond `\chordmode` modifiers for a type of chord, if it has any.
+fn lilypond_modifiers(chord_type: &ChordType) -> Option<&'static str> {
+    use ChordType::*;
+
+    Some(match chord_type {
+        Dyad(DyadQuality::Perfect(5)) => ":1.5",
+        Triad(TriadQuality::Major) => "",
+        Triad(TriadQuality::Minor) => ":m",
+        Triad(TriadQuality::Diminished) => ":dim",
+        Triad(TriadQuality::Augmented) => ":aug",
+        Triad(TriadQuality::Suspended(2)) => ":sus2",
+        Triad(TriadQuality::Suspended(4)) => ":sus4",
+        Tetrad(TetradQuality::SeventhDominant) => ":7",
+        Tetrad(TetradQuality::SeventhDominantFlatFive) => ":7.5-",
+        Tetrad(TetradQuality::SeventhMajor) => ":maj7",
+        Tetrad(TetradQuality::SeventhMajorFlatFive) => ":maj7.5-",
+        Tetrad(TetradQuality::SeventhMinor) => ":m7",
+        Tetrad(TetradQuality::SeventhMinorMajor) => ":m7+",
+        Tetrad(TetradQuality::SeventhAugmented) => ":aug7",
+        Tetrad(TetradQuality::SeventhDiminished) => ":dim7",
+        Tetrad(TetradQuality::SeventhHalfDiminished) => ":m7.5-",
+        Tetrad(TetradQuality::SeventhAugmentedMajor) => ":maj7.5+",
+        Tetrad(TetradQuality::Sixth) => ":6",
+        Tetrad(TetradQuality::SixthMinor) => ":m6",
+        Tetrad(TetradQuality::SeventhSuspendedFourth) => ":7sus4",
+        Tetrad(TetradQuality::SeventhSuspendedSecond) => ":7sus2",
+        Pentad(PentadQuality::Ninth) => ":9",
+        Pentad(PentadQuality::NinthMajor) => ":maj9",
+        Pentad(PentadQuality::NinthMinor) => ":m9",
+        Pentad(PentadQuality::SeventhFlatNinth) => ":7.9-",
+        Pentad(PentadQuality::SeventhSharpNinth) => ":7.9+",
+        Pentad(PentadQuality::SixNinth) => ":6.9",
+        Pentad(PentadQuality::SixNinthMinor) => ":m6.9",
+        Hexad(HexadQuality::Eleventh) => ":11",
+        Hexad(HexadQuality::EleventhMajor) => ":maj11",
+        Hexad(HexadQuality::EleventhMinor) => ":m11",
+        Hexad(HexadQuality::SeventhMajorSharpEleventh) => ":maj9.11+",
+        Heptad(HeptadQuality::Thirteenth) => ":13",
+        Heptad(HeptadQuality::ThirteenthMajor) => ":maj13",
+        Heptad(HeptadQuality::ThirteenthMinor) => ":m13",
+        _ => return None,
+    })
+}
+
 /// A default, empty chord with no notes, aka "silence"
 impl Default for Chord {
     fn default() -> Self {
@@ -256,15 +2166,75 @@ impl Default for Chord {
             notes: vec![],
             root: None,
             additions: None,
+            omissions: None,
         }
     }
 }
 
+/// Text representation of chords, using sharps for accidentals and naming inversions using slash
+/// notation. Chords with no identifiable root are written as an empty string.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let chord = Chord::from_notes(&[E3, G3, C4]);
+/// assert_eq!(format!("{}", chord), "C/E");
+///
+/// let chord = Chord::from_notes(&[CSharp3, E3, GSharp3, B3]);
+/// assert_eq!(format!("{}", chord), "C#m7");
+/// ```
+impl Display for Chord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_name(f, &NameOptions::default())
+    }
+}
+
+/// What is known about the music surrounding a set of notes, which helps telling which chord they
+/// make.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Context {
+    /// The root that is expected to be heard, even if it is not among the notes (e.g. because
+    /// someone else is playing it in the bass). Only its pitch class matters.
+    pub root: Option<Note>,
+}
+
+/// Convenient methods for building contexts.
+impl Context {
+    /// Build a context that expects some root to be heard.
+    pub fn with_root(root: Note) -> Self {
+        Self { root: Some(root) }
+    }
+}
+
+/// The different ways of naming notes that were added to a chord.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AdditionStyle {
+    /// Right after the chord quality, e.g. "Cadd9" or "Cadd9add11".
+    Plain,
+    /// Grouped between parentheses, e.g. "C(add 9)" or "C(add 9, 11)".
+    Parenthesized,
+}
+
+/// The different ways of separating the chords stacked in a polychord, from the highest to the
+/// lowest.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PolychordStyle {
+    /// A diagonal slash, e.g. "D/C", just like slash chords.
+    Slash,
+    /// A vertical bar, e.g. "D|C", standing for the horizontal line that is used in print for
+    /// telling polychords apart from slash chords.
+    Bar,
+}
+
 /// Different types of chords.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum ChordType {
     Complex(Vec<Chord>),
     Dyad(DyadQuality),
+    Heptad(HeptadQuality),
+    Hexad(HexadQuality),
+    Pentad(PentadQuality),
     Silence,
     SingleNote,
     Tetrad(TetradQuality),
@@ -272,6 +2242,77 @@ pub enum ChordType {
     Unknown,
 }
 
+/// Convenient methods for working with chord types.
+impl ChordType {
+    /// Get the semitone offsets of the notes in a root position chord of this type, relative to its
+    /// root. Chord types with no definite structure yield no offsets at all.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// let major = ChordType::Triad(TriadQuality::Major);
+    /// assert_eq!(major.semitones_from_root(), &[0, 4, 7]);
+    ///
+    /// assert_eq!(ChordType::SingleNote.semitones_from_root(), &[0]);
+    /// assert_eq!(ChordType::Unknown.semitones_from_root(), &[]);
+    /// ```
+    pub fn semitones_from_root(&self) -> &'static [u8] {
+        match self {
+            ChordType::Dyad(quality) => quality.semitones_from_root(),
+            ChordType::Heptad(quality) => quality.semitones_from_root(),
+            ChordType::Hexad(quality) => quality.semitones_from_root(),
+            ChordType::Pentad(quality) => quality.semitones_from_root(),
+            ChordType::SingleNote => &[0],
+            ChordType::Tetrad(quality) => quality.semitones_from_root(),
+            ChordType::Triad(quality) => quality.semitones_from_root(),
+            ChordType::Complex(_) | ChordType::Silence | ChordType::Unknown => &[],
+        }
+    }
+}
+
+/// Support for using dyad qualities wherever a chord type is expected.
+impl From<DyadQuality> for ChordType {
+    fn from(quality: DyadQuality) -> Self {
+        ChordType::Dyad(quality)
+    }
+}
+
+/// Support for using triad qualities wherever a chord type is expected.
+impl From<TriadQuality> for ChordType {
+    fn from(quality: TriadQuality) -> Self {
+        ChordType::Triad(quality)
+    }
+}
+
+/// Support for using tetrad qualities wherever a chord type is expected.
+impl From<TetradQuality> for ChordType {
+    fn from(quality: TetradQuality) -> Self {
+        ChordType::Tetrad(quality)
+    }
+}
+
+/// Support for using pentad qualities wherever a chord type is expected.
+impl From<PentadQuality> for ChordType {
+    fn from(quality: PentadQuality) -> Self {
+        ChordType::Pentad(quality)
+    }
+}
+
+/// Support for using hexad qualities wherever a chord type is expected.
+impl From<HexadQuality> for ChordType {
+    fn from(quality: HexadQuality) -> Self {
+        ChordType::Hexad(quality)
+    }
+}
+
+/// Support for using heptad qualities wherever a chord type is expected.
+impl From<HeptadQuality> for ChordType {
+    fn from(quality: HeptadQuality) -> Self {
+        ChordType::Heptad(quality)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::{ChordType::*, Note::*, *};
@@ -287,6 +2328,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C1),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -302,6 +2344,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -317,6 +2360,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -332,6 +2376,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C1),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -347,6 +2392,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -362,6 +2408,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -377,6 +2424,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C1),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -392,6 +2440,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -407,6 +2456,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -422,6 +2472,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C1),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -437,6 +2488,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C1),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -452,6 +2504,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C1),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -467,6 +2520,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -482,6 +2536,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C1),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -497,6 +2552,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -512,6 +2568,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -527,6 +2584,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C2),
             additions: None,
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -542,6 +2600,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C4),
             additions: Some(vec![C3]),
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -557,6 +2616,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C4),
             additions: Some(vec![C5]),
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -572,6 +2632,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C4),
             additions: Some(vec![D4]),
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -587,6 +2648,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C4),
             additions: Some(vec![CSharp4]),
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -602,6 +2664,7 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C4),
             additions: Some(vec![F4]),
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
@@ -617,8 +2680,324 @@ mod tests {
             notes: Vec::from(notes.as_ref()),
             root: Some(C4),
             additions: Some(vec![FSharp4]),
+            omissions: None,
+        };
+
+        assert_eq!(chord, expected);
+    }
+
+    #[test]
+    fn test_symbols_match_guessed_chords() {
+        let suffixes = [
+            "", "m", "dim", "aug", "sus2", "sus4", "5", "7", "M7", "m7", "mM7", "dim7", "m7b5",
+            "aug7", "M7#5", "7b5", "M7b5", "mM7b5", "6", "m6", "7sus4", "7sus2", "9", "M9", "m9",
+            "7b9", "7#9", "6/9", "m6/9", "11", "M11", "m11", "M7#11", "13", "M13", "m13",
+        ];
+
+        for suffix in suffixes.iter() {
+            let parsed = Chord::from_symbol(&format!("D{}", suffix)).unwrap();
+            let guessed = Chord::from_notes(&parsed.notes);
+
+            assert_eq!(parsed, guessed, "D{}", suffix);
+        }
+    }
+
+    #[test]
+    fn test_slash_symbol_with_foreign_bass() {
+        let chord = Chord::from_symbol("C/D").unwrap();
+        let expected = Chord {
+            intervals: vec![4, 3],
+            chord_type: Triad(TriadQuality::Major),
+            notes: vec![D3, C4, E4, G4],
+            root: Some(C4),
+            additions: Some(vec![D3]),
+            omissions: None,
         };
 
         assert_eq!(chord, expected);
     }
+
+    #[test]
+    fn test_pentads() {
+        let cases: [(&[Note], &str); 5] = [
+            (&[C3, E3, G3, ASharp3, D4], "C9"),
+            (&[C3, E3, G3, B3, D4], "CM9"),
+            (&[C3, DSharp3, G3, ASharp3, D4], "Cm9"),
+            (&[C3, E3, ASharp3, CSharp4, G4], "C7b9"),
+            (&[C3, A3, D4, E4, G4], "C6/9"),
+        ];
+
+        for (notes, name) in cases.iter() {
+            let chord = Chord::from_notes(notes);
+
+            assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some(*name));
+        }
+    }
+
+    #[test]
+    fn test_hexads() {
+        let cases: [(&[Note], &str); 3] = [
+            (&[C3, E3, G3, ASharp3, D4, F4], "C11"),
+            (&[C3, G3, DSharp4, ASharp4, D5, F5], "Cm11"),
+            (&[C3, E3, G3, B3, D4, FSharp4], "CM7#11"),
+        ];
+
+        for (notes, name) in cases.iter() {
+            let chord = Chord::from_notes(notes);
+
+            assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some(*name));
+        }
+    }
+
+    #[test]
+    fn test_thirteenths() {
+        let cases: [(&[Note], &str); 5] = [
+            (&[C3, E3, G3, ASharp3, D4, F4, A4], "C13"),
+            (&[C3, DSharp3, G3, ASharp3, D4, F4, A4], "Cm13"),
+            (&[C3, E3, G3, B3, D4, F4, A4], "CM13"),
+            // Without the 11th
+            (&[C3, E3, G3, ASharp3, D4, A4], "C13"),
+            // Without the 5th nor the 11th
+            (&[C3, E3, ASharp3, D4, A4], "C13"),
+        ];
+
+        for (notes, name) in cases.iter() {
+            let chord = Chord::from_notes(notes);
+
+            assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some(*name));
+        }
+    }
+
+    #[test]
+    fn test_sixths() {
+        let cases: [(&[Note], TetradQuality, Note); 4] = [
+            (&[C3, E3, G3, A3], TetradQuality::Sixth, C3),
+            (&[C3, DSharp3, G3, A3], TetradQuality::SixthMinor, C3),
+            // With the 6th in the bass, these are the relative seventh chords
+            (&[A2, C3, E3, G3], TetradQuality::SeventhMinor, A2),
+            (
+                &[A2, C3, DSharp3, G3],
+                TetradQuality::SeventhHalfDiminished,
+                A2,
+            ),
+        ];
+
+        for (notes, quality, root) in cases.iter() {
+            let chord = Chord::from_notes(notes);
+
+            assert_eq!(chord.chord_type, Tetrad(*quality));
+            assert_eq!(chord.root, Some(*root));
+        }
+    }
+
+    #[test]
+    fn test_suspended_sevenths() {
+        let cases: [(&[Note], TetradQuality, Note); 3] = [
+            (&[G3, C4, D4, F4], TetradQuality::SeventhSuspendedFourth, G3),
+            (&[C4, D4, F4, G4], TetradQuality::SeventhSuspendedFourth, G4),
+            (&[G3, A3, D4, F4], TetradQuality::SeventhSuspendedSecond, G3),
+        ];
+
+        for (notes, quality, root) in cases.iter() {
+            let chord = Chord::from_notes(notes);
+
+            assert_eq!(chord.chord_type, Tetrad(*quality));
+            assert_eq!(chord.root, Some(*root));
+        }
+    }
+
+    #[test]
+    fn test_unicode_names() {
+        // Lower case letters, which are not to be mistaken for flats
+        struct Lower;
+
+        impl NamingConvention for Lower {
+            fn write_pitch(
+                &self,
+                f: &mut dyn core::fmt::Write,
+                letter: char,
+                accidental: Accidental,
+                style: AccidentalStyle,
+            ) -> core::fmt::Result {
+                f.write_char(letter.to_ascii_lowercase())?;
+                f.write_str(accidental.symbol(style))
+            }
+        }
+
+        let options = NameOptions::new(FlatOrSharp::Flat).with_convention(&Lower);
+        let unicode = options.with_accidental_style(AccidentalStyle::Unicode);
+
+        let chord = Chord::from_notes(&[ASharp3, CSharp4, E4, GSharp4]);
+        assert_eq!(chord.name(options).as_deref(), Some("bbm7b5"));
+        assert_eq!(chord.name(unicode).as_deref(), Some("b♭m7♭5"));
+
+        let chord = Chord::from_notes(&[ASharp3, C4, D4, F4]);
+        assert_eq!(chord.name(options).as_deref(), Some("bbadd9"));
+        assert_eq!(chord.name(unicode).as_deref(), Some("b♭add9"));
+    }
+
+    #[test]
+    fn test_addition_names() {
+        let cases: [(&[Note], AdditionStyle, &str); 5] = [
+            (&[C4, D4, E4, G4], AdditionStyle::Plain, "Cadd9"),
+            // Only tensions are added, rather than the fifth in "Caugadd5"
+            (&[C4, E4, G4, GSharp4], AdditionStyle::Plain, "Caddb13"),
+            (&[C4, E4, FSharp4, G4], AdditionStyle::Plain, "Cadd#11"),
+            (
+                &[C4, D4, DSharp4, G4],
+                AdditionStyle::Parenthesized,
+                "Cm(add 9)",
+            ),
+            // Doublings are not additions
+            (&[C4, E4, G4, C5], AdditionStyle::Plain, "C"),
+        ];
+
+        for (notes, style, name) in cases.iter() {
+            let chord = Chord::from_notes(notes);
+
+            assert_eq!(
+                chord
+                    .name(NameOptions::new(FlatOrSharp::Sharp).with_addition_style(*style))
+                    .as_deref(),
+                Some(*name)
+            );
+        }
+
+        // Foreign basses are only named once
+        let chord = Chord::from_symbol("C/D").unwrap();
+        assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some("C/D"));
+        let name = chord.name(NameOptions::new(FlatOrSharp::Sharp).with_slash(false));
+        assert_eq!(name.as_deref(), Some("Cadd9"));
+    }
+
+    #[test]
+    fn test_tetrad_names() {
+        let suffixes = [
+            "7", "M7", "m7", "mM7", "dim7", "m7b5", "aug7", "M7#5", "7b5", "M7b5", "mM7b5", "6",
+            "m6", "7sus4", "7sus2",
+        ];
+
+        for suffix in suffixes.iter() {
+            let symbol = format!("D{}", suffix);
+            let chord = Chord::from_symbol(&symbol).unwrap();
+
+            assert_eq!(chord.name(FlatOrSharp::Sharp), Some(symbol));
+        }
+    }
+
+    #[test]
+    fn test_spread_tetrads() {
+        // Spread voicing of C major seventh
+        let chord = Chord::from_notes(&[C3, G3, E4, B4]);
+        assert_eq!(chord.chord_type, Tetrad(TetradQuality::SeventhMajor));
+        assert_eq!(chord.root, Some(C3));
+        assert_eq!(chord.notes, vec![C3, G3, E4, B4]);
+
+        // C major with an added ninth above it
+        let chord = Chord::from_notes(&[C4, E4, G4, D5]);
+        assert_eq!(chord.chord_type, Triad(TriadQuality::Major));
+        assert_eq!(chord.root, Some(C4));
+        assert_eq!(chord.additions, Some(vec![D5]));
+        assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some("Cadd9"));
+    }
+
+    #[test]
+    fn test_polychords() {
+        // F# major over C major
+        let chord = Chord::from_notes(&[C3, E3, G3, FSharp4, ASharp4, CSharp5]);
+        assert_eq!(
+            chord.chord_type,
+            Complex(vec![
+                Chord::from_notes(&[C3, E3, G3]),
+                Chord::from_notes(&[FSharp4, ASharp4, CSharp5])
+            ])
+        );
+        assert_eq!(chord.root, Some(C3));
+        assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some("F#/C"));
+
+        // When there are several ways of splitting the notes, the lowest chord is kept smallest
+        let chord = Chord::from_notes(&[C3, E3, G3, B3, CSharp4, F4, GSharp4]);
+        assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some("C#7/C"));
+
+        // Stacked chords sharing some note are not polychords
+        let chord = Chord::from_notes(&[C3, E3, G3, G4, B4, D5, E5]);
+        assert!(!matches!(chord.chord_type, Complex(_)));
+
+        // Inversions of extended chords are not split
+        let chord = Chord::from_notes(&[E3, G3, B3, C4, D4, FSharp4]);
+        assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some("CM7#11/E"));
+        let chord = Chord::from_notes(&[C3, E3, G3, D4, FSharp4, A4]);
+        assert_eq!(chord.name(FlatOrSharp::Sharp).as_deref(), Some("D11/C"));
+
+        // Both stacked chords need to be complete
+        let chord = Chord::from_notes(&[C3, E3, ASharp3, FSharp4, A4, CSharp5]);
+        assert!(!matches!(chord.chord_type, Complex(_)));
+    }
+
+    #[test]
+    fn test_rootless_voicings() {
+        let context = Context::with_root(C2);
+
+        // Rootless voicings keep their own notes, with the root below the bass
+        let chord = Chord::from_notes_with(&[E3, G3, B3, D4], &context);
+        assert_eq!(chord.chord_type, Pentad(PentadQuality::NinthMajor));
+        assert_eq!(chord.root, Some(C3));
+        assert_eq!(chord.notes, vec![E3, G3, B3, D4]);
+
+        // Chords already built on the expected root are left untouched
+        let chord = Chord::from_notes_with(&[C3, E3, G3], &context);
+        assert_eq!(chord, Chord::from_notes(&[C3, E3, G3]));
+
+        // Notes that make no chord on the expected root are read without context
+        let chord = Chord::from_notes_with(&[CSharp3, F3, GSharp3], &context);
+        assert_eq!(chord, Chord::from_notes(&[CSharp3, F3, GSharp3]));
+    }
+
+    #[test]
+    fn test_fuzzy_matching() {
+        // Unrecognizable stacks are read as the closest chord, with a lower confidence
+        let chord = Chord::from_notes(&[C3, E3, F3, G3, B3]);
+        let (fuzzy, confidence) = Chord::from_notes_fuzzy(&[C3, E3, F3, G3, B3]);
+        assert!(fuzzy.confidence() > chord.confidence());
+        assert_eq!(fuzzy.root, Some(C3));
+        assert_eq!(fuzzy.omissions, Some(vec![14]));
+        assert_eq!(confidence, fuzzy.confidence());
+        assert!(confidence < 1.0);
+
+        // Silence has nothing to match
+        assert_eq!(Chord::from_notes_fuzzy(&[]), (Chord::default(), 0.0));
+    }
+
+    #[test]
+    fn test_roman_numerals() {
+        use alloc::string::ToString;
+
+        let numerals = |scale: Scale, key: Key| {
+            scale
+                .diatonic_sevenths()
+                .iter()
+                .map(|(_, chord)| chord.roman_numeral(&key).unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            numerals(Scale::new(C4, ScaleKind::Major), Key::major(C4)),
+            ["IM7", "ii7", "iii7", "IVM7", "V7", "vi7", "viiø7"]
+        );
+        assert_eq!(
+            numerals(Scale::new(A3, ScaleKind::HarmonicMinor), Key::minor(A3)),
+            ["iM7", "iiø7", "III+M7", "iv7", "V7", "VIM7", "#vii°7"]
+        );
+
+        // Chromatic roots in major keys
+        let c_major = Key::major(C4);
+        let roots = [CSharp4, DSharp4, FSharp4, GSharp4, ASharp4]
+            .iter()
+            .map(|root| {
+                let chord = Chord::build(*root, TriadQuality::Major).unwrap();
+                chord.roman_numeral(&c_major).unwrap().to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(roots, ["bII", "bIII", "#IV", "bVI", "bVII"]);
+    }
 }
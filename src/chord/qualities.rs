@@ -1,8 +1,9 @@
-use alloc::string::String;
-use core::fmt::{Display, Formatter};
+use crate::chord::guess;
+use crate::note::{write_symbol, AccidentalStyle};
+use core::fmt::{Display, Formatter, Write};
 
 /// Different qualities of dyads.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum DyadQuality {
     Augmented(u8),
     Diminished(u8),
@@ -12,26 +13,85 @@ pub enum DyadQuality {
     Perfect(u8),
 }
 
-/// Text representations of dyad qualities.
+/// Semitone offsets from the lower note for every dyad quality, indexed by interval size.
+const DYAD_SEMITONES: [[u8; 2]; 26] = [
+    [0, 0],
+    [0, 1],
+    [0, 2],
+    [0, 3],
+    [0, 4],
+    [0, 5],
+    [0, 6],
+    [0, 7],
+    [0, 8],
+    [0, 9],
+    [0, 10],
+    [0, 11],
+    [0, 12],
+    [0, 13],
+    [0, 14],
+    [0, 15],
+    [0, 16],
+    [0, 17],
+    [0, 18],
+    [0, 19],
+    [0, 20],
+    [0, 21],
+    [0, 22],
+    [0, 23],
+    [0, 24],
+    [0, 25],
+];
+
+/// Convenient methods for working with dyad qualities.
+impl DyadQuality {
+    /// Get the semitone offsets of the notes in a dyad of this quality, relative to its lower note.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(DyadQuality::Perfect(5).semitones_from_root(), &[0, 7]);
+    /// assert_eq!(DyadQuality::Minor(3).semitones_from_root(), &[0, 3]);
+    /// assert_eq!(DyadQuality::Perfect(0).semitones_from_root(), &[0, 0]);
+    /// assert_eq!(DyadQuality::Indeterminate.semitones_from_root(), &[]);
+    /// ```
+    pub fn semitones_from_root(&self) -> &'static [u8] {
+        match (0..DYAD_SEMITONES.len()).find(|size| guess::dyad_quality(*size as u8) == *self) {
+            Some(size) => &DYAD_SEMITONES[size],
+            None => &[],
+        }
+    }
+}
+
+/// Text representations of dyad qualities, using the usual shorthand for intervals (e.g. "P5" for a
+/// perfect fifth, or "m3" for a minor third).
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// assert_eq!(format!("{}", DyadQuality::Perfect(5)), "P5");
+/// assert_eq!(format!("{}", DyadQuality::Major(3)), "M3");
+/// assert_eq!(format!("{}", DyadQuality::Augmented(4)), "A4");
+/// ```
 impl Display for DyadQuality {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         use DyadQuality::*;
 
-        let name = match self {
-            Augmented(x) => format!("aug{}", x),
-            Diminished(x) => format!("sus{}", x),
-            Indeterminate => String::from("(ind)"),
-            Major(x) => format!("{}", x),
-            Minor(x) => format!("m{}", x),
-            Perfect(x) => format!("P{}", x),
-        };
-
-        write!(f, "{}", name)
+        match self {
+            Augmented(x) => write!(f, "A{}", x),
+            Diminished(x) => write!(f, "d{}", x),
+            Indeterminate => f.write_str("(ind)"),
+            Major(x) => write!(f, "M{}", x),
+            Minor(x) => write!(f, "m{}", x),
+            Perfect(x) => write!(f, "P{}", x),
+        }
     }
 }
 
 /// Different qualities of triads.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum TriadQuality {
     Augmented,
     Diminished,
@@ -41,26 +101,61 @@ pub enum TriadQuality {
     Suspended(u8),
 }
 
-/// Text representations of triad qualities.
-impl Display for TriadQuality {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+/// Convenient methods for working with triad qualities.
+impl TriadQuality {
+    /// Get the semitone offsets of the notes in a root position triad of this quality, relative to
+    /// its root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(TriadQuality::Major.semitones_from_root(), &[0, 4, 7]);
+    /// assert_eq!(TriadQuality::Suspended(4).semitones_from_root(), &[0, 5, 7]);
+    /// assert_eq!(TriadQuality::Indeterminate.semitones_from_root(), &[]);
+    /// ```
+    pub fn semitones_from_root(&self) -> &'static [u8] {
         use TriadQuality::*;
 
-        let name = match self {
-            Augmented => String::from("aug"),
-            Diminished => String::from("dim"),
-            Indeterminate => String::from("ind"),
-            Major => String::new(),
-            Minor => String::from("m"),
-            Suspended(x) => format!("sus{}", x),
-        };
+        match self {
+            Augmented => &[0, 4, 8],
+            Diminished => &[0, 3, 6],
+            Major => &[0, 4, 7],
+            Minor => &[0, 3, 7],
+            Suspended(2) => &[0, 2, 7],
+            Suspended(4) => &[0, 5, 7],
+            Indeterminate | Suspended(_) => &[],
+        }
+    }
 
-        write!(f, "{}", name)
+    /// Write the symbol for this quality in some style into any `core::fmt::Write` implementor.
+    pub(crate) fn write_name<W: Write>(&self, f: &mut W, style: NameStyle) -> core::fmt::Result {
+        use NameStyle::*;
+        use TriadQuality::*;
+
+        match (self, style) {
+            (Augmented, Standard | Plain) => f.write_str("aug"),
+            (Augmented, Jazz | Classical) => f.write_char('+'),
+            (Diminished, Standard | Plain) => f.write_str("dim"),
+            (Diminished, Jazz | Classical) => f.write_char('°'),
+            (Indeterminate, _) => f.write_str("ind"),
+            (Major, _) => Ok(()),
+            (Minor, Jazz) => f.write_char('-'),
+            (Minor, _) => f.write_char('m'),
+            (Suspended(x), _) => write!(f, "sus{}", x),
+        }
+    }
+}
+
+/// Text representations of triad qualities, in the standard style.
+impl Display for TriadQuality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_name(f, NameStyle::Standard)
     }
 }
 
 /// Different types of tetrads.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum TetradQuality {
     Indeterminate,
     SeventhDiminished,       // Tertian
@@ -74,28 +169,351 @@ pub enum TetradQuality {
     SeventhDiminishedMajor,  // Non-Tertian
     SeventhHalfDiminished,   // Tertian | Also: SeventhMinorFlatFive
     SeventhAugmentedMajor,   // Tertian | Also: SeventhMajorSharpFive
+    Sixth,                   // Tertian | Same notes as the relative SeventhMinor
+    SixthMinor,              // Tertian | Same notes as the relative SeventhHalfDiminished
+    SeventhSuspendedFourth,  // Non-tertian
+    SeventhSuspendedSecond,  // Non-tertian
+}
+
+/// Convenient methods for working with tetrad qualities.
+impl TetradQuality {
+    /// Get the semitone offsets of the notes in a root position tetrad of this quality, relative to
+    /// its root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(TetradQuality::SeventhDominant.semitones_from_root(), &[0, 4, 7, 10]);
+    /// assert_eq!(TetradQuality::Indeterminate.semitones_from_root(), &[]);
+    /// ```
+    pub fn semitones_from_root(&self) -> &'static [u8] {
+        use TetradQuality::*;
+
+        match self {
+            Indeterminate => &[],
+            SeventhDiminished => &[0, 3, 6, 9],
+            SeventhDominant => &[0, 4, 7, 10],
+            SeventhDominantFlatFive => &[0, 4, 6, 10],
+            SeventhMajor => &[0, 4, 7, 11],
+            SeventhMajorFlatFive => &[0, 4, 6, 11],
+            SeventhMinor => &[0, 3, 7, 10],
+            SeventhMinorMajor => &[0, 3, 7, 11],
+            SeventhAugmented => &[0, 4, 8, 10],
+            SeventhDiminishedMajor => &[0, 3, 6, 11],
+            SeventhHalfDiminished => &[0, 3, 6, 10],
+            SeventhAugmentedMajor => &[0, 4, 8, 11],
+            Sixth => &[0, 4, 7, 9],
+            SixthMinor => &[0, 3, 7, 9],
+            SeventhSuspendedFourth => &[0, 5, 7, 10],
+            SeventhSuspendedSecond => &[0, 2, 7, 10],
+        }
+    }
+
+    /// Write the symbol for this quality in some style, and its accidentals in another, into any
+    /// `core::fmt::Write` implementor.
+    pub(crate) fn write_name<W: Write>(
+        &self,
+        f: &mut W,
+        style: NameStyle,
+        accidentals: AccidentalStyle,
+    ) -> core::fmt::Result {
+        use NameStyle::*;
+        use TetradQuality::*;
+
+        let name = match (self, style) {
+            (Indeterminate, _) => "ind",
+            (SeventhDiminished, Standard | Plain) => "dim7",
+            (SeventhDiminished, Jazz | Classical) => "°7",
+            (SeventhDominant, _) => "7",
+            (SeventhDominantFlatFive, _) => "7b5",
+            (SeventhMajor, Standard | Classical) => "M7",
+            (SeventhMajor, Plain) => "maj7",
+            (SeventhMajor, Jazz) => "△7",
+            (SeventhMajorFlatFive, Standard | Classical) => "M7b5",
+            (SeventhMajorFlatFive, Plain) => "maj7b5",
+            (SeventhMajorFlatFive, Jazz) => "△7b5",
+            (SeventhMinor, Standard | Plain | Classical) => "m7",
+            (SeventhMinor, Jazz) => "-7",
+            (SeventhMinorMajor, Standard | Classical) => "mM7",
+            (SeventhMinorMajor, Plain) => "mmaj7",
+            (SeventhMinorMajor, Jazz) => "-△7",
+            (SeventhAugmented, Standard | Plain) => "aug7",
+            (SeventhAugmented, Jazz | Classical) => "+7",
+            (SeventhDiminishedMajor, Standard) => "mM7b5",
+            (SeventhDiminishedMajor, Plain) => "mmaj7b5",
+            (SeventhDiminishedMajor, Jazz) => "°△7",
+            (SeventhDiminishedMajor, Classical) => "°M7",
+            (SeventhHalfDiminished, Standard | Plain) => "m7b5",
+            (SeventhHalfDiminished, Jazz | Classical) => "ø7",
+            (SeventhAugmentedMajor, Standard) => "M7#5",
+            (SeventhAugmentedMajor, Plain) => "maj7#5",
+            (SeventhAugmentedMajor, Jazz) => "+△7",
+            (SeventhAugmentedMajor, Classical) => "+M7",
+            (Sixth, _) => "6",
+            (SixthMinor, Standard | Plain | Classical) => "m6",
+            (SixthMinor, Jazz) => "-6",
+            (SeventhSuspendedFourth, _) => "7sus4",
+            (SeventhSuspendedSecond, _) => "7sus2",
+        };
+
+        write_symbol(f, name, accidentals)
+    }
 }
 
-/// Text representations of tetrad qualities.
+/// Text representations of tetrad qualities, in the standard style.
 impl Display for TetradQuality {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        use TetradQuality::*;
+        self.write_name(f, NameStyle::Standard, AccidentalStyle::Ascii)
+    }
+}
+
+/// Different qualities of pentads, that is, ninth chords and their relatives.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PentadQuality {
+    Indeterminate,
+    Ninth,             // Dominant seventh plus major ninth
+    NinthMajor,        // Major seventh plus major ninth
+    NinthMinor,        // Minor seventh plus major ninth
+    SeventhFlatNinth,  // Dominant seventh plus minor ninth
+    SeventhSharpNinth, // Dominant seventh plus augmented ninth
+    SixNinth,          // Major sixth plus major ninth
+    SixNinthMinor,     // Minor sixth plus major ninth
+}
+
+/// Convenient methods for working with pentad qualities.
+impl PentadQuality {
+    /// Get the semitone offsets of the notes in a root position pentad of this quality, relative to
+    /// its root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(PentadQuality::Ninth.semitones_from_root(), &[0, 4, 7, 10, 14]);
+    /// assert_eq!(PentadQuality::SixNinth.semitones_from_root(), &[0, 4, 7, 9, 14]);
+    /// ```
+    pub fn semitones_from_root(&self) -> &'static [u8] {
+        use PentadQuality::*;
+
+        match self {
+            Indeterminate => &[],
+            Ninth => &[0, 4, 7, 10, 14],
+            NinthMajor => &[0, 4, 7, 11, 14],
+            NinthMinor => &[0, 3, 7, 10, 14],
+            SeventhFlatNinth => &[0, 4, 7, 10, 13],
+            SeventhSharpNinth => &[0, 4, 7, 10, 15],
+            SixNinth => &[0, 4, 7, 9, 14],
+            SixNinthMinor => &[0, 3, 7, 9, 14],
+        }
+    }
+
+    /// Write the symbol for this quality in some style, and its accidentals in another, into any
+    /// `core::fmt::Write` implementor.
+    pub(crate) fn write_name<W: Write>(
+        &self,
+        f: &mut W,
+        style: NameStyle,
+        accidentals: AccidentalStyle,
+    ) -> core::fmt::Result {
+        use NameStyle::*;
+        use PentadQuality::*;
 
-        let name = match self {
-            Indeterminate => String::from("ind"),
-            SeventhDiminished => String::from("dim7"),
-            SeventhDominant => String::from("7"),
-            SeventhDominantFlatFive => String::from("7b5"),
-            SeventhMajor => String::from("M7"),
-            SeventhMajorFlatFive => String::from("M7b5"),
-            SeventhMinor => String::from("m7"),
-            SeventhMinorMajor => String::from("mM7"),
-            SeventhAugmented => String::from("aug7"),
-            SeventhDiminishedMajor => String::from("mM7b5"),
-            SeventhHalfDiminished => String::from("m7b5"),
-            SeventhAugmentedMajor => String::from("M7#5"),
+        let name = match (self, style) {
+            (Indeterminate, _) => "ind",
+            (Ninth, _) => "9",
+            (NinthMajor, Standard | Classical) => "M9",
+            (NinthMajor, Plain) => "maj9",
+            (NinthMajor, Jazz) => "△9",
+            (NinthMinor, Standard | Plain | Classical) => "m9",
+            (NinthMinor, Jazz) => "-9",
+            (SeventhFlatNinth, _) => "7b9",
+            (SeventhSharpNinth, _) => "7#9",
+            (SixNinth, _) => "6/9",
+            (SixNinthMinor, Standard | Plain | Classical) => "m6/9",
+            (SixNinthMinor, Jazz) => "-6/9",
         };
 
-        write!(f, "{}", name)
+        write_symbol(f, name, accidentals)
     }
 }
+
+/// Text representations of pentad qualities, in the standard style.
+impl Display for PentadQuality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_name(f, NameStyle::Standard, AccidentalStyle::Ascii)
+    }
+}
+
+/// Different qualities of hexads, that is, eleventh chords.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum HexadQuality {
+    Indeterminate,
+    Eleventh,                  // Dominant ninth plus perfect eleventh
+    EleventhMajor,             // Major ninth plus perfect eleventh
+    EleventhMinor,             // Minor ninth plus perfect eleventh
+    SeventhMajorSharpEleventh, // Major ninth plus augmented eleventh
+}
+
+/// Convenient methods for working with hexad qualities.
+impl HexadQuality {
+    /// Get the semitone offsets of the notes in a root position hexad of this quality, relative to
+    /// its root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(HexadQuality::Eleventh.semitones_from_root(), &[0, 4, 7, 10, 14, 17]);
+    /// ```
+    pub fn semitones_from_root(&self) -> &'static [u8] {
+        use HexadQuality::*;
+
+        match self {
+            Indeterminate => &[],
+            Eleventh => &[0, 4, 7, 10, 14, 17],
+            EleventhMajor => &[0, 4, 7, 11, 14, 17],
+            EleventhMinor => &[0, 3, 7, 10, 14, 17],
+            SeventhMajorSharpEleventh => &[0, 4, 7, 11, 14, 18],
+        }
+    }
+
+    /// Write the symbol for this quality in some style, and its accidentals in another, into any
+    /// `core::fmt::Write` implementor.
+    pub(crate) fn write_name<W: Write>(
+        &self,
+        f: &mut W,
+        style: NameStyle,
+        accidentals: AccidentalStyle,
+    ) -> core::fmt::Result {
+        use HexadQuality::*;
+        use NameStyle::*;
+
+        let name = match (self, style) {
+            (Indeterminate, _) => "ind",
+            (Eleventh, _) => "11",
+            (EleventhMajor, Standard | Classical) => "M11",
+            (EleventhMajor, Plain) => "maj11",
+            (EleventhMajor, Jazz) => "△11",
+            (EleventhMinor, Standard | Plain | Classical) => "m11",
+            (EleventhMinor, Jazz) => "-11",
+            (SeventhMajorSharpEleventh, Standard | Classical) => "M7#11",
+            (SeventhMajorSharpEleventh, Plain) => "maj7#11",
+            (SeventhMajorSharpEleventh, Jazz) => "△7#11",
+        };
+
+        write_symbol(f, name, accidentals)
+    }
+}
+
+/// Text representations of hexad qualities, in the standard style.
+impl Display for HexadQuality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_name(f, NameStyle::Standard, AccidentalStyle::Ascii)
+    }
+}
+
+/// Different qualities of heptads, that is, thirteenth chords.
+///
+/// Thirteenth chords are often voiced without their 5th or their 11th, so those partial voicings
+/// also get one of these qualities despite having fewer than seven notes.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum HeptadQuality {
+    Indeterminate,
+    Thirteenth,      // Dominant eleventh plus major thirteenth
+    ThirteenthMajor, // Major eleventh plus major thirteenth
+    ThirteenthMinor, // Minor eleventh plus major thirteenth
+}
+
+/// Convenient methods for working with heptad qualities.
+impl HeptadQuality {
+    /// Get the semitone offsets of the notes in a root position heptad of this quality, relative to
+    /// its root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(HeptadQuality::Thirteenth.semitones_from_root(), &[0, 4, 7, 10, 14, 17, 21]);
+    /// ```
+    pub fn semitones_from_root(&self) -> &'static [u8] {
+        use HeptadQuality::*;
+
+        match self {
+            Indeterminate => &[],
+            Thirteenth => &[0, 4, 7, 10, 14, 17, 21],
+            ThirteenthMajor => &[0, 4, 7, 11, 14, 17, 21],
+            ThirteenthMinor => &[0, 3, 7, 10, 14, 17, 21],
+        }
+    }
+
+    /// Write the symbol for this quality in some style, and its accidentals in another, into any
+    /// `core::fmt::Write` implementor.
+    pub(crate) fn write_name<W: Write>(
+        &self,
+        f: &mut W,
+        style: NameStyle,
+        accidentals: AccidentalStyle,
+    ) -> core::fmt::Result {
+        use HeptadQuality::*;
+        use NameStyle::*;
+
+        let name = match (self, style) {
+            (Indeterminate, _) => "ind",
+            (Thirteenth, _) => "13",
+            (ThirteenthMajor, Standard | Classical) => "M13",
+            (ThirteenthMajor, Plain) => "maj13",
+            (ThirteenthMajor, Jazz) => "△13",
+            (ThirteenthMinor, Standard | Plain | Classical) => "m13",
+            (ThirteenthMinor, Jazz) => "-13",
+        };
+
+        write_symbol(f, name, accidentals)
+    }
+}
+
+/// Text representations of heptad qualities, in the standard style.
+impl Display for HeptadQuality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_name(f, NameStyle::Standard, AccidentalStyle::Ascii)
+    }
+}
+
+/// The different styles for writing chord qualities in chord symbols.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let options = NameOptions::default();
+/// let chord = Chord::from_notes(&[C3, E3, G3, B3]);
+/// assert_eq!(chord.name(options.with_style(NameStyle::Standard)).unwrap(), "CM7");
+/// assert_eq!(chord.name(options.with_style(NameStyle::Plain)).unwrap(), "Cmaj7");
+/// assert_eq!(chord.name(options.with_style(NameStyle::Jazz)).unwrap(), "C△7");
+/// assert_eq!(chord.name(options.with_style(NameStyle::Classical)).unwrap(), "CM7");
+///
+/// let chord = Chord::from_notes(&[B3, D4, F4, A4]);
+/// assert_eq!(chord.name(options.with_style(NameStyle::Standard)).unwrap(), "Bm7b5");
+/// assert_eq!(chord.name(options.with_style(NameStyle::Plain)).unwrap(), "Bm7b5");
+/// assert_eq!(chord.name(options.with_style(NameStyle::Jazz)).unwrap(), "Bø7");
+/// assert_eq!(chord.name(options.with_style(NameStyle::Classical)).unwrap(), "Bø7");
+///
+/// let chord = Chord::from_notes(&[D3, F3, A3, C4]);
+/// assert_eq!(chord.name(options.with_style(NameStyle::Jazz)).unwrap(), "D-7");
+///
+/// let chord = Chord::from_notes(&[B3, D4, F4]);
+/// assert_eq!(chord.name(options.with_style(NameStyle::Standard)).unwrap(), "Bdim");
+/// assert_eq!(chord.name(options.with_style(NameStyle::Classical)).unwrap(), "B°");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum NameStyle {
+    /// Short letters and abbreviations, e.g. "CM7", "Cm7b5" or "Cdim". This is the style used when
+    /// displaying chords and qualities.
+    Standard,
+    /// Spelled-out abbreviations that can hardly be misread, e.g. "Cmaj7", "Cm7b5" or "Cdim".
+    Plain,
+    /// The symbols found in lead sheets and real books, e.g. "C△7", "C-7", "Cø7" or "C°".
+    Jazz,
+    /// The symbols found in harmony textbooks, e.g. "CM7", "Cm7", "Cø7", "C°7" or "C+".
+    Classical,
+}
@@ -0,0 +1,242 @@
+use crate::error::Error;
+use crate::note::parse_pitch_class;
+use crate::prelude::*;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+/// Every accepted chord symbol suffix, together with the chord type it stands for.
+const SUFFIXES: &[(&str, ChordType)] = &[
+    // Dyads
+    ("5", ChordType::Dyad(DyadQuality::Perfect(5))),
+    // Triads
+    ("", ChordType::Triad(TriadQuality::Major)),
+    ("M", ChordType::Triad(TriadQuality::Major)),
+    ("maj", ChordType::Triad(TriadQuality::Major)),
+    ("m", ChordType::Triad(TriadQuality::Minor)),
+    ("min", ChordType::Triad(TriadQuality::Minor)),
+    ("-", ChordType::Triad(TriadQuality::Minor)),
+    ("dim", ChordType::Triad(TriadQuality::Diminished)),
+    ("°", ChordType::Triad(TriadQuality::Diminished)),
+    ("o", ChordType::Triad(TriadQuality::Diminished)),
+    ("aug", ChordType::Triad(TriadQuality::Augmented)),
+    ("+", ChordType::Triad(TriadQuality::Augmented)),
+    ("sus2", ChordType::Triad(TriadQuality::Suspended(2))),
+    ("sus4", ChordType::Triad(TriadQuality::Suspended(4))),
+    ("sus", ChordType::Triad(TriadQuality::Suspended(4))),
+    // Tetrads
+    ("7", ChordType::Tetrad(TetradQuality::SeventhDominant)),
+    ("dom7", ChordType::Tetrad(TetradQuality::SeventhDominant)),
+    ("M7", ChordType::Tetrad(TetradQuality::SeventhMajor)),
+    ("maj7", ChordType::Tetrad(TetradQuality::SeventhMajor)),
+    ("ma7", ChordType::Tetrad(TetradQuality::SeventhMajor)),
+    ("Δ", ChordType::Tetrad(TetradQuality::SeventhMajor)),
+    ("Δ7", ChordType::Tetrad(TetradQuality::SeventhMajor)),
+    ("m7", ChordType::Tetrad(TetradQuality::SeventhMinor)),
+    ("min7", ChordType::Tetrad(TetradQuality::SeventhMinor)),
+    ("-7", ChordType::Tetrad(TetradQuality::SeventhMinor)),
+    ("mM7", ChordType::Tetrad(TetradQuality::SeventhMinorMajor)),
+    ("mmaj7", ChordType::Tetrad(TetradQuality::SeventhMinorMajor)),
+    (
+        "m(maj7)",
+        ChordType::Tetrad(TetradQuality::SeventhMinorMajor),
+    ),
+    ("-Δ7", ChordType::Tetrad(TetradQuality::SeventhMinorMajor)),
+    ("dim7", ChordType::Tetrad(TetradQuality::SeventhDiminished)),
+    ("°7", ChordType::Tetrad(TetradQuality::SeventhDiminished)),
+    ("o7", ChordType::Tetrad(TetradQuality::SeventhDiminished)),
+    (
+        "m7b5",
+        ChordType::Tetrad(TetradQuality::SeventhHalfDiminished),
+    ),
+    (
+        "min7b5",
+        ChordType::Tetrad(TetradQuality::SeventhHalfDiminished),
+    ),
+    (
+        "-7b5",
+        ChordType::Tetrad(TetradQuality::SeventhHalfDiminished),
+    ),
+    ("ø", ChordType::Tetrad(TetradQuality::SeventhHalfDiminished)),
+    (
+        "ø7",
+        ChordType::Tetrad(TetradQuality::SeventhHalfDiminished),
+    ),
+    ("aug7", ChordType::Tetrad(TetradQuality::SeventhAugmented)),
+    ("+7", ChordType::Tetrad(TetradQuality::SeventhAugmented)),
+    ("7#5", ChordType::Tetrad(TetradQuality::SeventhAugmented)),
+    (
+        "M7#5",
+        ChordType::Tetrad(TetradQuality::SeventhAugmentedMajor),
+    ),
+    (
+        "maj7#5",
+        ChordType::Tetrad(TetradQuality::SeventhAugmentedMajor),
+    ),
+    (
+        "+M7",
+        ChordType::Tetrad(TetradQuality::SeventhAugmentedMajor),
+    ),
+    (
+        "7b5",
+        ChordType::Tetrad(TetradQuality::SeventhDominantFlatFive),
+    ),
+    (
+        "M7b5",
+        ChordType::Tetrad(TetradQuality::SeventhMajorFlatFive),
+    ),
+    (
+        "maj7b5",
+        ChordType::Tetrad(TetradQuality::SeventhMajorFlatFive),
+    ),
+    (
+        "mM7b5",
+        ChordType::Tetrad(TetradQuality::SeventhDiminishedMajor),
+    ),
+    (
+        "mmaj7b5",
+        ChordType::Tetrad(TetradQuality::SeventhDiminishedMajor),
+    ),
+    (
+        "7sus4",
+        ChordType::Tetrad(TetradQuality::SeventhSuspendedFourth),
+    ),
+    (
+        "7sus",
+        ChordType::Tetrad(TetradQuality::SeventhSuspendedFourth),
+    ),
+    (
+        "7sus2",
+        ChordType::Tetrad(TetradQuality::SeventhSuspendedSecond),
+    ),
+    ("6", ChordType::Tetrad(TetradQuality::Sixth)),
+    ("M6", ChordType::Tetrad(TetradQuality::Sixth)),
+    ("maj6", ChordType::Tetrad(TetradQuality::Sixth)),
+    ("m6", ChordType::Tetrad(TetradQuality::SixthMinor)),
+    ("min6", ChordType::Tetrad(TetradQuality::SixthMinor)),
+    ("-6", ChordType::Tetrad(TetradQuality::SixthMinor)),
+    // Pentads
+    ("9", ChordType::Pentad(PentadQuality::Ninth)),
+    ("M9", ChordType::Pentad(PentadQuality::NinthMajor)),
+    ("maj9", ChordType::Pentad(PentadQuality::NinthMajor)),
+    ("m9", ChordType::Pentad(PentadQuality::NinthMinor)),
+    ("min9", ChordType::Pentad(PentadQuality::NinthMinor)),
+    ("-9", ChordType::Pentad(PentadQuality::NinthMinor)),
+    ("7b9", ChordType::Pentad(PentadQuality::SeventhFlatNinth)),
+    ("7#9", ChordType::Pentad(PentadQuality::SeventhSharpNinth)),
+    ("6/9", ChordType::Pentad(PentadQuality::SixNinth)),
+    ("69", ChordType::Pentad(PentadQuality::SixNinth)),
+    ("m6/9", ChordType::Pentad(PentadQuality::SixNinthMinor)),
+    ("m69", ChordType::Pentad(PentadQuality::SixNinthMinor)),
+    // Hexads
+    ("11", ChordType::Hexad(HexadQuality::Eleventh)),
+    ("M11", ChordType::Hexad(HexadQuality::EleventhMajor)),
+    ("maj11", ChordType::Hexad(HexadQuality::EleventhMajor)),
+    ("m11", ChordType::Hexad(HexadQuality::EleventhMinor)),
+    ("min11", ChordType::Hexad(HexadQuality::EleventhMinor)),
+    ("-11", ChordType::Hexad(HexadQuality::EleventhMinor)),
+    (
+        "M7#11",
+        ChordType::Hexad(HexadQuality::SeventhMajorSharpEleventh),
+    ),
+    (
+        "maj7#11",
+        ChordType::Hexad(HexadQuality::SeventhMajorSharpEleventh),
+    ),
+    // Heptads
+    ("13", ChordType::Heptad(HeptadQuality::Thirteenth)),
+    ("M13", ChordType::Heptad(HeptadQuality::ThirteenthMajor)),
+    ("maj13", ChordType::Heptad(HeptadQuality::ThirteenthMajor)),
+    ("m13", ChordType::Heptad(HeptadQuality::ThirteenthMinor)),
+    ("min13", ChordType::Heptad(HeptadQuality::ThirteenthMinor)),
+    ("-13", ChordType::Heptad(HeptadQuality::ThirteenthMinor)),
+];
+
+/// MIDI key number of C4, the octave where the roots of parsed chords are placed.
+const ROOT_OCTAVE_START: u8 = 60;
+/// MIDI key number of C3, the octave where the basses of parsed slash chords are placed.
+const BASS_OCTAVE_START: u8 = 48;
+
+/// Parse a chord symbol such as "Cmaj7" or "G/B" into a chord voiced in closed position.
+pub(crate) fn parse(symbol: &str) -> Result<Chord, Error> {
+    let symbol = symbol.trim();
+
+    // Split the slash bass, if any. Slashes can also be part of some suffixes, such as "6/9".
+    let bass = match symbol.rfind('/') {
+        Some(position) => match parse_pitch_class(&symbol[position + 1..]) {
+            Some((bass, "")) => Some((position, bass)),
+            _ => None,
+        },
+        None => None,
+    };
+    let (main, bass) = match bass {
+        Some((position, bass)) => (&symbol[..position], Some(bass)),
+        None => (symbol, None),
+    };
+
+    let (root, suffix) = parse_pitch_class(main).ok_or(Error::InvalidChordSymbol)?;
+    let chord_type = SUFFIXES
+        .iter()
+        .find(|(candidate, _)| *candidate == suffix)
+        .map(|(_, chord_type)| chord_type.clone())
+        .ok_or(Error::InvalidChordSymbol)?;
+
+    // Voice the chord in root position, starting in the 4th octave
+    let root = Note::from(ROOT_OCTAVE_START + root);
+    let mut tones = chord_type
+        .semitones_from_root()
+        .iter()
+        .map(|semitones| Note::from(root.midi_key_number() + semitones))
+        .collect::<Vec<Note>>();
+
+    let bass = bass
+        .map(|bass| Note::from(BASS_OCTAVE_START + bass))
+        .filter(|bass| bass.pitch_class() != root.pitch_class());
+
+    let (notes, root, additions) = match bass {
+        // Inversions: start from the bass, then stack the rest of the chord tones on top of it
+        Some(bass) => match tones
+            .iter()
+            .position(|tone| tone.pitch_class() == bass.pitch_class())
+        {
+            Some(position) => {
+                tones.rotate_left(position);
+                tones[0] = bass;
+                for index in 1..tones.len() {
+                    // Find the lowest note above the previous one with the right pitch class
+                    let previous = tones[index - 1].midi_key_number();
+                    let distance = (tones[index].pitch_class() + 12 - previous % 12) % 12;
+                    tones[index] = Note::from(previous + if distance == 0 { 12 } else { distance });
+                }
+                let root = tones[(tones.len() - position) % tones.len()];
+
+                (tones.clone(), root, None)
+            }
+            // The bass does not belong to the chord, so it is an addition below it
+            None => {
+                let mut notes = vec![bass];
+                notes.extend(tones.iter());
+
+                (notes, root, Some(vec![bass]))
+            }
+        },
+        None => (tones.clone(), root, None),
+    };
+
+    Ok(Chord {
+        intervals: guess::intervals(&tones),
+        chord_type,
+        notes,
+        root: Some(root),
+        additions,
+        omissions: None,
+    })
+}
+
+/// Support for parsing chords from their symbols through `str::parse`.
+impl FromStr for Chord {
+    type Err = Error;
+
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        parse(symbol)
+    }
+}
@@ -0,0 +1,562 @@
+use crate::error::Error;
+use crate::prelude::*;
+use crate::tuning::PITCH_BEND_CENTER;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+/// The MIDI controller number of the sustain pedal.
+const SUSTAIN_PEDAL: u8 = 64;
+
+/// The pitch bend range, in semitones, that MIDI instruments use unless told otherwise.
+const DEFAULT_BEND_RANGE: u8 = 2;
+
+/// Keeps track of the chord being played on a MIDI instrument, out of the note on and note off
+/// messages that it sends.
+///
+/// A new chord is reported whenever a note starts sounding and the chord changes. Releasing some
+/// of the notes while others are still held does not report anything, as the chord is just fading
+/// away, and releasing all of them reports silence.
+///
+/// The sustain pedal (controller 64) is honored: notes released while it is down keep sounding,
+/// and are part of the chord, until it is released.
+///
+/// Notes are told apart by the channel that they are played on, so MPE (MIDI Polyphonic
+/// Expression) controllers, which send every note on a channel of its own along with its own pitch
+/// bend, are supported: the notes sounding on all the channels make a single chord.
+///
+/// Notes on a channel that is bent by more than 50 cents are taken as the chromatic neighbor that
+/// they sound closest to, so bending a string on a guitar-to-MIDI converter or sliding a finger on
+/// an MPE controller does not make wrong chords. The same goes for notes retuned through the MIDI
+/// Tuning Standard.
+///
+/// Chords can also be debounced with a hold time, so that rolled attacks, in which the notes of a
+/// chord start sounding one after another, are reported once rather than note by note.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChordTracker {
+    held: BTreeSet<(Note, u8)>,
+    sustained: BTreeSet<(Note, u8)>,
+    pedal: bool,
+    bends: BTreeMap<u8, u16>,
+    bend_range: u8,
+    tuning: TuningTable,
+    current: Option<Chord>,
+    hold_time: u64,
+    changed_at: Option<u64>,
+}
+
+/// Chord trackers start with no notes being held and the default pitch bend range.
+impl Default for ChordTracker {
+    fn default() -> Self {
+        Self {
+            held: BTreeSet::new(),
+            sustained: BTreeSet::new(),
+            pedal: false,
+            bends: BTreeMap::new(),
+            bend_range: DEFAULT_BEND_RANGE,
+            tuning: TuningTable::new(),
+            current: None,
+            hold_time: 0,
+            changed_at: None,
+        }
+    }
+}
+
+/// Convenient methods for tracking chords.
+impl ChordTracker {
+    /// Build a chord tracker with no notes being held.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long, in milliseconds, the sounding notes need to stay the same before
+    /// `process_at` and `poll` report the chord that they make.
+    pub fn with_hold_time(mut self, milliseconds: u64) -> Self {
+        self.hold_time = milliseconds;
+
+        self
+    }
+
+    /// Get the hold time of the tracker, in milliseconds.
+    pub fn hold_time(&self) -> u64 {
+        self.hold_time
+    }
+
+    /// Set how many semitones the pitch wheel bends notes when pushed all the way up or down.
+    /// Defaults to 2, as per the MIDI specification.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let bend = |value| MidiMessage::PitchBend { channel: 0, value };
+    /// let mut tracker = ChordTracker::new().with_bend_range(12);
+    /// tracker.process(&MidiMessage::note_on(0, C4, 100));
+    ///
+    /// // A quarter of the way up is three semitones
+    /// tracker.process(&bend(8192 + 2048));
+    /// assert_eq!(tracker.sounding_notes(), [DSharp4]);
+    /// ```
+    pub fn with_bend_range(mut self, semitones: u8) -> Self {
+        self.bend_range = semitones;
+
+        self
+    }
+
+    /// Get the pitch bend range of the tracker, in semitones.
+    pub fn bend_range(&self) -> u8 {
+        self.bend_range
+    }
+
+    /// Set the tuning of the instrument being tracked.
+    pub fn with_tuning(mut self, tuning: TuningTable) -> Self {
+        self.tuning = tuning;
+
+        self
+    }
+
+    /// Get the tuning of the instrument being tracked, as retuned by the MIDI Tuning Standard
+    /// messages fed into the tracker so far.
+    pub fn tuning(&self) -> &TuningTable {
+        &self.tuning
+    }
+
+    /// Feed a MIDI message into the tracker, and get the new chord if it has just changed.
+    /// Besides note on and note off messages, only the sustain pedal and pitch bend are taken into
+    /// account.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = ChordTracker::new();
+    /// tracker.process(&MidiMessage::note_on(0, C4, 100));
+    /// tracker.process(&MidiMessage::note_on(0, E4, 100));
+    /// let chord = tracker.process(&MidiMessage::note_on(0, G4, 100)).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C");
+    ///
+    /// // Releasing some of the notes keeps the chord as it was
+    /// assert_eq!(tracker.process(&MidiMessage::note_off(0, E4)), None);
+    /// assert_eq!(tracker.held_notes(), [C4, G4]);
+    ///
+    /// // Adding a note makes a new chord
+    /// let chord = tracker.process(&MidiMessage::note_on(0, DSharp4, 100)).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "Cm");
+    ///
+    /// // Releasing everything makes silence, where note on messages with no velocity count as
+    /// // note off messages
+    /// tracker.process(&MidiMessage::note_off(0, C4));
+    /// tracker.process(&MidiMessage::note_on(0, DSharp4, 0));
+    /// let chord = tracker.process(&MidiMessage::note_off(0, G4)).unwrap();
+    /// assert!(chord.is_silence());
+    /// assert_eq!(tracker.held_notes(), []);
+    /// ```
+    ///
+    /// With the sustain pedal down, released notes keep sounding:
+    ///
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let pedal = |value| MidiMessage::ControlChange { channel: 0, controller: 64, value };
+    /// let mut tracker = ChordTracker::new();
+    ///
+    /// // An arpeggio under the pedal
+    /// tracker.process(&pedal(127));
+    /// for note in [A2, C3, E3] {
+    ///     tracker.process(&MidiMessage::note_on(0, note, 80));
+    ///     tracker.process(&MidiMessage::note_off(0, note));
+    /// }
+    /// assert!(tracker.held_notes().is_empty());
+    /// assert_eq!(tracker.sounding_notes(), [A2, C3, E3]);
+    /// assert_eq!(tracker.current().unwrap().name(FlatOrSharp::Sharp).unwrap(), "Am");
+    ///
+    /// // Releasing the pedal silences everything
+    /// assert!(tracker.process(&pedal(0)).unwrap().is_silence());
+    /// ```
+    pub fn process(&mut self, message: &MidiMessage) -> Option<Chord> {
+        if self.apply(message) {
+            self.analyze()
+        } else {
+            None
+        }
+    }
+
+    /// Feed a MIDI Tuning Standard SysEx message into the tracker, and get the new chord if
+    /// retuning the instrument has just changed it. See `TuningTable::apply_sysex` for the
+    /// supported messages.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = ChordTracker::new();
+    /// tracker.process(&MidiMessage::note_on(0, C4, 100));
+    /// tracker.process(&MidiMessage::note_on(0, E4, 100));
+    /// tracker.process(&MidiMessage::note_on(0, G4, 100));
+    ///
+    /// // Retuning E4 70 cents down makes it sound closer to D#4
+    /// let sysex = [0xF0, 0x7F, 0x7F, 0x08, 0x02, 0x00, 0x01, 64, 63, 0x26, 0x33, 0xF7];
+    /// let chord = tracker.process_sysex(&sysex).unwrap().unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "Cm");
+    /// ```
+    pub fn process_sysex(&mut self, bytes: &[u8]) -> Result<Option<Chord>, Error> {
+        self.tuning.apply_sysex(bytes)?;
+        if self.is_silent() {
+            return Ok(None);
+        }
+
+        Ok(self.analyze())
+    }
+
+    /// Feed a MIDI message into the tracker along with the time at which it was received, in
+    /// milliseconds, and get the new chord once the sounding notes have been stable for the hold
+    /// time. Timestamps are expected to never go backwards.
+    ///
+    /// A chord that is still pending when no more messages arrive can be picked up later on with
+    /// `poll`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = ChordTracker::new().with_hold_time(30);
+    ///
+    /// // A rolled attack does not report every partial chord on the way
+    /// assert_eq!(tracker.process_at(&MidiMessage::note_on(0, C3, 90), 0), None);
+    /// assert_eq!(tracker.process_at(&MidiMessage::note_on(0, E3, 90), 10), None);
+    /// assert_eq!(tracker.process_at(&MidiMessage::note_on(0, G3, 90), 20), None);
+    ///
+    /// // Not stable for long enough yet
+    /// assert_eq!(tracker.poll(40), None);
+    /// let chord = tracker.poll(50).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C");
+    ///
+    /// // A later message reports the previous chord if it was already stable
+    /// tracker.process_at(&MidiMessage::note_on(0, ASharp3, 90), 1000);
+    /// let chord = tracker.process_at(&MidiMessage::note_off(0, C3), 1200).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "C7");
+    /// ```
+    pub fn process_at(&mut self, message: &MidiMessage, timestamp: u64) -> Option<Chord> {
+        let stable = self.poll(timestamp);
+        if self.apply(message) {
+            self.changed_at = Some(timestamp);
+        }
+
+        stable.or_else(|| self.poll(timestamp))
+    }
+
+    /// Tell the tracker what time it is, in milliseconds, and get the chord made by the sounding
+    /// notes if they have just been stable for the hold time.
+    pub fn poll(&mut self, timestamp: u64) -> Option<Chord> {
+        let changed_at = self.changed_at?;
+        if timestamp.saturating_sub(changed_at) < self.hold_time {
+            return None;
+        }
+        self.changed_at = None;
+
+        self.analyze()
+    }
+
+    /// Apply a MIDI message to the notes being held and sustained, and tell whether the chord may
+    /// have changed.
+    fn apply(&mut self, message: &MidiMessage) -> bool {
+        match *message {
+            // Running status makes keyboards send note off messages as note on with no velocity
+            MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity: 0,
+            } => return self.apply(&MidiMessage::note_off(channel, note)),
+            MidiMessage::NoteOn { channel, note, .. } => {
+                self.held.insert((note, channel));
+            }
+            MidiMessage::NoteOff { channel, note, .. } => {
+                self.held.remove(&(note, channel));
+                if self.pedal {
+                    self.sustained.insert((note, channel));
+                }
+                if !self.is_silent() {
+                    return false;
+                }
+            }
+            MidiMessage::ControlChange {
+                controller: SUSTAIN_PEDAL,
+                value,
+                ..
+            } => {
+                self.pedal = value >= 64;
+                if self.pedal {
+                    return false;
+                }
+                self.sustained.clear();
+                if !self.is_silent() {
+                    return false;
+                }
+            }
+            MidiMessage::PitchBend { channel, value } => {
+                self.bends.insert(channel, value);
+                return self
+                    .held
+                    .union(&self.sustained)
+                    .any(|(_, sounding_on)| *sounding_on == channel);
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Get the notes being held at the moment, from lowest to highest, as played (i.e. regardless
+    /// of pitch bend).
+    pub fn held_notes(&self) -> Vec<Note> {
+        self.held
+            .iter()
+            .map(|(note, _)| *note)
+            .collect::<BTreeSet<Note>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Get the notes sounding at the moment, from lowest to highest. These are the notes being
+    /// held, plus the ones being sustained by the pedal, as resolved after pitch bend.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = ChordTracker::new();
+    /// tracker.process(&MidiMessage::note_on(0, A3, 100));
+    /// tracker.process(&MidiMessage::note_on(1, C4, 100));
+    /// tracker.process(&MidiMessage::note_on(2, E4, 100));
+    /// assert_eq!(tracker.current().unwrap().name(FlatOrSharp::Sharp).unwrap(), "Am");
+    ///
+    /// // Bending C4 by less than 50 cents keeps it as it is
+    /// tracker.process(&MidiMessage::PitchBend { channel: 1, value: 10000 });
+    /// assert_eq!(tracker.sounding_notes(), [A3, C4, E4]);
+    ///
+    /// // Bending it further turns it into C#4, and the chord into A major
+    /// let chord = tracker.process(&MidiMessage::PitchBend { channel: 1, value: 12500 }).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "A");
+    /// assert_eq!(tracker.sounding_notes(), [A3, CSharp4, E4]);
+    /// assert_eq!(tracker.held_notes(), [A3, C4, E4]);
+    /// ```
+    pub fn sounding_notes(&self) -> Vec<Note> {
+        self.held
+            .union(&self.sustained)
+            .map(|(note, channel)| self.resolve(*note, *channel))
+            .collect::<BTreeSet<Note>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Get the notes sounding at the moment along with the channels that they are sounding on,
+    /// from lowest to highest, as played (i.e. regardless of pitch bend). A note sounding on
+    /// several channels at once is listed once for each of them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // An MPE controller plays every note on a channel of its own
+    /// let mut tracker = ChordTracker::new();
+    /// tracker.process(&MidiMessage::note_on(1, D4, 100));
+    /// tracker.process(&MidiMessage::note_on(2, FSharp4, 100));
+    /// tracker.process(&MidiMessage::note_on(3, A4, 100));
+    /// tracker.process(&MidiMessage::note_on(4, FSharp4, 100));
+    /// tracker.process(&MidiMessage::PitchBend { channel: 3, value: 9000 });
+    /// assert_eq!(tracker.current().unwrap().name(FlatOrSharp::Sharp).unwrap(), "D");
+    ///
+    /// // Releasing a note on a channel keeps it sounding on the others
+    /// tracker.process(&MidiMessage::note_off(2, FSharp4));
+    /// assert_eq!(tracker.sounding_notes(), [D4, FSharp4, A4]);
+    /// assert_eq!(tracker.sounding_notes_by_channel(), [(D4, 1), (FSharp4, 4), (A4, 3)]);
+    /// assert_eq!(tracker.pitch_bend(3), 9000);
+    /// assert_eq!(tracker.pitch_bend(1), 8192);
+    /// ```
+    pub fn sounding_notes_by_channel(&self) -> Vec<(Note, u8)> {
+        self.held.union(&self.sustained).copied().collect()
+    }
+
+    /// Get the last pitch bend received on a channel, from 0 to 16383, where 8192 is the center
+    /// (no bend).
+    pub fn pitch_bend(&self, channel: u8) -> u16 {
+        self.bends
+            .get(&channel)
+            .copied()
+            .unwrap_or(PITCH_BEND_CENTER)
+    }
+
+    /// Get the last chord reported by the tracker, if any.
+    pub fn current(&self) -> Option<&Chord> {
+        self.current.as_ref()
+    }
+
+    /// Tell which note a note played on a channel sounds closest to, after the tuning of the
+    /// instrument and the pitch bend of the channel. Notes that would go out of the MIDI range are
+    /// kept as they are.
+    fn resolve(&self, note: Note, channel: u8) -> Note {
+        let bend = Cents::from_pitch_bend(self.pitch_bend(channel), self.bend_range);
+        let tuning =
+            Cents::from_semitones(self.tuning.pitch(note) - f32::from(note.midi_key_number()));
+        let offset = (bend + tuning).semitones();
+        // Only offsets beyond 50 cents move notes to their neighbors
+        let semitones = libm::copysignf(libm::ceilf(offset.abs() - 0.5), offset);
+
+        note.transposed(semitones as i8).unwrap_or(note)
+    }
+
+    /// Tell whether no note is sounding at all.
+    fn is_silent(&self) -> bool {
+        self.held.is_empty() && self.sustained.is_empty()
+    }
+
+    /// Tell which chord the sounding notes make, and report it if it is not the current one.
+    fn analyze(&mut self) -> Option<Chord> {
+        let chord = Chord::from_notes(&self.sounding_notes());
+        if self.current.as_ref() == Some(&chord) {
+            return None;
+        }
+        self.current = Some(chord.clone());
+
+        Some(chord)
+    }
+}
+
+/// Recognizes the chords played as arpeggios or broken chords on a MIDI instrument, i.e. with
+/// their notes played one after another rather than together.
+///
+/// Notes are gathered from the moment that the first one is played, and the chord that they make
+/// is reported once the arpeggio completes, by going back to the pitch class that it started on
+/// after at least three different ones, or once the time window closes, whatever happens first.
+/// Notes can be played in any octave, and releasing them is irrelevant.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArpeggioTracker {
+    window: u64,
+    notes: Vec<Note>,
+    started_at: Option<u64>,
+    current: Option<Chord>,
+}
+
+/// Convenient methods for tracking arpeggios.
+impl ArpeggioTracker {
+    /// Build an arpeggio tracker that gathers notes during a time window, in milliseconds.
+    pub fn new(window: u64) -> Self {
+        Self {
+            window,
+            notes: Vec::new(),
+            started_at: None,
+            current: None,
+        }
+    }
+
+    /// Get the time window of the tracker, in milliseconds.
+    pub fn window(&self) -> u64 {
+        self.window
+    }
+
+    /// Feed a MIDI message into the tracker along with the time at which it was received, in
+    /// milliseconds, and get the new chord if an arpeggio has just been recognized. Timestamps are
+    /// expected to never go backwards. Messages other than note on are ignored.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = ArpeggioTracker::new(1000);
+    ///
+    /// // Going back to the first pitch class completes the arpeggio
+    /// assert_eq!(tracker.process_at(&MidiMessage::note_on(0, A2, 80), 0), None);
+    /// assert_eq!(tracker.process_at(&MidiMessage::note_on(0, E3, 80), 100), None);
+    /// assert_eq!(tracker.process_at(&MidiMessage::note_on(0, C4, 80), 200), None);
+    /// let chord = tracker.process_at(&MidiMessage::note_on(0, A4, 80), 300).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "Am");
+    ///
+    /// // The note that completes an arpeggio starts the next one
+    /// assert_eq!(tracker.played_notes(), [A4]);
+    /// ```
+    pub fn process_at(&mut self, message: &MidiMessage, timestamp: u64) -> Option<Chord> {
+        let closed = self.poll(timestamp);
+
+        let note = match *message {
+            MidiMessage::NoteOn { velocity: 0, .. } => return closed,
+            MidiMessage::NoteOn { note, .. } => note,
+            _ => return closed,
+        };
+        let completed = if self.completes(note) {
+            self.close()
+        } else {
+            None
+        };
+        if self.notes.is_empty() {
+            self.started_at = Some(timestamp);
+        }
+        self.notes.push(note);
+
+        closed.or(completed)
+    }
+
+    /// Tell the tracker what time it is, in milliseconds, and get the chord made by the notes
+    /// gathered so far if the time window has just closed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = ArpeggioTracker::new(500);
+    /// tracker.process_at(&MidiMessage::note_on(0, G2, 80), 0);
+    /// tracker.process_at(&MidiMessage::note_on(0, B2, 80), 150);
+    /// tracker.process_at(&MidiMessage::note_on(0, D3, 80), 300);
+    /// tracker.process_at(&MidiMessage::note_on(0, F3, 80), 450);
+    ///
+    /// assert_eq!(tracker.poll(499), None);
+    /// let chord = tracker.poll(500).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "G7");
+    /// assert!(tracker.played_notes().is_empty());
+    /// ```
+    pub fn poll(&mut self, timestamp: u64) -> Option<Chord> {
+        let started_at = self.started_at?;
+        if timestamp.saturating_sub(started_at) < self.window {
+            return None;
+        }
+
+        self.close()
+    }
+
+    /// Get the notes gathered for the arpeggio in progress, in the order that they were played.
+    pub fn played_notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    /// Get the last chord reported by the tracker, if any.
+    pub fn current(&self) -> Option<&Chord> {
+        self.current.as_ref()
+    }
+
+    /// Tell whether playing a note completes the arpeggio in progress.
+    fn completes(&self, note: Note) -> bool {
+        let pitch_classes = self
+            .notes
+            .iter()
+            .map(|note| note.pitch_class())
+            .collect::<BTreeSet<u8>>();
+
+        pitch_classes.len() >= 3
+            && self
+                .notes
+                .first()
+                .is_some_and(|first| first.pitch_class() == note.pitch_class())
+    }
+
+    /// Tell which chord the gathered notes make, start over, and report the chord if it is not the
+    /// current one.
+    fn close(&mut self) -> Option<Chord> {
+        let chord = Chord::from_notes_any_octave(&self.notes);
+        self.notes.clear();
+        self.started_at = None;
+        if self.current.as_ref() == Some(&chord) {
+            return None;
+        }
+        self.current = Some(chord.clone());
+
+        Some(chord)
+    }
+}
@@ -0,0 +1,189 @@
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+/// The different ways of laying out the notes of a chord.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum VoicingKind {
+    /// Every chord tone exactly once, as close to each other as possible.
+    Closed,
+    /// Every chord tone exactly once, with the second lowest note raised an octave so that the
+    /// voicing spans more than an octave.
+    Open,
+    /// A closed voicing plus the root doubled an octave above.
+    Doubled,
+}
+
+/// Enumerate all the voicings of a kind for a chord whose root and type (or quality) are known,
+/// with all their notes falling inside a range.
+///
+/// Only the pitch class of the root matters, as voicings are looked for in every octave of the
+/// range, and on top of every chord tone (i.e. in every inversion). Voicings are returned sorted,
+/// each of them as a set of notes sorted from the lowest to the highest.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// // C major in closed position, in every inversion between C4 and C5
+/// let closed = voicings(C4, TriadQuality::Major, C4..=C5, VoicingKind::Closed);
+/// assert_eq!(closed, vec![vec![C4, E4, G4], vec![E4, G4, C5]]);
+///
+/// // C major in open position
+/// let open = voicings(C4, TriadQuality::Major, C3..=C5, VoicingKind::Open);
+/// assert!(open.contains(&vec![C3, G3, E4]));
+///
+/// // C major with a doubled root
+/// let doubled = voicings(C4, TriadQuality::Major, C4..=C5, VoicingKind::Doubled);
+/// assert_eq!(doubled, vec![vec![C4, E4, G4, C5]]);
+///
+/// // Every voicing is recognized as the chord it was generated from
+/// for voicing in voicings(C4, TetradQuality::SeventhMajor, C3..=C5, VoicingKind::Closed) {
+///     assert_eq!(Chord::from_notes(&voicing).root().unwrap().pitch_class(), 0);
+/// }
+/// ```
+pub fn voicings(
+    root: Note,
+    chord_type: impl Into<ChordType>,
+    range: RangeInclusive<Note>,
+    kind: VoicingKind,
+) -> Vec<Vec<Note>> {
+    let chord_type = chord_type.into();
+
+    // Distinct pitch classes of the chord tones, starting from the root
+    let mut tones = Vec::new();
+    for semitones in chord_type.semitones_from_root() {
+        let pitch_class = (root.pitch_class() + semitones) % 12;
+        if !tones.contains(&pitch_class) {
+            tones.push(pitch_class);
+        }
+    }
+
+    let mut voicings = BTreeSet::new();
+    for bass in range.start().midi_key_number()..=range.end().midi_key_number() {
+        let bass = Note::from(bass);
+        let position = match tones.iter().position(|tone| *tone == bass.pitch_class()) {
+            Some(position) => position,
+            None => continue,
+        };
+
+        let closed = match closed_voicing(bass, &tones, position) {
+            Some(closed) => closed,
+            None => continue,
+        };
+        let voicing = match kind {
+            VoicingKind::Closed => Some(closed),
+            VoicingKind::Open => open_voicing(closed),
+            VoicingKind::Doubled => doubled_voicing(closed, root.pitch_class()),
+        };
+
+        if let Some(voicing) = voicing {
+            if voicing.iter().all(|note| range.contains(note)) {
+                voicings.insert(voicing);
+            }
+        }
+    }
+
+    voicings.into_iter().collect()
+}
+
+/// Stack every chord tone on top of a bass, in closed position, starting from the tone at some
+/// position.
+fn closed_voicing(bass: Note, tones: &[u8], position: usize) -> Option<Vec<Note>> {
+    let mut notes = vec![bass];
+    for tone in tones
+        .iter()
+        .cycle()
+        .skip(position + 1)
+        .take(tones.len() - 1)
+    {
+        let previous = notes[notes.len() - 1];
+        let distance = (tone + 12 - previous.pitch_class()) % 12;
+        notes.push(previous.transposed(distance as i8).ok()?);
+    }
+
+    Some(notes)
+}
+
+/// Raise the second lowest note of a closed voicing an octave.
+fn open_voicing(mut notes: Vec<Note>) -> Option<Vec<Note>> {
+    if notes.len() < 3 {
+        return None;
+    }
+    notes[1] = notes[1].transposed(12).ok()?;
+    notes.sort();
+
+    Some(notes)
+}
+
+/// Double the root of a closed voicing an octave above.
+fn doubled_voicing(mut notes: Vec<Note>, root: u8) -> Option<Vec<Note>> {
+    let root = notes.iter().find(|note| note.pitch_class() == root)?;
+    let doubled = root.transposed(12).ok()?;
+    if notes.contains(&doubled) {
+        return None;
+    }
+    notes.push(doubled);
+    notes.sort();
+
+    Some(notes)
+}
+
+/// Find the voicing of a chord (of any kind) that can be reached from the notes of another chord
+/// with the smoothest voice leading, that is, moving its notes as little as possible. All the
+/// notes in the voicing fall inside a range.
+///
+/// Nothing is returned if there is no voicing of the chord inside the range.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// // From C major, G major is best reached as G/B, keeping the G and moving the rest by step
+/// let c_major = Chord::from_notes(&[C4, E4, G4]);
+/// let g_major = smoothest_voicing(&c_major, G4, TriadQuality::Major, C3..=C6).unwrap();
+/// assert_eq!(g_major, vec![B3, D4, G4]);
+///
+/// // There is no room for G major between C4 and D4
+/// assert_eq!(smoothest_voicing(&c_major, G4, TriadQuality::Major, C4..=D4), None);
+/// ```
+pub fn smoothest_voicing(
+    current: &Chord,
+    root: Note,
+    chord_type: impl Into<ChordType>,
+    range: RangeInclusive<Note>,
+) -> Option<Vec<Note>> {
+    let chord_type = chord_type.into();
+
+    [VoicingKind::Closed, VoicingKind::Open, VoicingKind::Doubled]
+        .iter()
+        .flat_map(|kind| voicings(root, chord_type.clone(), range.clone(), *kind))
+        .min_by_key(|voicing| voice_leading_distance(current.notes(), voicing))
+}
+
+/// Measure how far apart two sets of notes are in terms of voice leading, as the number of
+/// semitones that every note in each set needs to move to reach the closest note in the other
+/// set. Identical sets are at a distance of 0.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// // From C major to A minor, only the G needs to move up a whole tone (and back)
+/// assert_eq!(voice_leading_distance(&[C4, E4, G4], &[C4, E4, A4]), 4);
+/// assert_eq!(voice_leading_distance(&[C4, E4, G4], &[C4, E4, G4]), 0);
+/// ```
+pub fn voice_leading_distance(from: &[Note], to: &[Note]) -> u32 {
+    let one_way = |from: &[Note], to: &[Note]| -> u32 {
+        from.iter()
+            .filter_map(|a| {
+                to.iter()
+                    .map(|b| u32::from(a.interval_to(*b).semitones()))
+                    .min()
+            })
+            .sum()
+    };
+
+    one_way(from, to) + one_way(to, from)
+}
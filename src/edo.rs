@@ -0,0 +1,447 @@
+use crate::error::Error;
+use crate::note::{parse_octave, Note};
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt::Write;
+use core::ops::Sub;
+use core::str::FromStr;
+use libm::{log2f, powf, roundf};
+
+/// The natural tones in the order that they appear in the chain of fifths, starting from F.
+const TONES_BY_FIFTHS: [char; 7] = ['F', 'C', 'G', 'D', 'A', 'E', 'B'];
+
+/// A major second, two fifths up and an octave down (C-G-D).
+const MAJOR_SECOND: (i32, i32) = (2, 1);
+/// A minor third, three fifths down and two octaves up (C-F-Bb-Eb).
+const MINOR_THIRD: (i32, i32) = (-3, -2);
+/// A major third, four fifths up and two octaves down (C-G-D-A-E).
+const MAJOR_THIRD: (i32, i32) = (4, 2);
+/// A perfect fourth, a fifth down and an octave up (C-F).
+const PERFECT_FOURTH: (i32, i32) = (-1, -1);
+/// A diminished fifth, six fifths down and four octaves up (C-F-Bb-Eb-Ab-Db-Gb).
+const DIMINISHED_FIFTH: (i32, i32) = (-6, -4);
+/// A perfect fifth.
+const PERFECT_FIFTH: (i32, i32) = (1, 0);
+/// An augmented fifth, eight fifths up and four octaves down (C-G-D-A-E-B-F#-C#-G#).
+const AUGMENTED_FIFTH: (i32, i32) = (8, 4);
+/// A major sixth, three fifths up and an octave down (C-G-D-A).
+const MAJOR_SIXTH: (i32, i32) = (3, 1);
+/// A diminished seventh, nine fifths down and six octaves up (C-F-Bb-Eb-Ab-Db-Gb-Cb-Fb-Bbb).
+const DIMINISHED_SEVENTH: (i32, i32) = (-9, -6);
+/// A minor seventh, two fifths down and two octaves up (C-F-Bb).
+const MINOR_SEVENTH: (i32, i32) = (-2, -2);
+/// A major seventh, five fifths up and two octaves down (C-G-D-A-E-B).
+const MAJOR_SEVENTH: (i32, i32) = (5, 2);
+
+/// The chord qualities that can be recognized in any EDO, with the intervals above the root that
+/// make them. Intervals are given as how many fifths up (or down, if negative) and octaves down
+/// (or up, if negative) they span, so that their size can be worked out for any EDO.
+///
+/// In EDOs other than 12, intervals that sound the same in 12-EDO (e.g. a major third and a
+/// diminished fourth) are told apart, and so are the chords made out of them.
+const EDO_QUALITIES: [(&str, &[(i32, i32)]); 13] = [
+    ("", &[MAJOR_THIRD, PERFECT_FIFTH]),
+    ("m", &[MINOR_THIRD, PERFECT_FIFTH]),
+    ("dim", &[MINOR_THIRD, DIMINISHED_FIFTH]),
+    ("aug", &[MAJOR_THIRD, AUGMENTED_FIFTH]),
+    ("sus2", &[MAJOR_SECOND, PERFECT_FIFTH]),
+    ("sus4", &[PERFECT_FOURTH, PERFECT_FIFTH]),
+    ("7", &[MAJOR_THIRD, PERFECT_FIFTH, MINOR_SEVENTH]),
+    ("maj7", &[MAJOR_THIRD, PERFECT_FIFTH, MAJOR_SEVENTH]),
+    ("m7", &[MINOR_THIRD, PERFECT_FIFTH, MINOR_SEVENTH]),
+    ("m7b5", &[MINOR_THIRD, DIMINISHED_FIFTH, MINOR_SEVENTH]),
+    ("dim7", &[MINOR_THIRD, DIMINISHED_FIFTH, DIMINISHED_SEVENTH]),
+    ("6", &[MAJOR_THIRD, PERFECT_FIFTH, MAJOR_SIXTH]),
+    ("m6", &[MINOR_THIRD, PERFECT_FIFTH, MAJOR_SIXTH]),
+];
+
+/// A pitch in an equal division of the octave (EDO) into `STEPS` steps, e.g. 19-EDO or 31-EDO,
+/// counted in steps above C-1 (the lowest MIDI note).
+///
+/// Pitches are named after the chain of fifths, using the fifth of the EDO that sounds the
+/// closest to a pure one, so notes that sound the same in 12-EDO may not do so in others (e.g.
+/// C# and Db are different pitches in 19-EDO).
+///
+/// # Examples
+/// ```rust
+/// use core::convert::TryFrom;
+/// use whatthechord::prelude::*;
+///
+/// let c_sharp = "C#4".parse::<Edo19>().unwrap();
+/// let d_flat = "Db4".parse::<Edo19>().unwrap();
+/// assert_eq!(c_sharp.steps(), 96);
+/// assert_eq!(d_flat.steps(), 97);
+/// assert_eq!(d_flat.name(), "Db4");
+///
+/// // 12-EDO is just the chromatic scale
+/// assert_eq!(EdoPitch::<12>::try_from(Note::A4).unwrap().steps(), 69);
+/// assert_eq!("Gb4".parse::<EdoPitch<12>>().unwrap().name(), "F#4");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EdoPitch<const STEPS: u16> {
+    steps: u16,
+}
+
+/// A pitch in 19-tone equal temperament.
+pub type Edo19 = EdoPitch<19>;
+/// A pitch in 31-tone equal temperament.
+pub type Edo31 = EdoPitch<31>;
+/// A pitch in 53-tone equal temperament.
+pub type Edo53 = EdoPitch<53>;
+
+/// Convenient methods for working with pitches in any EDO.
+impl<const STEPS: u16> EdoPitch<STEPS> {
+    /// Build a pitch out of how many steps above C-1 it is.
+    ///
+    /// EDOs need at least one step per octave, so using `EdoPitch<0>` fails to compile.
+    ///
+    /// ```compile_fail
+    /// use whatthechord::prelude::*;
+    ///
+    /// EdoPitch::<0>::new(5).name();
+    /// ```
+    pub fn new(steps: u16) -> Self {
+        const { assert!(STEPS > 0, "EDOs need at least one step per octave") };
+
+        Self { steps }
+    }
+
+    /// Get how many steps above C-1 a pitch is.
+    pub fn steps(self) -> u16 {
+        self.steps
+    }
+
+    /// Get the position of a pitch in its octave, in steps above C.
+    pub fn pitch_class(self) -> u16 {
+        self.steps % STEPS
+    }
+
+    /// Get how many steps make the fifth of the EDO, i.e. the one that sounds the closest to a
+    /// pure fifth.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(EdoPitch::<12>::fifth(), 7);
+    /// assert_eq!(Edo19::fifth(), 11);
+    /// assert_eq!(Edo31::fifth(), 18);
+    /// assert_eq!(Edo53::fifth(), 31);
+    /// ```
+    pub fn fifth() -> u16 {
+        roundf(f32::from(STEPS) * log2f(1.5f32)) as u16
+    }
+
+    /// Get how many steps an interval spans in the EDO, out of how many fifths up and octaves
+    /// down it spans.
+    fn interval(fifths: i32, octaves: i32) -> i32 {
+        fifths * i32::from(Self::fifth()) - octaves * i32::from(STEPS)
+    }
+
+    /// Get how many steps apart the pitches in the chain of fifths are, at the least. This is 1
+    /// unless the fifth shares a factor with the number of steps (e.g. 2 in 24-EDO), in which case
+    /// the chain never reaches the pitches in between.
+    fn chain_spacing() -> u16 {
+        let (mut a, mut b) = (STEPS, Self::fifth());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+
+        a
+    }
+
+    /// Get the frequency in Hertz of a pitch, with C-1 tuned just like in 12-EDO.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// let c4 = "C4".parse::<Edo31>().unwrap();
+    /// assert_eq!(c4.frequency(), Note::C4.frequency());
+    ///
+    /// let e4 = "E4".parse::<Edo31>().unwrap();
+    /// assert_eq!(e4.frequency(), 327.17993);
+    /// ```
+    pub fn frequency(self) -> f32 {
+        Note::CMinus1.frequency() * powf(2f32, f32::from(self.steps) / f32::from(STEPS))
+    }
+
+    /// Transpose a pitch by some steps, up or down.
+    pub fn transposed(self, steps: i32) -> Result<Self, Error> {
+        u16::try_from(i32::from(self.steps) + steps)
+            .map(Self::new)
+            .map_err(|_| Error::OutOfMIDIRange)
+    }
+
+    /// Get the name of a pitch, with as few accidentals as possible.
+    ///
+    /// Pitches that the chain of fifths never reaches (e.g. the quarter tones of 24-EDO) are named
+    /// after the closest pitch that it does reach, with a half-sharp ("+") or a half-flat ("d")
+    /// for every step up or down from it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!("Bbb4".parse::<Edo31>().unwrap().name(), "Bbb4");
+    ///
+    /// // 24-EDO is made of quarter tones, half of which are off the chain of fifths
+    /// let names = (120..125).map(|steps| EdoPitch::<24>::new(steps).name()).collect::<Vec<_>>();
+    /// assert_eq!(names, ["C4", "C+4", "Db4", "Db+4", "D4"]);
+    /// assert_eq!("Dd4".parse::<EdoPitch<24>>().unwrap().name(), "Db+4");
+    ///
+    /// // In 72-EDO, every pitch is within three steps of the chain
+    /// assert_eq!(EdoPitch::<72>::new(365).name(), "Dbd4");
+    /// assert_eq!(EdoPitch::<72>::new(363).name(), "C+++4");
+    /// ```
+    pub fn name(self) -> String {
+        let mut name = String::new();
+        // Writing into a `String` never fails
+        self.write_name(&mut name).unwrap();
+
+        name
+    }
+
+    /// Write the name of a pitch into any `core::fmt::Write` implementor.
+    fn write_name<W: Write>(self, f: &mut W) -> core::fmt::Result {
+        let span = i32::from(STEPS);
+        let spacing = i32::from(Self::chain_spacing());
+
+        // Pitches off the chain of fifths are named after the closest one on it
+        let offset = i32::from(self.pitch_class()) % spacing;
+        let (offset, mark) = if offset * 2 <= spacing {
+            (offset, '+')
+        } else {
+            (offset - spacing, 'd')
+        };
+        let steps = i32::from(self.steps) - offset;
+        let pitch_class = steps.rem_euclid(span);
+
+        // Walk the chain of fifths from C both ways until the pitch class shows up, which takes
+        // no more than half the steps either way
+        let fifths = (0..=span / 2)
+            .flat_map(|distance| [distance, -distance])
+            .find(|fifths| Self::interval(*fifths, 0).rem_euclid(span) == pitch_class)
+            .ok_or(core::fmt::Error)?;
+
+        // Every seven fifths add a sharp (or a flat if going down)
+        let position = fifths + 1;
+        let tone = TONES_BY_FIFTHS[position.rem_euclid(7) as usize];
+        let accidentals = position.div_euclid(7);
+        f.write_char(tone)?;
+        for _ in 0..accidentals.abs() {
+            f.write_char(if accidentals > 0 { '#' } else { 'b' })?;
+        }
+        for _ in 0..offset.abs() {
+            f.write_char(mark)?;
+        }
+
+        // The octave goes after the tone, so "B#" or "Cb" may belong to the neighboring one
+        let unwrapped = Self::interval(position - 1 - accidentals * 7, 0).rem_euclid(span)
+            + accidentals * Self::interval(7, 4);
+        let octave = (steps - unwrapped).div_euclid(span) - 1;
+
+        write!(f, "{}", octave)
+    }
+}
+
+/// Notes of the chromatic scale are pitches in any EDO, as long as the EDO has a fifth to name
+/// them by, and the EDO is not so fine that the highest notes lie beyond the steps that can be
+/// counted.
+///
+/// # Examples
+/// ```rust
+/// use core::convert::TryFrom;
+/// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+///
+/// assert_eq!(Edo31::try_from(A4).unwrap().name(), "A4");
+/// assert_eq!(EdoPitch::<7000>::try_from(G9), Err(OutOfMIDIRange));
+/// ```
+impl<const STEPS: u16> TryFrom<Note> for EdoPitch<STEPS> {
+    type Error = Error;
+
+    fn try_from(note: Note) -> Result<Self, Self::Error> {
+        let fifths = (i32::from(note.pitch_class()) * 7).rem_euclid(12);
+        let fifths = if fifths > 5 { fifths - 12 } else { fifths };
+        let pitch_class = Self::interval(fifths, 0).rem_euclid(i32::from(STEPS));
+        let steps = (i32::from(note.octave()) + 1) * i32::from(STEPS) + pitch_class;
+
+        u16::try_from(steps)
+            .map(Self::new)
+            .map_err(|_| Error::OutOfMIDIRange)
+    }
+}
+
+/// Support for subtracting pitches, which gives the signed distance in steps from the second one
+/// to the first one.
+impl<const STEPS: u16> Sub<EdoPitch<STEPS>> for EdoPitch<STEPS> {
+    type Output = i32;
+
+    fn sub(self, other: EdoPitch<STEPS>) -> Self::Output {
+        i32::from(self.steps) - i32::from(other.steps)
+    }
+}
+
+/// Support for parsing pitches written in scientific pitch notation, with any number of sharps
+/// ("#") or flats ("b"), followed by any number of half-sharps ("+") or half-flats ("d") that move
+/// them a step up or down.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{error::Error::*, prelude::*};
+///
+/// // In 31-EDO, E# and Fb fall in between E and F
+/// let e = "E4".parse::<Edo31>().unwrap();
+/// let e_sharp = "E#4".parse::<Edo31>().unwrap();
+/// let f_flat = "Fb4".parse::<Edo31>().unwrap();
+/// let f = "F4".parse::<Edo31>().unwrap();
+/// assert_eq!((e_sharp - e, f_flat - e, f - e), (2, 1, 3));
+///
+/// assert_eq!("H4".parse::<Edo31>(), Err(InvalidNoteName));
+/// assert_eq!("C99999999".parse::<Edo31>(), Err(OutOfMIDIRange));
+/// assert_eq!("C3000".parse::<Edo31>(), Err(OutOfMIDIRange));
+/// ```
+impl<const STEPS: u16> FromStr for EdoPitch<STEPS> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let tone = s.chars().next().ok_or(Error::InvalidNoteName)?;
+        let position = TONES_BY_FIFTHS
+            .iter()
+            .position(|name| *name == tone.to_ascii_uppercase())
+            .ok_or(Error::InvalidNoteName)? as i32;
+
+        let rest = &s[tone.len_utf8()..];
+        let marks = rest.trim_start_matches(['#', 'b']);
+        let alteration = rest[..rest.len() - marks.len()]
+            .chars()
+            .map(|accidental| if accidental == '#' { 1 } else { -1 })
+            .sum::<i32>();
+        let octave = marks.trim_start_matches(['+', 'd']);
+        let offset = marks[..marks.len() - octave.len()]
+            .chars()
+            .map(|mark| if mark == '+' { 1 } else { -1 })
+            .sum::<i32>();
+        let octave = parse_octave(octave)?;
+
+        let natural = Self::interval(position - 1, 0).rem_euclid(i32::from(STEPS));
+        let steps = (i32::from(octave) + 1)
+            .checked_mul(i32::from(STEPS))
+            .and_then(|steps| steps.checked_add(natural))
+            .and_then(|steps| steps.checked_add(alteration.checked_mul(Self::interval(7, 4))?))
+            .and_then(|steps| steps.checked_add(offset))
+            .ok_or(Error::OutOfMIDIRange)?;
+
+        u16::try_from(steps)
+            .map(Self::new)
+            .map_err(|_| Error::OutOfMIDIRange)
+    }
+}
+
+/// A chord made of pitches in an EDO, as recognized from the intervals between them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EdoChord<const STEPS: u16> {
+    root: EdoPitch<STEPS>,
+    bass: EdoPitch<STEPS>,
+    quality: &'static str,
+}
+
+/// Convenient methods for working with chords in any EDO.
+impl<const STEPS: u16> EdoChord<STEPS> {
+    /// Recognize the chord that some pitches make, regardless of their octaves and order, if any.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// let pitches = |names: &[&str]| {
+    ///     names.iter().map(|name| name.parse::<Edo31>().unwrap()).collect::<Vec<_>>()
+    /// };
+    ///
+    /// let chord = EdoChord::from_pitches(&pitches(&["E3", "C4", "G4"])).unwrap();
+    /// assert_eq!(chord.name(), "C/E");
+    ///
+    /// let chord = EdoChord::from_pitches(&pitches(&["C4", "Eb4", "Gb4", "Bbb4"])).unwrap();
+    /// assert_eq!(chord.name(), "Cdim7");
+    ///
+    /// // Unlike in 12-EDO, a diminished fourth is not a major third
+    /// assert_eq!(EdoChord::from_pitches(&pitches(&["C4", "Fb4", "G4"])), None);
+    ///
+    /// // In 19-EDO, too
+    /// let pitches = ["D4", "F#4", "A4", "C5"].map(|name| name.parse::<Edo19>().unwrap());
+    /// assert_eq!(EdoChord::from_pitches(&pitches).unwrap().name(), "D7");
+    /// ```
+    pub fn from_pitches(pitches: &[EdoPitch<STEPS>]) -> Option<Self> {
+        let bass = *pitches.iter().min()?;
+        let pitch_classes = pitches
+            .iter()
+            .map(|pitch| pitch.pitch_class())
+            .collect::<BTreeSet<u16>>();
+
+        for root in pitches {
+            for &(quality, intervals) in EDO_QUALITIES.iter() {
+                let candidate = intervals
+                    .iter()
+                    .map(|(fifths, octaves)| {
+                        let interval = EdoPitch::<STEPS>::interval(*fifths, *octaves);
+                        (i32::from(root.pitch_class()) + interval).rem_euclid(i32::from(STEPS))
+                            as u16
+                    })
+                    .chain(core::iter::once(root.pitch_class()))
+                    .collect::<BTreeSet<u16>>();
+
+                if candidate == pitch_classes {
+                    return Some(Self {
+                        root: *root,
+                        bass,
+                        quality,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Get the root of a chord.
+    pub fn root(&self) -> EdoPitch<STEPS> {
+        self.root
+    }
+
+    /// Get the bass of a chord, that is, its lowest pitch.
+    pub fn bass(&self) -> EdoPitch<STEPS> {
+        self.bass
+    }
+
+    /// Get the quality of a chord, written as in chord symbols (e.g. "m7").
+    pub fn quality(&self) -> &'static str {
+        self.quality
+    }
+
+    /// Get the name of a chord, with the bass after a slash if it is not the root.
+    pub fn name(&self) -> String {
+        let mut name = String::new();
+        // Writing into a `String` never fails
+        write_pitch_class(&mut name, self.root).unwrap();
+        name.push_str(self.quality);
+        if self.bass.pitch_class() != self.root.pitch_class() {
+            name.push('/');
+            write_pitch_class(&mut name, self.bass).unwrap();
+        }
+
+        name
+    }
+}
+
+/// Write the name of a pitch without its octave into any `core::fmt::Write` implementor.
+fn write_pitch_class<W: Write, const STEPS: u16>(
+    f: &mut W,
+    pitch: EdoPitch<STEPS>,
+) -> core::fmt::Result {
+    let mut name = String::new();
+    pitch.write_name(&mut name)?;
+
+    f.write_str(name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '-'))
+}
@@ -1,5 +1,70 @@
+use core::fmt::{Display, Formatter};
+
+/// Everything that can go wrong when using this library.
 #[derive(Debug, Eq, PartialEq)]
 pub enum Error {
+    /// A note would fall outside of the 0-127 range of MIDI key numbers.
     OutOfMIDIRange,
+    /// A note does not exist in the keyboard of an instrument.
     OutOfInstrumentRange,
+    /// There are more notes than fit in a fixed-size chord.
+    TooManyNotes,
+    /// A chord symbol could not be understood.
+    InvalidChordSymbol,
+    /// A note name could not be understood.
+    InvalidNoteName,
+    /// An interval name could not be understood.
+    InvalidInterval,
+    /// A chord cannot be built because its type does not tell which notes it has.
+    UnbuildableChord,
+    /// A key signature has more accidentals than possible.
+    InvalidKeySignature,
+    /// A roman numeral could not be understood.
+    InvalidRomanNumeral,
+    /// Some bytes are not a valid MIDI message, or not one of the supported kinds.
+    InvalidMidiMessage,
+    /// Reading from or writing into some I/O device failed.
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+/// Text representations of errors.
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::OutOfMIDIRange => f.write_str("note is out of the MIDI range"),
+            Error::OutOfInstrumentRange => f.write_str("note is out of the instrument range"),
+            Error::TooManyNotes => f.write_str("too many notes for the chord capacity"),
+            Error::InvalidChordSymbol => f.write_str("invalid chord symbol"),
+            Error::InvalidNoteName => f.write_str("invalid note name"),
+            Error::InvalidInterval => f.write_str("invalid interval name"),
+            Error::UnbuildableChord => f.write_str("chord type does not define its notes"),
+            Error::InvalidKeySignature => f.write_str("too many accidentals in key signature"),
+            Error::InvalidRomanNumeral => f.write_str("invalid roman numeral"),
+            Error::InvalidMidiMessage => f.write_str("invalid MIDI message"),
+            #[cfg(feature = "std")]
+            Error::Io(kind) => write!(f, "I/O error: {}", kind),
+        }
+    }
+}
+
+/// Integration with the standard library error handling.
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Support for bubbling up I/O errors with the `?` operator.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::error::Error;
+///
+/// let io_error = std::io::Error::from(std::io::ErrorKind::UnexpectedEof);
+/// let error = Error::from(io_error);
+/// assert_eq!(error, Error::Io(std::io::ErrorKind::UnexpectedEof));
+/// ```
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error.kind())
+    }
 }
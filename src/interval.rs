@@ -0,0 +1,222 @@
+use crate::error::Error;
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// Semitones spanned by each of the seven simple interval degrees (unison, 2nd, ..., 7th) in their
+/// perfect or major form.
+const DEGREE_SEMITONES: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// Quality and degree of each simple interval, indexed by its size in semitones. The tritone is
+/// left out, as it is named on its own.
+const SIMPLE_NAMES: [(char, u8); 12] = [
+    ('P', 1),
+    ('m', 2),
+    ('M', 2),
+    ('m', 3),
+    ('M', 3),
+    ('P', 4),
+    ('A', 4),
+    ('P', 5),
+    ('m', 6),
+    ('M', 6),
+    ('m', 7),
+    ('M', 7),
+];
+
+/// The distance between two notes, measured in semitones.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Interval {
+    semitones: u8,
+}
+
+/// Convenient methods for working with intervals.
+impl Interval {
+    /// Build an interval spanning a number of semitones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// let fifth = Interval::new(7);
+    /// assert_eq!(fifth.semitones(), 7);
+    /// ```
+    pub fn new(semitones: u8) -> Self {
+        Self { semitones }
+    }
+
+    /// Get the number of semitones spanned by an interval.
+    pub fn semitones(&self) -> u8 {
+        self.semitones
+    }
+
+    /// Tells whether an interval spans more than an octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(Interval::new(7).is_compound(), false);
+    /// assert_eq!(Interval::new(12).is_compound(), false);
+    /// assert_eq!(Interval::new(14).is_compound(), true);
+    /// ```
+    pub fn is_compound(&self) -> bool {
+        self.semitones > 12
+    }
+
+    /// Get the number of whole octaves contained in an interval.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(Interval::new(7).octaves(), 0);
+    /// assert_eq!(Interval::new(19).octaves(), 1);
+    /// ```
+    pub fn octaves(&self) -> u8 {
+        self.semitones / 12
+    }
+
+    /// Get the simple interval (less than an octave) that remains after taking all the whole
+    /// octaves out of an interval.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// // A major ninth is a major second plus an octave
+    /// assert_eq!(Interval::new(14).simple(), Interval::new(2));
+    ///
+    /// // Simple intervals are left untouched
+    /// assert_eq!(Interval::new(4).simple(), Interval::new(4));
+    /// ```
+    pub fn simple(&self) -> Interval {
+        Interval::new(self.semitones % 12)
+    }
+
+    /// Split an interval into its number of whole octaves and its simple interval.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// // A perfect twelfth is a perfect fifth plus an octave
+    /// assert_eq!(Interval::new(19).split(), (1, Interval::new(7)));
+    /// ```
+    pub fn split(&self) -> (u8, Interval) {
+        (self.octaves(), self.simple())
+    }
+
+    /// Get the inversion of an interval, that is, the interval that adds up to an octave when
+    /// stacked on top of its simple interval. Unisons and octaves are the inversion of each other.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// // A major third inverts into a minor sixth
+    /// assert_eq!(Interval::new(4).inverted(), Interval::new(8));
+    ///
+    /// // Compound intervals are inverted as their simple interval
+    /// assert_eq!(Interval::new(14).inverted(), Interval::new(10));
+    ///
+    /// // The tritone is its own inversion
+    /// assert_eq!(Interval::new(6).inverted(), Interval::new(6));
+    ///
+    /// assert_eq!(Interval::new(0).inverted(), Interval::new(12));
+    /// assert_eq!(Interval::new(12).inverted(), Interval::new(0));
+    /// ```
+    pub fn inverted(&self) -> Interval {
+        match self.semitones {
+            0 => Interval::new(12),
+            semitones => Interval::new((12 - semitones % 12) % 12),
+        }
+    }
+}
+
+/// Text representations of intervals, using the usual shorthand made of a quality ("P" for perfect,
+/// "M" for major, "m" for minor, "A" for augmented) and a degree. Simple tritones are written as
+/// "TT".
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// assert_eq!(format!("{}", Interval::new(3)), "m3");
+/// assert_eq!(format!("{}", Interval::new(7)), "P5");
+/// assert_eq!(format!("{}", Interval::new(6)), "TT");
+/// assert_eq!(format!("{}", Interval::new(12)), "P8");
+/// assert_eq!(format!("{}", Interval::new(14)), "M9");
+/// assert_eq!(format!("{}", Interval::new(18)), "A11");
+/// ```
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.semitones == 6 {
+            return f.write_str("TT");
+        }
+
+        let octaves = self.semitones / 12;
+        let (quality, degree) = SIMPLE_NAMES[usize::from(self.semitones % 12)];
+
+        // Whole octaves are named as perfect octaves rather than as compound unisons
+        match (octaves, degree) {
+            (0, _) | (_, 2..=7) => write!(f, "{}{}", quality, degree + 7 * octaves),
+            _ => write!(f, "P{}", 1 + 7 * octaves),
+        }
+    }
+}
+
+/// Support for parsing intervals from their shorthand names through `str::parse`, as written by
+/// their `Display` implementation. Diminished intervals ("d5") are accepted too.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{error::Error, prelude::*};
+///
+/// assert_eq!("m3".parse(), Ok(Interval::new(3)));
+/// assert_eq!("TT".parse(), Ok(Interval::new(6)));
+/// assert_eq!("d5".parse(), Ok(Interval::new(6)));
+/// assert_eq!("M9".parse(), Ok(Interval::new(14)));
+/// assert_eq!("P15".parse(), Ok(Interval::new(24)));
+///
+/// // Perfect intervals cannot be major or minor, and the other way around
+/// assert_eq!("M5".parse::<Interval>(), Err(Error::InvalidInterval));
+/// assert_eq!("P3".parse::<Interval>(), Err(Error::InvalidInterval));
+/// ```
+impl FromStr for Interval {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let name = name.trim();
+        if name == "TT" {
+            return Ok(Interval::new(6));
+        }
+
+        let mut chars = name.chars();
+        let quality = chars.next().ok_or(Error::InvalidInterval)?;
+        let degree = chars
+            .as_str()
+            .parse::<u8>()
+            .ok()
+            .filter(|degree| *degree > 0)
+            .ok_or(Error::InvalidInterval)?;
+
+        let octaves = (degree - 1) / 7;
+        let simple_degree = usize::from((degree - 1) % 7);
+        let is_perfect = matches!(simple_degree, 0 | 3 | 4);
+        let base = i16::from(DEGREE_SEMITONES[simple_degree]) + 12 * i16::from(octaves);
+
+        let semitones = match (quality, is_perfect) {
+            ('P', true) | ('M', false) => base,
+            ('m', false) => base - 1,
+            ('A', _) => base + 1,
+            ('d', true) => base - 1,
+            ('d', false) => base - 2,
+            _ => return Err(Error::InvalidInterval),
+        };
+
+        u8::try_from(semitones)
+            .map(Interval::new)
+            .map_err(|_| Error::InvalidInterval)
+    }
+}
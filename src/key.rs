@@ -0,0 +1,619 @@
+use crate::error::Error;
+use crate::prelude::*;
+use alloc::vec::Vec;
+use libm::sqrtf;
+
+/// Pitch classes of the sharps in key signatures, in the order in which they are added.
+const SHARPS: [u8; 7] = [6, 1, 8, 3, 10, 5, 0];
+/// Pitch classes of the flats in key signatures, in the order in which they are added.
+const FLATS: [u8; 7] = [10, 3, 8, 1, 6, 11, 4];
+
+/// Semitone offsets from the tonic for the notes in a major key.
+const MAJOR_OFFSETS: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+/// Semitone offsets from the tonic for the notes in a (natural) minor key.
+const MINOR_OFFSETS: [u8; 7] = [0, 2, 3, 5, 7, 8, 10];
+
+/// How much better another key needs to fit the music being tracked by a `KeyTracker` than the
+/// current key, for the tracker to move into it.
+const KEY_CHANGE_MARGIN: f32 = 0.1;
+
+/// How well each pitch class fits in a major key, from the tonic upwards, as rated by listeners in
+/// the probe tone experiments by Krumhansl and Kessler.
+const MAJOR_PROFILE: [f32; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+
+/// How well each pitch class fits in a minor key, from the tonic upwards, as rated by listeners in
+/// the probe tone experiments by Krumhansl and Kessler.
+const MINOR_PROFILE: [f32; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+/// A tonal center, made of a tonic note and a mode (major or minor).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Key {
+    tonic: Note,
+    mode: KeyMode,
+}
+
+/// Convenient methods for working with musical keys.
+impl Key {
+    /// Build a key from its tonic and mode.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let key = Key::new(A3, KeyMode::Minor);
+    /// assert_eq!(key.tonic(), A3);
+    /// assert_eq!(key.mode(), KeyMode::Minor);
+    /// ```
+    pub fn new(tonic: Note, mode: KeyMode) -> Self {
+        Self { tonic, mode }
+    }
+
+    /// Build a major key from its tonic.
+    pub fn major(tonic: Note) -> Self {
+        Self::new(tonic, KeyMode::Major)
+    }
+
+    /// Build a (natural) minor key from its tonic.
+    pub fn minor(tonic: Note) -> Self {
+        Self::new(tonic, KeyMode::Minor)
+    }
+
+    /// Build the major key that has some number of flats or sharps in its signature. There can be
+    /// no more than seven of them.
+    ///
+    /// The tonic of the key is placed in the 4th octave. As notes are named after their pitch
+    /// alone, keys with seven accidentals get the same tonic as their enharmonic keys (e.g. C#
+    /// major and Db major).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// assert_eq!(Key::from_accidentals(3, FlatOrSharp::Sharp), Ok(Key::major(A4)));
+    /// assert_eq!(Key::from_accidentals(3, FlatOrSharp::Flat), Ok(Key::major(DSharp4)));
+    /// assert_eq!(Key::from_accidentals(0, FlatOrSharp::Flat), Ok(Key::major(C4)));
+    /// assert_eq!(Key::from_accidentals(8, FlatOrSharp::Flat), Err(InvalidKeySignature));
+    /// ```
+    pub fn from_accidentals(count: u8, accidental: FlatOrSharp) -> Result<Self, Error> {
+        if count > 7 {
+            return Err(Error::InvalidKeySignature);
+        }
+
+        // Every sharp is a fifth up the circle of fifths, and every flat is a fifth down
+        let pitch_class = match accidental {
+            FlatOrSharp::Sharp => count * 7 % 12,
+            FlatOrSharp::Flat => count * 5 % 12,
+        };
+
+        Ok(Self::major(Note::from(60 + pitch_class)))
+    }
+
+    /// Estimate which key some music is in, out of how much each pitch class (0-11, where 0 is C)
+    /// is heard in it, e.g. how many times or for how long.
+    ///
+    /// This follows the Krumhansl-Schmuckler algorithm: the histogram is correlated with the
+    /// profile of each of the 24 major and minor keys, and the keys are returned together with
+    /// their correlation (from -1 to 1), from the most likely one to the least. Tonics are placed
+    /// in the 4th octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // The notes of a G major scale, with the tonic and dominant heard the most
+    /// let histogram = [1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 3.0, 0.0, 1.0, 0.0, 1.0];
+    /// let keys = Key::estimate(&histogram);
+    /// assert_eq!(keys.len(), 24);
+    /// assert_eq!(keys[0].0, Key::major(G4));
+    /// assert!(keys[0].1 > 0.8);
+    /// ```
+    pub fn estimate(histogram: &[f32; 12]) -> Vec<(Self, f32)> {
+        let mut keys = Vec::with_capacity(24);
+        for tonic in 0..12u8 {
+            for (mode, profile) in [
+                (KeyMode::Major, &MAJOR_PROFILE),
+                (KeyMode::Minor, &MINOR_PROFILE),
+            ] {
+                // The profile of the key, starting from C rather than from its tonic
+                let profile = core::array::from_fn(|pitch_class| {
+                    profile[(pitch_class + 12 - usize::from(tonic)) % 12]
+                });
+                let key = Self::new(Note::from(60 + tonic), mode);
+                keys.push((key, correlation(histogram, &profile)));
+            }
+        }
+        keys.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(core::cmp::Ordering::Equal));
+
+        keys
+    }
+
+    /// Estimate which key a sequence of chords is in, counting every note in them once, as done by
+    /// `Key::estimate`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chords = [
+    ///     Chord::from_notes(&[A3, C4, E4]),
+    ///     Chord::from_notes(&[D3, F3, A3]),
+    ///     Chord::from_notes(&[E3, GSharp3, B3, D4]),
+    ///     Chord::from_notes(&[A3, C4, E4]),
+    /// ];
+    /// assert_eq!(Key::estimate_from_chords(&chords)[0].0, Key::minor(A4));
+    /// ```
+    pub fn estimate_from_chords(chords: &[Chord]) -> Vec<(Self, f32)> {
+        let mut histogram = [0f32; 12];
+        for note in chords.iter().flat_map(|chord| chord.notes()) {
+            histogram[usize::from(note.pitch_class())] += 1.0;
+        }
+
+        Self::estimate(&histogram)
+    }
+
+    /// Get the tonic of a key.
+    pub fn tonic(&self) -> Note {
+        self.tonic
+    }
+
+    /// Get the mode of a key.
+    pub fn mode(&self) -> KeyMode {
+        self.mode
+    }
+
+    /// Get the parallel key of a key, i.e. the one that has the same tonic but the opposite mode.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Key::major(C4).parallel(), Key::minor(C4));
+    /// assert_eq!(Key::minor(A3).parallel(), Key::major(A3));
+    /// ```
+    pub fn parallel(&self) -> Self {
+        let mode = match self.mode {
+            KeyMode::Major => KeyMode::Minor,
+            KeyMode::Minor => KeyMode::Major,
+        };
+
+        Self::new(self.tonic, mode)
+    }
+
+    /// Get the pitch classes (0-11, where 0 is C) of the seven notes in a key, starting from the
+    /// tonic.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // C major has no accidentals
+    /// let c_major = Key::major(C4).pitch_classes();
+    /// assert_eq!(c_major, [0, 2, 4, 5, 7, 9, 11]);
+    ///
+    /// // E minor has F#
+    /// let e_minor = Key::minor(E4).pitch_classes();
+    /// assert_eq!(e_minor, [4, 6, 7, 9, 11, 0, 2]);
+    /// ```
+    pub fn pitch_classes(&self) -> [u8; 7] {
+        let offsets = match self.mode {
+            KeyMode::Major => MAJOR_OFFSETS,
+            KeyMode::Minor => MINOR_OFFSETS,
+        };
+        let tonic = self.tonic.pitch_class();
+
+        let mut pitch_classes = [0u8; 7];
+        for (pitch_class, offset) in pitch_classes.iter_mut().zip(offsets.iter()) {
+            *pitch_class = (tonic + offset) % 12;
+        }
+
+        pitch_classes
+    }
+
+    /// Get the key signature of a key, as the number of accidentals in it and whether those are
+    /// flats or sharps. Keys with no accidentals are deemed sharp, and keys with six accidentals
+    /// are spelled with sharps (e.g. F# major rather than Gb major).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Key::major(C4).signature(), (0, FlatOrSharp::Sharp));
+    /// assert_eq!(Key::major(E4).signature(), (4, FlatOrSharp::Sharp));
+    /// assert_eq!(Key::major(ASharp4).signature(), (2, FlatOrSharp::Flat));
+    ///
+    /// // Minor keys share their signatures with their relative major keys
+    /// assert_eq!(Key::minor(D4).signature(), (1, FlatOrSharp::Flat));
+    /// assert_eq!(Key::minor(CSharp4).signature(), (4, FlatOrSharp::Sharp));
+    /// ```
+    pub fn signature(&self) -> (u8, FlatOrSharp) {
+        let relative_major = match self.mode {
+            KeyMode::Major => self.tonic.pitch_class(),
+            KeyMode::Minor => (self.tonic.pitch_class() + 3) % 12,
+        };
+
+        // Position of the relative major key in the circle of fifths, going up from C
+        match relative_major * 7 % 12 {
+            fifths @ 0..=6 => (fifths, FlatOrSharp::Sharp),
+            fifths => (12 - fifths, FlatOrSharp::Flat),
+        }
+    }
+
+    /// Get the pitch classes (0-11, where 0 is C) that are altered in the signature of a key, in
+    /// the order in which they are written in the staff.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // D major has F# and C#
+    /// assert_eq!(Key::major(D4).accidentals(), vec![6, 1]);
+    ///
+    /// // C minor has Bb, Eb and Ab
+    /// assert_eq!(Key::minor(C4).accidentals(), vec![10, 3, 8]);
+    ///
+    /// assert!(Key::minor(A4).accidentals().is_empty());
+    /// ```
+    pub fn accidentals(&self) -> Vec<u8> {
+        let (count, accidental) = self.signature();
+        let accidentals = match accidental {
+            FlatOrSharp::Sharp => &SHARPS,
+            FlatOrSharp::Flat => &FLATS,
+        };
+
+        accidentals[..usize::from(count)].to_vec()
+    }
+
+    /// Get the accidental that notes in a key are preferably spelled with, as told by its
+    /// signature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Key::major(F4).accidental(), FlatOrSharp::Flat);
+    /// assert_eq!(Key::minor(B4).accidental(), FlatOrSharp::Sharp);
+    /// ```
+    pub fn accidental(&self) -> FlatOrSharp {
+        self.signature().1
+    }
+
+    /// Tells whether a note belongs to a key, regardless of its octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let g_major = Key::major(G3);
+    /// assert_eq!(g_major.contains(FSharp1), true);
+    /// assert_eq!(g_major.contains(F5), false);
+    /// ```
+    pub fn contains(&self, note: Note) -> bool {
+        self.pitch_classes().contains(&note.pitch_class())
+    }
+
+    /// Suggest different ways to modulate from this key into another one, each of them as a short
+    /// progression starting on the tonic of this key and resolving into the tonic of the other.
+    ///
+    /// Three kinds of modulation are suggested:
+    /// - Pivot chord: going through any chord that is diatonic to both keys.
+    /// - Common tone: jumping straight between both tonics, if they share any note.
+    /// - Dominant chain: approaching the new tonic through its dominant and the dominant of its
+    ///   dominant.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Key::major(C4);
+    /// let g_major = Key::major(G3);
+    /// let modulations = c_major.modulations_to(&g_major);
+    ///
+    /// // E minor is the first pivot chord found, as it is iii in C major and vi in G major
+    /// let pivot = &modulations[0];
+    /// assert_eq!(pivot.kind(), ModulationKind::PivotChord);
+    /// assert_eq!(pivot.chords()[1], Chord::from_notes(&[E4, G4, B4]));
+    ///
+    /// // A minor is also a pivot chord, as it is vi in C major and ii in G major
+    /// let pivot = &modulations[1];
+    /// assert_eq!(pivot.chords()[1], Chord::from_notes(&[A4, C5, E5]));
+    ///
+    /// // C major and G major tonic triads share a G
+    /// let common_tone = modulations
+    ///     .iter()
+    ///     .find(|modulation| modulation.kind() == ModulationKind::CommonTone)
+    ///     .unwrap();
+    /// assert_eq!(common_tone.chords().len(), 2);
+    ///
+    /// // There is no modulation from a key into itself
+    /// assert!(c_major.modulations_to(&c_major).is_empty());
+    /// ```
+    pub fn modulations_to(&self, destination: &Key) -> Vec<Modulation> {
+        let mut modulations = Vec::new();
+
+        if self.pitch_classes() == destination.pitch_classes() && self.mode == destination.mode {
+            return modulations;
+        }
+
+        let (source_tonic, destination_tonic) = match (self.triad(0), destination.triad(0)) {
+            (Some(source_tonic), Some(destination_tonic)) => (source_tonic, destination_tonic),
+            _ => return modulations,
+        };
+        let destination_dominant = dominant_seventh(destination.tonic, -5);
+        let destination_secondary_dominant = dominant_seventh(destination.tonic, 2);
+
+        // Pivot chords: any chord other than both tonics that is diatonic in both keys
+        if let Some(destination_dominant) = destination_dominant {
+            for degree in 1..7 {
+                if let Some(pivot) = self.triad(degree) {
+                    let is_destination_tonic = pivot
+                        .iter()
+                        .zip(destination_tonic.iter())
+                        .all(|(a, b)| a.pitch_class() == b.pitch_class());
+                    if !is_destination_tonic && pivot.iter().all(|note| destination.contains(*note))
+                    {
+                        modulations.push(Modulation {
+                            kind: ModulationKind::PivotChord,
+                            chords: vec![
+                                Chord::from_notes(&source_tonic),
+                                Chord::from_notes(&pivot),
+                                Chord::from_notes(&destination_dominant),
+                                Chord::from_notes(&destination_tonic),
+                            ],
+                        });
+                    }
+                }
+            }
+        }
+
+        // Common tone: both tonic triads share at least one pitch class
+        let shares_tone = source_tonic.iter().any(|source_note| {
+            destination_tonic
+                .iter()
+                .any(|destination_note| source_note.pitch_class() == destination_note.pitch_class())
+        });
+        if shares_tone {
+            modulations.push(Modulation {
+                kind: ModulationKind::CommonTone,
+                chords: vec![
+                    Chord::from_notes(&source_tonic),
+                    Chord::from_notes(&destination_tonic),
+                ],
+            });
+        }
+
+        // Dominant chain: V/V -> V -> I in the destination key
+        if let (Some(secondary_dominant), Some(dominant)) =
+            (destination_secondary_dominant, destination_dominant)
+        {
+            modulations.push(Modulation {
+                kind: ModulationKind::DominantChain,
+                chords: vec![
+                    Chord::from_notes(&source_tonic),
+                    Chord::from_notes(&secondary_dominant),
+                    Chord::from_notes(&dominant),
+                    Chord::from_notes(&destination_tonic),
+                ],
+            });
+        }
+
+        modulations
+    }
+
+    /// Build the notes of the triad made of stacked thirds on top of a degree (0-6) of the key.
+    fn triad(&self, degree: usize) -> Option<[Note; 3]> {
+        let pitch_classes = self.pitch_classes();
+        let tonic = self.tonic.pitch_class();
+
+        let mut notes = [self.tonic; 3];
+        for (note, step) in notes
+            .iter_mut()
+            .zip([degree, degree + 2, degree + 4].iter())
+        {
+            let offset = (pitch_classes[step % 7] + 12 - tonic) % 12;
+            let octave = if *step >= 7 { 12 } else { 0 };
+            *note = self.tonic.transposed((offset + octave) as i8).ok()?;
+        }
+
+        Some(notes)
+    }
+}
+
+/// Build the notes of a dominant seventh chord whose root is a number of half tones away from a
+/// given note.
+fn dominant_seventh(note: Note, half_tones: i8) -> Option<[Note; 4]> {
+    let root = note.transposed(half_tones).ok()?;
+
+    Some([
+        root,
+        root.transposed(4).ok()?,
+        root.transposed(7).ok()?,
+        root.transposed(10).ok()?,
+    ])
+}
+
+/// Keeps track of the key of some music as it is being played, e.g. when analysing live MIDI
+/// input, noticing when it modulates.
+///
+/// The tracker keeps a profile of how much each pitch class has been heard, which fades away as
+/// new notes come in, so that the estimated key follows the most recent music.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyTracker {
+    profile: [f32; 12],
+    decay: f32,
+    steps: usize,
+    changes: Vec<(usize, Key)>,
+}
+
+/// Convenient methods for tracking keys.
+impl KeyTracker {
+    /// Build a key tracker, telling how much of the profile is kept on each update (from 0 to 1,
+    /// where lower values make the tracker react faster to modulations).
+    pub fn new(decay: f32) -> Self {
+        Self {
+            profile: [0.0; 12],
+            decay: decay.clamp(0.0, 1.0),
+            steps: 0,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Feed the notes that have just been played into the tracker, and get the new key if it has
+    /// just changed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = KeyTracker::new(0.8);
+    ///
+    /// // I-IV-V-I in C major
+    /// assert_eq!(tracker.update(&[C4, E4, G4]), Some(Key::major(C4)));
+    /// assert_eq!(tracker.update(&[F3, A3, C4]), None);
+    /// assert_eq!(tracker.update(&[G3, B3, D4, F4]), None);
+    /// assert_eq!(tracker.update(&[C4, E4, G4]), None);
+    ///
+    /// // Moving into G major, through its dominant
+    /// assert_eq!(tracker.update(&[D4, FSharp4, A4, C5]), None);
+    /// assert_eq!(tracker.update(&[G3, B3, D4]), Some(Key::major(G4)));
+    /// assert_eq!(tracker.current(), Some(Key::major(G4)));
+    /// ```
+    pub fn update(&mut self, notes: &[Note]) -> Option<Key> {
+        for weight in self.profile.iter_mut() {
+            *weight *= self.decay;
+        }
+        for note in notes {
+            self.profile[usize::from(note.pitch_class())] += 1.0;
+        }
+        self.steps += 1;
+
+        if self.profile.iter().all(|weight| *weight == 0.0) {
+            return None;
+        }
+
+        // Only move into another key if it fits clearly better than the current one
+        let keys = Key::estimate(&self.profile);
+        let (key, correlation) = *keys.first()?;
+        let current = keys
+            .iter()
+            .find(|(key, _)| Some(*key) == self.current())
+            .map(|(_, correlation)| *correlation);
+        if current.is_some_and(|current| correlation - current <= KEY_CHANGE_MARGIN) {
+            return None;
+        }
+        self.changes.push((self.steps - 1, key));
+
+        Some(key)
+    }
+
+    /// Feed a chord that has just been played into the tracker, as done by `KeyTracker::update`.
+    pub fn update_with_chord(&mut self, chord: &Chord) -> Option<Key> {
+        self.update(chord.notes())
+    }
+
+    /// Get the key that the music is most likely in at the moment, if any note was played yet.
+    pub fn current(&self) -> Option<Key> {
+        self.changes.last().map(|(_, key)| *key)
+    }
+
+    /// Get every change of key so far, together with the number of the update (starting from 0)
+    /// that the change happened on.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tracker = KeyTracker::new(0.8);
+    ///
+    /// // Nothing was played at first
+    /// tracker.update(&[]);
+    /// tracker.update(&[C4, E4, G4]);
+    /// tracker.update(&[F3, A3, C4]);
+    /// tracker.update(&[G3, B3, D4, F4]);
+    /// tracker.update(&[C4, E4, G4]);
+    /// tracker.update(&[D4, FSharp4, A4, C5]);
+    /// tracker.update(&[G3, B3, D4]);
+    /// assert_eq!(tracker.changes(), [(1, Key::major(C4)), (6, Key::major(G4))]);
+    /// ```
+    pub fn changes(&self) -> &[(usize, Key)] {
+        &self.changes
+    }
+}
+
+/// Get the Pearson correlation between two series of values, which is deemed to be 0 if any of
+/// them is flat.
+pub(crate) fn correlation(a: &[f32; 12], b: &[f32; 12]) -> f32 {
+    let mean = |values: &[f32; 12]| values.iter().sum::<f32>() / 12.0;
+    let (mean_a, mean_b) = (mean(a), mean(b));
+
+    let (mut covariance, mut variance_a, mut variance_b) = (0f32, 0f32, 0f32);
+    for (a, b) in a.iter().zip(b.iter()) {
+        covariance += (a - mean_a) * (b - mean_b);
+        variance_a += (a - mean_a) * (a - mean_a);
+        variance_b += (b - mean_b) * (b - mean_b);
+    }
+
+    let deviations = sqrtf(variance_a * variance_b);
+    if deviations > 0.0 {
+        covariance / deviations
+    } else {
+        0.0
+    }
+}
+
+/// Support for naming notes and chords after the preferred accidental of a key.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let chord = Chord::from_notes(&[DSharp3, G3, ASharp3]);
+/// assert_eq!(chord.name(&Key::minor(C4)).unwrap(), "Eb");
+/// assert_eq!(chord.name(&Key::major(E4)).unwrap(), "D#");
+/// ```
+impl From<&Key> for FlatOrSharp {
+    fn from(key: &Key) -> Self {
+        key.accidental()
+    }
+}
+
+/// The two modes a key can be in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KeyMode {
+    Major,
+    Minor,
+}
+
+/// The different techniques for moving from one key into another.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ModulationKind {
+    /// Going through a chord that belongs to both keys.
+    PivotChord,
+    /// Jumping between chords that share at least one note.
+    CommonTone,
+    /// Approaching the new tonic through a chain of dominants.
+    DominantChain,
+}
+
+/// A candidate way of modulating from one key into another, as a short progression of chords.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Modulation {
+    kind: ModulationKind,
+    chords: Vec<Chord>,
+}
+
+/// Convenient methods for working with modulations.
+impl Modulation {
+    /// Get the technique used by a modulation.
+    pub fn kind(&self) -> ModulationKind {
+        self.kind
+    }
+
+    /// Get the progression of chords that performs a modulation.
+    pub fn chords(&self) -> &[Chord] {
+        &self.chords
+    }
+}
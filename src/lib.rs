@@ -1,16 +1,52 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc(html_playground_url = "https://play.rust-lang.org/")]
 
 /// Enable allocations despite being `no_std`.
 #[macro_use]
 extern crate alloc;
 
+/// Parsing and writing of notes and chords in ABC notation.
+#[cfg(feature = "abc")]
+pub mod abc;
+/// Rendering of chords into PCM audio, and detection of the pitches in it.
+#[cfg(feature = "audio")]
+pub mod audio;
 /// Data structures and convenient methods for working with musical harmonies and chords.
 pub mod chord;
+/// Pitches and chords in equal divisions of the octave other than the usual 12, such as 19-EDO or
+/// 31-EDO.
+pub mod edo;
 /// Error types for this library.
 pub mod error;
+/// Data structures and convenient methods for working with the distances between notes.
+pub mod interval;
+/// Data structures and convenient methods for working with musical keys.
+pub mod key;
+/// Microtonal notes, which fall in between the notes of the chromatic scale.
+pub mod microtonal;
+/// Export of chords into MusicXML elements, for importing them into notation software.
+#[cfg(feature = "musicxml")]
+pub mod musicxml;
+/// Conventions for naming notes in different parts of the world, such as letters or solfège, and
+/// options for naming notes and chords.
+pub mod naming;
 /// Data structures and convenient methods for working with musical notes and MIDI messages.
 pub mod note;
+/// Data structures and convenient methods for working with sequences of chords.
+pub mod progression;
+/// Roman numeral analysis of chords in the context of a key.
+pub mod roman;
+/// Data structures and convenient methods for working with musical scales.
+pub mod scale;
+/// Pitch-class set theory tools for the analysis of atonal harmony.
+pub mod set_theory;
+/// Spelling of notes and chords with the right letter names for their context, such as a key.
+pub mod spelling;
+/// Tuning systems, and retuning of notes as done by the MIDI Tuning Standard.
+pub mod tuning;
+/// Support for writing names of notes, chords and qualities through `ufmt`-compatible writers.
+#[cfg(feature = "ufmt")]
+pub mod ufmt;
 
 /// Exports all the core features of this library through a simple export.
 ///
@@ -20,5 +56,9 @@ pub mod note;
 /// /* Now you have access to `Note`, `Chord`, etc.*/
 /// ```
 pub mod prelude {
-    pub use crate::{chord::qualities::*, chord::*, note::*};
+    pub use crate::{
+        chord::analysis::*, chord::fixed::*, chord::qualities::*, chord::tracker::*,
+        chord::voicing::*, chord::*, edo::*, interval::*, key::*, microtonal::*, naming::*,
+        note::*, progression::*, roman::*, scale::*, set_theory::*, spelling::*, tuning::*,
+    };
 }
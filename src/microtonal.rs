@@ -0,0 +1,222 @@
+use crate::error::Error;
+use crate::note::{parse_octave, parse_tone, FlatOrSharp, Note};
+use crate::tuning::{Cents, STANDARD_CONCERT_PITCH};
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt::Write;
+use core::str::FromStr;
+use libm::{log2f, powf, roundf};
+
+/// A pitch in 24-tone equal temperament, i.e. any of the notes of the chromatic scale plus the
+/// quarter tones that fall halfway between them, as used in Arabic, Persian or Turkish music.
+///
+/// Quarter tones are named after the note below with a half-sharp ("+"), or after the note above
+/// with a half-flat ("d"), e.g. "E+4" or "Fd4" for the quarter tone between E4 and F4.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// // The E half-flat that makes the Rast maqam
+/// let note = MicroNote::half_flat(E4).unwrap();
+/// assert_eq!(note.name(FlatOrSharp::Flat), "Ed4");
+/// assert_eq!(note.name(FlatOrSharp::Sharp), "D#+4");
+/// assert_eq!(note.pitch(), 63.5);
+/// assert!(note.is_quarter_tone());
+///
+/// // Notes of the chromatic scale are microtonal notes as well
+/// let note = MicroNote::from(C4);
+/// assert_eq!(note.note(), Some(C4));
+/// assert_eq!(note.name(FlatOrSharp::Sharp), "C4");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MicroNote {
+    quarter_tones: u8,
+}
+
+/// Convenient methods for working with microtonal notes.
+impl MicroNote {
+    /// Build a microtonal note out of how many quarter tones above C-1 (the lowest MIDI note) it
+    /// is.
+    pub fn from_quarter_tones(quarter_tones: u8) -> Self {
+        Self { quarter_tones }
+    }
+
+    /// Build the microtonal note a quarter tone above a note.
+    pub fn half_sharp(note: Note) -> Self {
+        Self::from_quarter_tones(note.midi_key_number() * 2 + 1)
+    }
+
+    /// Build the microtonal note a quarter tone below a note. There is nothing below the lowest
+    /// MIDI note, though.
+    pub fn half_flat(note: Note) -> Result<Self, Error> {
+        (note.midi_key_number() * 2)
+            .checked_sub(1)
+            .map(Self::from_quarter_tones)
+            .ok_or(Error::OutOfMIDIRange)
+    }
+
+    /// Get how many quarter tones above C-1 (the lowest MIDI note) a microtonal note is.
+    pub fn quarter_tones(self) -> u8 {
+        self.quarter_tones
+    }
+
+    /// Tell whether a microtonal note falls halfway between two notes of the chromatic scale.
+    pub fn is_quarter_tone(self) -> bool {
+        self.quarter_tones % 2 == 1
+    }
+
+    /// Get the note of the chromatic scale that a microtonal note is, unless it is a quarter tone.
+    pub fn note(self) -> Option<Note> {
+        if self.is_quarter_tone() {
+            return None;
+        }
+
+        Some(Note::from(self.quarter_tones / 2))
+    }
+
+    /// Get the pitch of a microtonal note, as a fractional MIDI key number.
+    pub fn pitch(self) -> f32 {
+        f32::from(self.quarter_tones) / 2f32
+    }
+
+    /// Get the frequency in Hertz of a microtonal note.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(MicroNote::from(A4).frequency(), A4.frequency());
+    /// assert_eq!(MicroNote::half_sharp(A4).frequency(), 452.89297);
+    /// ```
+    pub fn frequency(self) -> f32 {
+        STANDARD_CONCERT_PITCH * powf(2f32, (self.pitch() - 69f32) / 12f32)
+    }
+
+    /// Find the microtonal note closest to a frequency in Hertz, along with how far apart from it
+    /// the frequency is, in cents. This is the inverse of `frequency()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let (note, cents) = MicroNote::from_frequency(450.);
+    /// assert_eq!(note, MicroNote::half_sharp(A4));
+    /// assert!((cents + 11.09).abs() < 0.01);
+    /// ```
+    pub fn from_frequency(frequency: f32) -> (MicroNote, f32) {
+        let quarter_tones = 2f32 * (69f32 + 12f32 * log2f(frequency / STANDARD_CONCERT_PITCH));
+        let nearest = roundf(quarter_tones).clamp(0f32, 255f32);
+
+        (
+            Self::from_quarter_tones(nearest as u8),
+            Cents::from_semitones((quarter_tones - nearest) / 2f32).value(),
+        )
+    }
+
+    /// Transpose a microtonal note by some quarter tones, up or down.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// let note = MicroNote::from(C4).transposed(3).unwrap();
+    /// assert_eq!(note, MicroNote::half_sharp(CSharp4));
+    ///
+    /// assert_eq!(MicroNote::from(C4).transposed(-121), Err(OutOfMIDIRange));
+    /// ```
+    pub fn transposed(self, quarter_tones: i16) -> Result<Self, Error> {
+        u8::try_from(i16::from(self.quarter_tones) + quarter_tones)
+            .map(Self::from_quarter_tones)
+            .map_err(|_| Error::OutOfMIDIRange)
+    }
+
+    /// Get the name of a microtonal note. Quarter tones take the name of the note below with a
+    /// half-sharp ("+") if sharps are asked for, or the name of the note above with a half-flat
+    /// ("d") if flats are.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let note = MicroNote::half_sharp(B3);
+    /// assert_eq!(note.name(FlatOrSharp::Sharp), "B+3");
+    /// assert_eq!(note.name(FlatOrSharp::Flat), "Cd4");
+    ///
+    /// let note = MicroNote::half_sharp(CSharp4);
+    /// assert_eq!(note.name(FlatOrSharp::Sharp), "C#+4");
+    /// assert_eq!(note.name(FlatOrSharp::Flat), "Dd4");
+    /// ```
+    pub fn name(self, accidental: FlatOrSharp) -> String {
+        let mut name = String::new();
+        // Writing into a `String` never fails
+        self.write_name(&mut name, accidental).unwrap();
+
+        name
+    }
+
+    /// Write the name of a microtonal note into any `core::fmt::Write` implementor.
+    fn write_name<W: Write>(self, f: &mut W, accidental: FlatOrSharp) -> core::fmt::Result {
+        let below = Note::from(self.quarter_tones / 2);
+        if !self.is_quarter_tone() {
+            return below.write_name(f, accidental);
+        }
+
+        // There is no note above the highest quarter tone
+        let above = below.transposed(1).ok();
+        let (note, quarter) = match (accidental, above) {
+            (FlatOrSharp::Flat, Some(above)) => (above, 'd'),
+            _ => (below, '+'),
+        };
+        note.write_pitch_name(f, accidental)?;
+        f.write_char(quarter)?;
+
+        write!(f, "{}", note.octave())
+    }
+}
+
+/// Notes of the chromatic scale are microtonal notes too.
+impl From<Note> for MicroNote {
+    fn from(note: Note) -> Self {
+        Self::from_quarter_tones(note.midi_key_number() * 2)
+    }
+}
+
+/// Support for parsing microtonal notes written in scientific pitch notation, with an optional
+/// half-sharp ("+") or half-flat ("d") after the accidentals, such as "Ed4" or "F#+3".
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+///
+/// assert_eq!("Ed4".parse::<MicroNote>(), MicroNote::half_flat(E4));
+/// assert_eq!("F#+3".parse::<MicroNote>(), Ok(MicroNote::half_sharp(FSharp3)));
+/// assert_eq!("Bb2".parse::<MicroNote>(), Ok(MicroNote::from(ASharp2)));
+///
+/// assert_eq!("E%4".parse::<MicroNote>(), Err(InvalidNoteName));
+/// assert_eq!("Cd-1".parse::<MicroNote>(), Err(OutOfMIDIRange));
+/// assert_eq!("C9999".parse::<MicroNote>(), Err(OutOfMIDIRange));
+/// ```
+impl FromStr for MicroNote {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tone, rest) = parse_tone(s.trim()).ok_or(Error::InvalidNoteName)?;
+        let (quarter, octave) = match rest.chars().next() {
+            Some('+') => (1, &rest[1..]),
+            Some('d') => (-1, &rest[1..]),
+            _ => (0, rest),
+        };
+        let quarter_tones = parse_octave(octave)?
+            .checked_add(1)
+            .and_then(|octave| octave.checked_mul(12))
+            .and_then(|semitones| semitones.checked_add(i16::from(tone)))
+            .and_then(|semitones| semitones.checked_mul(2))
+            .and_then(|quarter_tones| quarter_tones.checked_add(quarter))
+            .ok_or(Error::OutOfMIDIRange)?;
+
+        u8::try_from(quarter_tones)
+            .map(Self::from_quarter_tones)
+            .map_err(|_| Error::OutOfMIDIRange)
+    }
+}
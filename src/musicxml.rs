@@ -0,0 +1,135 @@
+use crate::prelude::*;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Write a chord as a MusicXML `<harmony>` element, telling its root, its kind and its bass (if
+/// other than the root), so that it can be imported by notation software. Accidentals are spelled
+/// as requested.
+///
+/// Chord types with no MusicXML counterpart are written with the "other" kind. Nothing is
+/// returned for chords with no identifiable root.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{musicxml::harmony, prelude::{*, Note::*}};
+///
+/// let chord = Chord::from_notes(&[E3, A3, C4, G4]);
+/// assert_eq!(
+///     harmony(&chord, FlatOrSharp::Sharp).unwrap(),
+///     "<harmony><root><root-step>A</root-step></root><kind>minor-seventh</kind>\
+///      <bass><bass-step>E</bass-step></bass></harmony>"
+/// );
+///
+/// let chord = Chord::from_notes(&[ASharp3, D4, F4]);
+/// assert_eq!(
+///     harmony(&chord, FlatOrSharp::Flat).unwrap(),
+///     "<harmony><root><root-step>B</root-step><root-alter>-1</root-alter></root>\
+///      <kind>major</kind></harmony>"
+/// );
+///
+/// assert_eq!(harmony(&Chord::default(), FlatOrSharp::Flat), None);
+/// ```
+pub fn harmony(chord: &Chord, accidental: impl Into<FlatOrSharp>) -> Option<String> {
+    let accidental = accidental.into();
+    let root = chord.root()?;
+
+    // Writing into a `String` never fails
+    let mut xml = String::from("<harmony>");
+    write_pitch(&mut xml, "root", root, accidental).unwrap();
+    write!(xml, "<kind>{}</kind>", kind(chord.chord_type())).unwrap();
+    if let Some(bass) = chord
+        .bass()
+        .filter(|bass| bass.pitch_class() != root.pitch_class())
+    {
+        write_pitch(&mut xml, "bass", bass, accidental).unwrap();
+    }
+    xml.push_str("</harmony>");
+
+    Some(xml)
+}
+
+/// Write a chord as a MusicXML `<harmony>` element, as done by `harmony`, into any
+/// `std::io::Write` implementor, such as a file being exported.
+///
+/// Nothing gets written for chords with no identifiable root.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{musicxml::write_harmony, prelude::{*, Note::*}};
+///
+/// let mut xml = Vec::new();
+/// write_harmony(&mut xml, &Chord::from_notes(&[D3, F3, A3]), FlatOrSharp::Flat).unwrap();
+/// write_harmony(&mut xml, &Chord::default(), FlatOrSharp::Flat).unwrap();
+/// assert_eq!(
+///     String::from_utf8(xml).unwrap(),
+///     "<harmony><root><root-step>D</root-step></root><kind>minor</kind></harmony>"
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn write_harmony<W: std::io::Write>(
+    writer: &mut W,
+    chord: &Chord,
+    accidental: impl Into<FlatOrSharp>,
+) -> Result<(), crate::error::Error> {
+    if let Some(xml) = harmony(chord, accidental) {
+        writer.write_all(xml.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Write the step and alteration of a note, as found in the `<root>` and `<bass>` elements.
+fn write_pitch<W: Write>(
+    f: &mut W,
+    element: &str,
+    note: Note,
+    accidental: FlatOrSharp,
+) -> core::fmt::Result {
+    let mut name = String::new();
+    note.write_pitch_name(&mut name, accidental)?;
+    let mut chars = name.chars();
+    let step = chars.next().ok_or(core::fmt::Error)?;
+
+    write!(f, "<{0}><{0}-step>{1}</{0}-step>", element, step)?;
+    match chars.next() {
+        Some('#') => write!(f, "<{0}-alter>1</{0}-alter>", element)?,
+        Some('b') => write!(f, "<{0}-alter>-1</{0}-alter>", element)?,
+        _ => (),
+    }
+    write!(f, "</{}>", element)
+}
+
+/// Get the value of the MusicXML `<kind>` element for a type of chord.
+fn kind(chord_type: &ChordType) -> &'static str {
+    use ChordType::*;
+
+    match chord_type {
+        SingleNote => "pedal",
+        Dyad(DyadQuality::Perfect(5)) => "power",
+        Triad(TriadQuality::Major) => "major",
+        Triad(TriadQuality::Minor) => "minor",
+        Triad(TriadQuality::Augmented) => "augmented",
+        Triad(TriadQuality::Diminished) => "diminished",
+        Triad(TriadQuality::Suspended(2)) => "suspended-second",
+        Triad(TriadQuality::Suspended(4)) => "suspended-fourth",
+        Tetrad(TetradQuality::SeventhDominant) => "dominant",
+        Tetrad(TetradQuality::SeventhMajor) => "major-seventh",
+        Tetrad(TetradQuality::SeventhMinor) => "minor-seventh",
+        Tetrad(TetradQuality::SeventhMinorMajor) => "major-minor",
+        Tetrad(TetradQuality::SeventhDiminished) => "diminished-seventh",
+        Tetrad(TetradQuality::SeventhHalfDiminished) => "half-diminished",
+        Tetrad(TetradQuality::SeventhAugmented) => "augmented-seventh",
+        Tetrad(TetradQuality::Sixth) => "major-sixth",
+        Tetrad(TetradQuality::SixthMinor) => "minor-sixth",
+        Pentad(PentadQuality::Ninth) => "dominant-ninth",
+        Pentad(PentadQuality::NinthMajor) => "major-ninth",
+        Pentad(PentadQuality::NinthMinor) => "minor-ninth",
+        Hexad(HexadQuality::Eleventh) => "dominant-11th",
+        Hexad(HexadQuality::EleventhMajor) => "major-11th",
+        Hexad(HexadQuality::EleventhMinor) => "minor-11th",
+        Heptad(HeptadQuality::Thirteenth) => "dominant-13th",
+        Heptad(HeptadQuality::ThirteenthMajor) => "major-13th",
+        Heptad(HeptadQuality::ThirteenthMinor) => "minor-13th",
+        _ => "other",
+    }
+}
@@ -0,0 +1,452 @@
+use crate::prelude::*;
+use crate::spelling::LETTERS;
+use core::fmt::Write;
+
+/// Fixed-do solfège syllables for the natural tones, from C to B.
+const SYLLABLES: [&str; 7] = ["Do", "Re", "Mi", "Fa", "Sol", "La", "Si"];
+
+/// A convention for naming the natural tones (and the notes that alter them with accidentals), as
+/// those differ across the world. Implementing it allows naming notes and chords in any language.
+///
+/// # Examples
+/// ```rust
+/// use core::fmt::Write;
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let options = NameOptions::new(FlatOrSharp::Flat)
+///     .with_convention(&Solfege)
+///     .with_accidental_style(AccidentalStyle::Unicode);
+/// assert_eq!(GSharp4.name(options), "La♭4");
+///
+/// // Dutch names write accidentals as suffixes, like German ones, but keep the letter B
+/// struct Dutch;
+///
+/// impl NamingConvention for Dutch {
+///     fn write_pitch(
+///         &self,
+///         f: &mut dyn Write,
+///         letter: char,
+///         accidental: Accidental,
+///         _: AccidentalStyle,
+///     ) -> core::fmt::Result {
+///         f.write_char(letter)?;
+///         let suffix = if accidental < Accidental::Natural { "es" } else { "is" };
+///         (0..accidental.semitones().abs()).try_for_each(|_| f.write_str(suffix))
+///     }
+/// }
+///
+/// let chord = Chord::from_notes(&[ASharp3, D4, F4, GSharp4]);
+/// let options = NameOptions::new(FlatOrSharp::Flat).with_convention(&Dutch);
+/// assert_eq!(chord.name(options).unwrap(), "Bes7");
+/// ```
+pub trait NamingConvention {
+    /// Write the name of a natural tone, given by its letter (one of "A", "B", "C", "D", "E", "F"
+    /// or "G"), as altered by an accidental, into any `core::fmt::Write` implementor. The style
+    /// tells how accidentals are preferably written, for conventions that write them as symbols.
+    fn write_pitch(
+        &self,
+        f: &mut dyn Write,
+        letter: char,
+        accidental: Accidental,
+        style: AccidentalStyle,
+    ) -> core::fmt::Result;
+}
+
+/// Letters from A to G, as used in English-speaking countries (e.g. "C", "D#" or "Ab").
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Letters;
+
+impl NamingConvention for Letters {
+    fn write_pitch(
+        &self,
+        f: &mut dyn Write,
+        letter: char,
+        accidental: Accidental,
+        style: AccidentalStyle,
+    ) -> core::fmt::Result {
+        f.write_char(letter)?;
+
+        f.write_str(accidental.symbol(style))
+    }
+}
+
+/// Fixed-do solfège syllables, as used in Romance-language countries (e.g. "Do", "Re#" or "Lab").
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let chord = Chord::from_notes(&[D3, FSharp3, A3, C4]);
+/// let options = NameOptions::new(FlatOrSharp::Sharp).with_convention(&Solfege);
+/// assert_eq!(chord.name(options).unwrap(), "Re7");
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Solfege;
+
+impl NamingConvention for Solfege {
+    fn write_pitch(
+        &self,
+        f: &mut dyn Write,
+        letter: char,
+        accidental: Accidental,
+        style: AccidentalStyle,
+    ) -> core::fmt::Result {
+        let syllable = LETTERS
+            .iter()
+            .position(|candidate| *candidate == letter)
+            .map_or("", |letter| SYLLABLES[letter]);
+        f.write_str(syllable)?;
+
+        f.write_str(accidental.symbol(style))
+    }
+}
+
+/// German names, where B is called "H" and accidentals are written as the "is" (sharp) and "es"
+/// (flat) suffixes (e.g. "Cis", "Es", or "B" for B flat).
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let names = ["C#4", "Eb4", "Ab4", "B4", "Bb4", "Bbb4", "Fx4"].map(|name| {
+///     let note = name.parse::<SpelledNote>().unwrap();
+///     note.name(AccidentalStyle::Ascii, &German, OctaveNotation::Scientific)
+/// });
+/// assert_eq!(names, ["Cis4", "Es4", "As4", "H4", "B4", "Heses4", "Fisis4"]);
+///
+/// let chord = Chord::from_notes(&[ASharp3, D4, F4, GSharp4]);
+/// let options = NameOptions::new(FlatOrSharp::Flat).with_convention(&German);
+/// assert_eq!(chord.name(options).unwrap(), "B7");
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct German;
+
+impl NamingConvention for German {
+    fn write_pitch(
+        &self,
+        f: &mut dyn Write,
+        letter: char,
+        accidental: Accidental,
+        _: AccidentalStyle,
+    ) -> core::fmt::Result {
+        let name = if letter == 'B' { 'H' } else { letter };
+        let suffix = if accidental < Accidental::Natural {
+            "es"
+        } else {
+            "is"
+        };
+        let mut count = accidental.semitones().abs();
+
+        match (name, accidental) {
+            // B flat is the only flat with a name of its own
+            ('H', Accidental::Flat) => return f.write_char('B'),
+            // Flats of vowels drop the "e" of their suffix (e.g. "Es" or "Ases")
+            ('E', Accidental::Flat | Accidental::DoubleFlat)
+            | ('A', Accidental::Flat | Accidental::DoubleFlat) => {
+                write!(f, "{}s", name)?;
+                count -= 1;
+            }
+            _ => f.write_char(name)?,
+        }
+        for _ in 0..count {
+            f.write_str(suffix)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The notations for telling the octave of a note.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let options = NameOptions::default().with_octave_notation(OctaveNotation::Helmholtz);
+/// let names = [C0, C1, C2, C3, C4, C5].map(|note| note.name(options));
+/// assert_eq!(names, ["C,,", "C,", "C", "c", "c'", "c''"]);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OctaveNotation {
+    /// Octave numbers after the name of the note, where middle C starts octave 4 (e.g. "C4").
+    Scientific,
+    /// Upper case names for the octaves from C2 down, each of them with one more "," than the one
+    /// above it, and lower case names for the octaves from C3 up, each of them with one more "'"
+    /// than the one below it (e.g. "c'" for middle C).
+    Helmholtz,
+}
+
+/// Convenient methods for telling octaves after a notation.
+impl OctaveNotation {
+    /// Write the name of a note in some octave into any `core::fmt::Write` implementor, without
+    /// allocating, given a function that writes its name without the octave.
+    pub(crate) fn write_note<W: Write>(
+        self,
+        f: &mut W,
+        octave: i8,
+        write_pitch: impl FnOnce(&mut dyn Write) -> core::fmt::Result,
+    ) -> core::fmt::Result {
+        match self {
+            OctaveNotation::Scientific => {
+                write_pitch(f)?;
+                write!(f, "{}", octave)
+            }
+            OctaveNotation::Helmholtz if octave >= 3 => {
+                write_pitch(&mut Lowercase(f))?;
+                (3..octave).try_for_each(|_| f.write_char('\''))
+            }
+            OctaveNotation::Helmholtz => {
+                write_pitch(f)?;
+                (octave..2).try_for_each(|_| f.write_char(','))
+            }
+        }
+    }
+}
+
+/// Adapter for `core::fmt::Write` implementors that turns whatever is written into it into lower
+/// case.
+struct Lowercase<'a, W: Write>(&'a mut W);
+
+impl<W: Write> Write for Lowercase<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        s.chars()
+            .flat_map(char::to_lowercase)
+            .try_for_each(|c| self.0.write_char(c))
+    }
+}
+
+/// Options for naming notes and chords: how to spell them, the convention for naming their tones,
+/// and how to write accidentals, octaves, inversions, added notes and chord qualities. Options that
+/// only make sense for chords are ignored when naming notes, and the other way around.
+///
+/// Spellings (e.g. `FlatOrSharp::Flat` or a `Key`) turn into the default options for them, so they
+/// can be passed as is wherever options are expected.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let chord = Chord::from_notes(&[G2, C3, DSharp3, ASharp3]);
+/// assert_eq!(chord.name(FlatOrSharp::Flat).unwrap(), "Cm7/G");
+///
+/// let options = NameOptions::new(FlatOrSharp::Flat)
+///     .with_slash(false)
+///     .with_style(NameStyle::Jazz);
+/// assert_eq!(chord.name(options).unwrap(), "C-7");
+///
+/// let options = NameOptions::new(FlatOrSharp::Flat)
+///     .with_convention(&Solfege)
+///     .with_accidental_style(AccidentalStyle::Unicode);
+/// assert_eq!(DSharp3.name(options), "Mi♭3");
+/// assert_eq!(DSharp3.name(options.with_octave(false)), "Mi♭");
+/// ```
+#[derive(Copy, Clone)]
+pub struct NameOptions<'a> {
+    pub(crate) spelling: Spelling,
+    pub(crate) convention: &'a dyn NamingConvention,
+    pub(crate) accidentals: AccidentalStyle,
+    pub(crate) octave: bool,
+    pub(crate) octaves: OctaveNotation,
+    pub(crate) slash: bool,
+    pub(crate) additions: bool,
+    pub(crate) addition_style: AdditionStyle,
+    pub(crate) polychord_style: PolychordStyle,
+    pub(crate) style: NameStyle,
+}
+
+/// Convenient methods for building naming options.
+impl<'a> NameOptions<'a> {
+    /// Build the default options for some spelling: letter names, ASCII accidentals, octaves in
+    /// scientific pitch notation, slash basses, plain additions, slashes between the chords
+    /// stacked in a polychord and qualities in the standard style.
+    pub fn new(spelling: impl Into<Spelling>) -> Self {
+        Self {
+            spelling: spelling.into(),
+            convention: &Letters,
+            accidentals: AccidentalStyle::Ascii,
+            octave: true,
+            octaves: OctaveNotation::Scientific,
+            slash: true,
+            additions: true,
+            addition_style: AdditionStyle::Plain,
+            polychord_style: PolychordStyle::Slash,
+            style: NameStyle::Standard,
+        }
+    }
+
+    /// Set the spelling of notes, either with fixed accidentals or after a key.
+    pub fn with_spelling(mut self, spelling: impl Into<Spelling>) -> Self {
+        self.spelling = spelling.into();
+
+        self
+    }
+
+    /// Set the convention for naming natural tones, e.g. `Solfege` or `German`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[E3, A3, C4]);
+    /// let options = NameOptions::new(FlatOrSharp::Sharp).with_convention(&Solfege);
+    /// assert_eq!(chord.name(options).unwrap(), "Lam/Mi");
+    /// ```
+    pub fn with_convention(mut self, convention: &'a dyn NamingConvention) -> Self {
+        self.convention = convention;
+
+        self
+    }
+
+    /// Set whether accidentals are written with plain ASCII characters (e.g. "Bb") or with their
+    /// Unicode glyphs (e.g. "B♭"), which also applies to those in the quality of chords.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[CSharp3, E3, G3, B3]);
+    /// let options =
+    ///     NameOptions::new(FlatOrSharp::Sharp).with_accidental_style(AccidentalStyle::Unicode);
+    /// assert_eq!(chord.name(options).unwrap(), "C♯m7♭5");
+    /// ```
+    pub fn with_accidental_style(mut self, accidentals: AccidentalStyle) -> Self {
+        self.accidentals = accidentals;
+
+        self
+    }
+
+    /// Set whether notes are named with their octave (e.g. "C4") or not (e.g. "C").
+    pub fn with_octave(mut self, octave: bool) -> Self {
+        self.octave = octave;
+
+        self
+    }
+
+    /// Set the notation for telling the octave of notes.
+    pub fn with_octave_notation(mut self, octaves: OctaveNotation) -> Self {
+        self.octaves = octaves;
+
+        self
+    }
+
+    /// Set whether inversions of chords are named using slash notation (e.g. "C/G") or not (e.g.
+    /// "C").
+    pub fn with_slash(mut self, slash: bool) -> Self {
+        self.slash = slash;
+
+        self
+    }
+
+    /// Set whether the notes that were added to chords are named (e.g. "Cadd9") or not (e.g. "C").
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[C3, D3, E3, G3]);
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "Cadd9");
+    /// let options = NameOptions::new(FlatOrSharp::Sharp).with_additions(false);
+    /// assert_eq!(chord.name(options).unwrap(), "C");
+    /// ```
+    pub fn with_additions(mut self, additions: bool) -> Self {
+        self.additions = additions;
+
+        self
+    }
+
+    /// Set how to name the notes that were added to chords.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let chord = Chord::from_notes(&[C1, CSharp1, E1, G1]);
+    /// let options =
+    ///     NameOptions::new(FlatOrSharp::Flat).with_addition_style(AdditionStyle::Parenthesized);
+    /// assert_eq!(chord.name(options).unwrap(), "C(add b9)");
+    /// ```
+    pub fn with_addition_style(mut self, addition_style: AdditionStyle) -> Self {
+        self.addition_style = addition_style;
+
+        self
+    }
+
+    /// Set how to separate the chords stacked in a polychord.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // F# major over C major
+    /// let chord = Chord::from_notes(&[C3, E3, G3, FSharp4, ASharp4, CSharp5]);
+    /// let options =
+    ///     NameOptions::new(FlatOrSharp::Sharp).with_polychord_style(PolychordStyle::Bar);
+    /// assert_eq!(chord.name(options).unwrap(), "F#|C");
+    /// ```
+    pub fn with_polychord_style(mut self, polychord_style: PolychordStyle) -> Self {
+        self.polychord_style = polychord_style;
+
+        self
+    }
+
+    /// Set the style for writing the quality of chords, e.g. "C△7" for jazz or "Cmaj7" for plain
+    /// chord symbols.
+    pub fn with_style(mut self, style: NameStyle) -> Self {
+        self.style = style;
+
+        self
+    }
+}
+
+/// Default options for naming notes and chords, using sharps for accidentals.
+impl Default for NameOptions<'_> {
+    fn default() -> Self {
+        Self::new(FlatOrSharp::Sharp)
+    }
+}
+
+/// Debug representation of naming options. Naming conventions are not required to be `Debug`, so
+/// they are left out.
+impl core::fmt::Debug for NameOptions<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NameOptions")
+            .field("spelling", &self.spelling)
+            .field("accidentals", &self.accidentals)
+            .field("octave", &self.octave)
+            .field("octaves", &self.octaves)
+            .field("slash", &self.slash)
+            .field("additions", &self.additions)
+            .field("addition_style", &self.addition_style)
+            .field("polychord_style", &self.polychord_style)
+            .field("style", &self.style)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Default options for naming notes and chords with some spelling.
+impl From<Spelling> for NameOptions<'_> {
+    fn from(spelling: Spelling) -> Self {
+        Self::new(spelling)
+    }
+}
+
+/// Default options for naming notes and chords with fixed accidentals.
+impl From<FlatOrSharp> for NameOptions<'_> {
+    fn from(accidental: FlatOrSharp) -> Self {
+        Self::new(accidental)
+    }
+}
+
+/// Default options for naming notes and chords after a key.
+impl From<Key> for NameOptions<'_> {
+    fn from(key: Key) -> Self {
+        Self::new(key)
+    }
+}
+
+/// Default options for naming notes and chords after a key.
+impl From<&Key> for NameOptions<'_> {
+    fn from(key: &Key) -> Self {
+        Self::new(key)
+    }
+}
@@ -1,9 +1,20 @@
 use crate::error::Error;
+use crate::interval::Interval;
+use crate::naming::NameOptions;
+use crate::spelling::{SpelledNote, Spelling};
+use crate::tuning::{
+    Cents, Tuning, FIVE_LIMIT_RATIOS, PYTHAGOREAN_DEVIATIONS, QUARTER_COMMA_MEANTONE_DEVIATIONS,
+    STANDARD_CONCERT_PITCH, WERCKMEISTER_III_DEVIATIONS,
+};
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::convert::TryFrom;
-use libm::powf;
+use core::fmt::{Display, Formatter, Write};
+use core::ops::Sub;
+use core::str::FromStr;
+use libm::{log2f, pow, powf, roundf};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(u8)]
 pub enum Note {
     CMinus1,
@@ -137,7 +148,8 @@ pub enum Note {
 }
 
 impl Note {
-    /// Get the frequency in Hertz of a note.
+    /// Get the frequency in Hertz of a note, in 12-tone equal temperament. The frequencies of notes
+    /// on retuned instruments are given by `TuningTable::frequency` instead.
     ///
     /// # Examples
     /// ```rust
@@ -164,14 +176,179 @@ impl Note {
     /// assert_eq!(highest_freq, 12543.855f32);
     /// ```
     pub fn frequency(self) -> f32 {
-        let midi_key_number = f32::from(self.midi_key_number());
-        let relative_to_concert_pitch = midi_key_number - 69f32;
-        let octaved = relative_to_concert_pitch / 12f32;
+        self.frequency_with(&Tuning::default())
+    }
+
+    /// Get the frequency in Hertz of a note, in 12-tone equal temperament, with double precision.
+    /// This is meant for precise DSP work, such as driving audio-rate phase accumulators, where
+    /// the rounding errors of `frequency()` add up.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::Note;
+    ///
+    /// assert_eq!(Note::A4.frequency_f64(), 440f64);
+    /// assert_eq!(Note::C4.frequency_f64(), 261.6255653005986f64);
+    /// assert_eq!(Note::G9.frequency_f64(), 12543.853951415975f64);
+    /// ```
+    pub fn frequency_f64(self) -> f64 {
+        let relative_to_concert_pitch = f64::from(self.midi_key_number()) - 69f64;
 
-        440f32 * powf(2f32, octaved)
+        f64::from(STANDARD_CONCERT_PITCH) * pow(2f64, relative_to_concert_pitch / 12f64)
     }
 
-    /// Get the musician-friendly name of a note.
+    /// Get the period in seconds of a note, in 12-tone equal temperament, i.e. how long each cycle
+    /// of its waveform lasts.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::Note;
+    ///
+    /// assert_eq!(Note::A4.period(), 1f64 / 440f64);
+    /// assert!((Note::C4.period() - 0.0038222).abs() < 1e-7);
+    /// ```
+    pub fn period(self) -> f64 {
+        1f64 / self.frequency_f64()
+    }
+
+    /// Get the frequency in Hertz of a note in some tuning.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // Many orchestras tune a bit higher than 440Hz
+    /// let tuning = Tuning::equal_temperament(442.);
+    /// assert_eq!(A4.frequency_with(&tuning), 442.);
+    /// assert_eq!(A5.frequency_with(&tuning), 884.);
+    /// assert_eq!(C4.frequency_with(&tuning), 262.81476);
+    ///
+    /// // A pure fifth above the tonic is exactly one and a half times its frequency
+    /// let tuning = Tuning::Just { tonic: D3 };
+    /// assert_eq!(A3.frequency_with(&tuning), D3.frequency() * 1.5);
+    /// assert_eq!(A4.frequency_with(&tuning), D3.frequency() * 3.);
+    /// ```
+    pub fn frequency_with(self, tuning: &Tuning) -> f32 {
+        match *tuning {
+            Tuning::EqualTemperament { concert_pitch } => {
+                let midi_key_number = f32::from(self.midi_key_number());
+                let relative_to_concert_pitch = midi_key_number - 69f32;
+                let octaved = relative_to_concert_pitch / 12f32;
+
+                concert_pitch * powf(2f32, octaved)
+            }
+            Tuning::Just { tonic } => {
+                let distance =
+                    i16::from(self.midi_key_number()) - i16::from(tonic.midi_key_number());
+                let (numerator, denominator) = FIVE_LIMIT_RATIOS[distance.rem_euclid(12) as usize];
+                let octaves = f32::from(distance.div_euclid(12));
+
+                tonic.frequency() * f32::from(numerator) / f32::from(denominator)
+                    * powf(2f32, octaves)
+            }
+            Tuning::Pythagorean { concert_pitch } => {
+                self.tempered_frequency(concert_pitch, &PYTHAGOREAN_DEVIATIONS)
+            }
+            Tuning::QuarterCommaMeantone { concert_pitch } => {
+                self.tempered_frequency(concert_pitch, &QUARTER_COMMA_MEANTONE_DEVIATIONS)
+            }
+            Tuning::WerckmeisterIII { concert_pitch } => {
+                self.tempered_frequency(concert_pitch, &WERCKMEISTER_III_DEVIATIONS)
+            }
+        }
+    }
+
+    /// Get the frequency in Hertz of a note in a temperament, out of how far apart in cents every
+    /// pitch class is from equal temperament, keeping A4 at the concert pitch.
+    fn tempered_frequency(self, concert_pitch: f32, deviations: &[f32; 12]) -> f32 {
+        let equal = self.frequency_with(&Tuning::equal_temperament(concert_pitch));
+        let cents = Cents::new(deviations[usize::from(self.pitch_class())] - deviations[9]);
+
+        equal * cents.ratio()
+    }
+
+    /// Find the note closest to a frequency in Hertz, along with how far apart from it the
+    /// frequency is, in cents. This is the inverse of `frequency()`.
+    ///
+    /// Frequencies outside of the MIDI range are taken as its lowest or highest note, with a
+    /// deviation beyond 50 cents.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::Note;
+    ///
+    /// assert_eq!(Note::from_frequency(440.), (Note::A4, 0.));
+    ///
+    /// // A slightly flat middle C
+    /// let (note, cents) = Note::from_frequency(260.);
+    /// assert_eq!(note, Note::C4);
+    /// assert!((cents + 10.79).abs() < 0.01);
+    ///
+    /// // Going from note to frequency and back gives the same note
+    /// for midi_key_number in 0..128 {
+    ///     let note = Note::from(midi_key_number);
+    ///     assert_eq!(Note::from_frequency(note.frequency()).0, note);
+    /// }
+    ///
+    /// // Way too low
+    /// let (note, cents) = Note::from_frequency(1.);
+    /// assert_eq!(note, Note::CMinus1);
+    /// assert!(cents < -50.);
+    /// ```
+    pub fn from_frequency(frequency: f32) -> (Note, f32) {
+        Self::from_frequency_with(frequency, &Tuning::default())
+    }
+
+    /// Find the note closest to a frequency in Hertz in some tuning, along with how far apart from
+    /// it the frequency is, in cents. This is the inverse of `frequency_with()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // 440Hz is a bit flat for an A4 when tuning to 442Hz
+    /// let (note, cents) = Note::from_frequency_with(440., &Tuning::equal_temperament(442.));
+    /// assert_eq!(note, A4);
+    /// assert!((cents + 7.85).abs() < 0.01);
+    ///
+    /// // But it is a slightly sharp A#4 in baroque tuning
+    /// let (note, cents) = Note::from_frequency_with(440., &Tuning::equal_temperament(415.));
+    /// assert_eq!(note, ASharp4);
+    /// assert!((cents - 1.27).abs() < 0.01);
+    ///
+    /// // A pure major third above C4 is E4, in tune
+    /// let (note, cents) = Note::from_frequency_with(327.03192, &Tuning::Just { tonic: C4 });
+    /// assert_eq!(note, E4);
+    /// assert!(cents.abs() < 0.01);
+    /// ```
+    pub fn from_frequency_with(frequency: f32, tuning: &Tuning) -> (Note, f32) {
+        let concert_pitch = match *tuning {
+            Tuning::EqualTemperament { concert_pitch } => concert_pitch,
+            // Not evenly spaced, so every note needs to be tried
+            _ => {
+                return (0..128u8)
+                    .map(Note::from)
+                    .map(|note| {
+                        (
+                            note,
+                            Cents::between(note.frequency_with(tuning), frequency).value(),
+                        )
+                    })
+                    .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+                    .unwrap_or((Note::CMinus1, f32::NAN));
+            }
+        };
+        let pitch = 69f32 + 12f32 * log2f(frequency / concert_pitch);
+        let midi_key_number = roundf(pitch).clamp(0f32, 127f32);
+
+        (
+            Note::from(midi_key_number as u8),
+            Cents::from_semitones(pitch - midi_key_number).value(),
+        )
+    }
+
+    /// Get the musician-friendly name of a note, as told by some naming options, or merely by a
+    /// spelling (either with a fixed accidental or after a key) for the default ones.
     ///
     /// # Examples
     /// ```rust
@@ -196,8 +373,91 @@ impl Note {
     /// // Sharp C9 is also "Db9"
     /// let c_sharp9_name = Note::CSharp9.name(Sharp);
     /// assert_eq!(c_sharp9_name, String::from("C#9"));
+    ///
+    /// // In F# major, F is spelled as E#, which makes it belong to the octave below
+    /// use whatthechord::key::Key;
+    /// assert_eq!(Note::F4.name(&Key::major(Note::FSharp4)), "E#4");
+    ///
+    /// // Other options choose the glyphs for accidentals, the convention for naming natural tones
+    /// // and how to tell octaves, if at all
+    /// use whatthechord::naming::*;
+    /// use whatthechord::note::AccidentalStyle::*;
+    ///
+    /// let options = NameOptions::new(Flat).with_accidental_style(Unicode);
+    /// assert_eq!(Note::ASharp4.name(options), "B♭4");
+    /// assert_eq!(Note::ASharp4.name(options.with_convention(&Solfege)), "Si♭4");
+    /// assert_eq!(Note::ASharp4.name(options.with_octave(false)), "B♭");
+    ///
+    /// let options = options.with_spelling(&Key::major(Note::FSharp4));
+    /// assert_eq!(Note::F4.name(options), "E♯4");
+    ///
+    /// // Helmholtz pitch notation goes well with German names
+    /// let options = NameOptions::new(Flat)
+    ///     .with_convention(&German)
+    ///     .with_octave_notation(OctaveNotation::Helmholtz);
+    /// assert_eq!(Note::ASharp4.name(options), "b'");
+    /// assert_eq!(Note::CSharp2.name(options.with_spelling(Sharp)), "Cis");
     /// ```
-    pub fn name(self, accidental: FlatOrSharp) -> String {
+    pub fn name<'a>(self, options: impl Into<NameOptions<'a>>) -> String {
+        let mut name = String::new();
+        // Writing into a `String` never fails
+        self.write_name_with(&mut name, &options.into()).unwrap();
+
+        name
+    }
+
+    /// Write the name of a note, as told by some naming options, into any `core::fmt::Write`
+    /// implementor, without allocating.
+    pub(crate) fn write_name_with<W: Write>(
+        self,
+        f: &mut W,
+        options: &NameOptions,
+    ) -> core::fmt::Result {
+        let spelled = self.spelled(options.spelling);
+        if options.octave {
+            spelled.write(f, options.accidentals, options.convention, options.octaves)
+        } else {
+            spelled.write_pitch(f, options.accidentals, options.convention)
+        }
+    }
+
+    /// Spell a note with a letter name and an accidental, either fixed or chosen after a key.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let spelled = F4.spelled(&Key::major(FSharp4));
+    /// assert_eq!(spelled.letter(), 'E');
+    /// assert_eq!(spelled.accidental(), Accidental::Sharp);
+    ///
+    /// let spelled = G4.spelled(FlatOrSharp::Sharp);
+    /// assert_eq!(spelled.to_string(), "G4");
+    /// assert_eq!(spelled.note(), G4);
+    /// ```
+    pub fn spelled(self, spelling: impl Into<Spelling>) -> SpelledNote {
+        spelling.into().spell(self).with_octave(self)
+    }
+
+    /// Write the name of a note (e.g. "C#4") into any `core::fmt::Write` implementor, without
+    /// allocating.
+    pub(crate) fn write_name<W: Write>(
+        self,
+        f: &mut W,
+        accidental: FlatOrSharp,
+    ) -> core::fmt::Result {
+        self.write_pitch_name(f, accidental)?;
+
+        write!(f, "{}", self.octave())
+    }
+
+    /// Write the name of a note without its octave (e.g. "C#") into any `core::fmt::Write`
+    /// implementor, without allocating.
+    pub(crate) fn write_pitch_name<W: Write>(
+        self,
+        f: &mut W,
+        accidental: FlatOrSharp,
+    ) -> core::fmt::Result {
         use FlatOrSharp::*;
         let (transpose_half_tones, accidental) = match (self.is_sharp(), accidental) {
             (true, Flat) => (1i8, "b"),
@@ -205,9 +465,9 @@ impl Note {
             (false, _) => (0i8, ""),
         };
         let tone = self.tone_name(transpose_half_tones);
-        let octave = self.octave();
 
-        format!("{}{}{}", tone, accidental, octave)
+        f.write_char(tone)?;
+        f.write_str(accidental)
     }
 
     /// Get the tone name (one of "A", "B", "C", "D", "E", "F" or "G") of a note.
@@ -227,25 +487,55 @@ impl Note {
     /// // Sharp C1 tone name becomes 'D' if we think of it as a flat note instead of sharp note.
     /// let c_sharp1_tone_name = Note::CSharp1.tone_name(1);
     /// assert_eq!(c_sharp1_tone_name, 'D');
+    ///
+    /// // Sharp A4 tone name is 'A', or 'B' if we think of it as a flat note
+    /// assert_eq!(Note::ASharp4.tone_name(0), 'A');
+    /// assert_eq!(Note::ASharp4.tone_name(1), 'B');
+    ///
+    /// // The highest MIDI note is a G
+    /// assert_eq!(Note::G9.tone_name(0), 'G');
     /// ```
     pub fn tone_name(self, transpose_half_tones: i8) -> char {
-        // Unwrap is OK because Note has no more than 128 items
-        let midi_key_number = i8::try_from(self.midi_key_number()).unwrap();
-        let relative_to_a = (midi_key_number + transpose_half_tones + 3) % 12;
-        let char_offset = if relative_to_a < 7 && relative_to_a % 2 == 0 {
-            relative_to_a / 2
-        } else {
-            relative_to_a / 2 + 1
-        };
+        const TONE_NAMES: [char; 12] = ['C', 'C', 'D', 'D', 'E', 'F', 'F', 'G', 'G', 'A', 'A', 'B'];
+        let pitch_class =
+            (i16::from(self.pitch_class()) + i16::from(transpose_half_tones)).rem_euclid(12);
 
-        // Unwrap is OK because `char_offset` is always < 7
-        u8::try_from(char_offset)
-            .map(|char_offset| char::from(65 + char_offset))
-            .unwrap()
+        // Unwrap is OK because `rem_euclid` always yields a number in the 0-11 range
+        TONE_NAMES[usize::try_from(pitch_class).unwrap()]
     }
 
-    pub fn octave(self) -> u8 {
-        let midi_key_number = self.midi_key_number();
+    /// Get the pitch class (0-11) of a note, that is, its position in the octave starting from C.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::Note;
+    ///
+    /// // All the C notes share pitch class `0`, no matter the octave
+    /// assert_eq!(Note::C1.pitch_class(), 0);
+    /// assert_eq!(Note::C4.pitch_class(), 0);
+    ///
+    /// // A4 (concert pitch) has pitch class `9`
+    /// assert_eq!(Note::A4.pitch_class(), 9);
+    /// ```
+    pub fn pitch_class(self) -> u8 {
+        self.midi_key_number() % 12
+    }
+
+    /// Get the octave of a note in scientific pitch notation, where middle C starts octave 4.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::Note;
+    ///
+    /// assert_eq!(Note::C4.octave(), 4);
+    /// assert_eq!(Note::B3.octave(), 3);
+    ///
+    /// // The lowest MIDI octave is below zero
+    /// assert_eq!(Note::CMinus1.octave(), -1);
+    /// ```
+    pub fn octave(self) -> i8 {
+        // Unwrap is OK because Note has no more than 128 items
+        let midi_key_number = i8::try_from(self.midi_key_number()).unwrap();
 
         midi_key_number / 12 - 1
     }
@@ -380,6 +670,32 @@ impl Note {
             .map_err(|_| Error::OutOfMIDIRange)
     }
 
+    /// Get the interval between a note and another one, regardless of which of them is higher.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // From C4 up to G4 there is a perfect fifth
+    /// assert_eq!(C4.interval_to(G4), Interval::new(7));
+    ///
+    /// // From G4 down to C4 there is a perfect fifth too
+    /// assert_eq!(G4.interval_to(C4), Interval::new(7));
+    ///
+    /// // Compound intervals are kept as such
+    /// assert_eq!(C4.interval_to(E5).to_string(), "M10");
+    /// ```
+    pub fn interval_to(self, other: Note) -> Interval {
+        let (low, high) = if self <= other {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        Interval::new(high.midi_key_number() - low.midi_key_number())
+    }
+
     /// Get position (1-88) of a note in an 88-keys piano, or None if the note is not in the
     /// keyboard.
     ///
@@ -419,6 +735,152 @@ impl Note {
     }
 }
 
+/// Accidentals that can follow tone names, with the alteration in semitones that they stand for.
+/// Double accidentals come first, so that they are not taken for single ones.
+const ACCIDENTALS: [(&str, i8); 9] = [
+    ("##", 2),
+    ("x", 2),
+    ("𝄪", 2),
+    ("bb", -2),
+    ("𝄫", -2),
+    ("#", 1),
+    ("♯", 1),
+    ("b", -1),
+    ("♭", -1),
+];
+
+/// Parse a tone from the beginning of a string, made of a tone name ("A" to "G") and an optional
+/// accidental ("#", "b", their doubles "x" and "bb", or any of their Unicode glyphs), returning its distance in semitones from
+/// the C in the same octave (-2 for "Cbb" up to 13 for "Bx") together with the rest of the string.
+pub(crate) fn parse_tone(s: &str) -> Option<(i8, &str)> {
+    let mut chars = s.chars();
+    let natural = match chars.next()?.to_ascii_uppercase() {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+
+    let rest = chars.as_str();
+    let (alteration, rest) = ACCIDENTALS
+        .iter()
+        .find(|(accidental, _)| rest.starts_with(accidental))
+        .map_or((0, rest), |(accidental, alteration)| {
+            (*alteration, &rest[accidental.len()..])
+        });
+
+    Some((natural + alteration, rest))
+}
+
+/// Parse a pitch class (0-11) from the beginning of a string, made of a tone name ("A" to "G") and
+/// an optional accidental (as accepted by `parse_tone`), returning it together with the rest of the string.
+pub(crate) fn parse_pitch_class(s: &str) -> Option<(u8, &str)> {
+    let (tone, rest) = parse_tone(s)?;
+
+    // Unwrap is OK because `rem_euclid` always yields a number in the 0-11 range
+    Some((u8::try_from(tone.rem_euclid(12)).unwrap(), rest))
+}
+
+/// Parse the octave number that ends a note written in scientific pitch notation, made of ASCII
+/// digits after an optional minus sign (e.g. "4" or "-1"). Octaves too far away to fit are out of
+/// range, rather than invalid.
+pub(crate) fn parse_octave(s: &str) -> Result<i16, Error> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(Error::InvalidNoteName);
+    }
+
+    // Parsing can only fail because of overflow, as the digits have been checked already
+    let octave = s.parse::<i16>().map_err(|_| Error::OutOfMIDIRange)?;
+    if octave == 0 && digits.len() < s.len() {
+        return Err(Error::InvalidNoteName);
+    }
+
+    Ok(octave)
+}
+
+/// Support for parsing notes written in scientific pitch notation, such as "C#4", "Db3" or "A-1".
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{error::Error::*, note::Note};
+///
+/// assert_eq!("C4".parse::<Note>(), Ok(Note::C4));
+/// assert_eq!("C#4".parse::<Note>(), Ok(Note::CSharp4));
+/// assert_eq!("Db3".parse::<Note>(), Ok(Note::CSharp3));
+/// assert_eq!("A-1".parse::<Note>(), Ok(Note::AMinus1));
+///
+/// // Accidentals can cross octave boundaries
+/// assert_eq!("B#3".parse::<Note>(), Ok(Note::C4));
+/// assert_eq!("Cb4".parse::<Note>(), Ok(Note::B3));
+///
+/// // Double accidentals are written as "x" (or "##") and "bb"
+/// assert_eq!("Fx4".parse::<Note>(), Ok(Note::G4));
+/// assert_eq!("C##4".parse::<Note>(), Ok(Note::D4));
+/// assert_eq!("Bbb3".parse::<Note>(), Ok(Note::A3));
+/// assert_eq!("Cbb4".parse::<Note>(), Ok(Note::ASharp3));
+///
+/// // As are the Unicode glyphs for accidentals
+/// assert_eq!("E♭4".parse::<Note>(), Ok(Note::DSharp4));
+/// assert_eq!("F𝄪4".parse::<Note>(), Ok(Note::G4));
+///
+/// // Names are the inverse of parsing
+/// let name = Note::CMinus1.name(whatthechord::note::FlatOrSharp::Flat);
+/// assert_eq!(name, "C-1");
+/// assert_eq!(name.parse::<Note>(), Ok(Note::CMinus1));
+///
+/// // Notes beyond G9 do not exist in MIDI
+/// assert_eq!("G#9".parse::<Note>(), Err(OutOfMIDIRange));
+///
+/// // Garbage is rejected
+/// assert_eq!("H2".parse::<Note>(), Err(InvalidNoteName));
+/// assert_eq!("C".parse::<Note>(), Err(InvalidNoteName));
+/// assert_eq!("C#x".parse::<Note>(), Err(InvalidNoteName));
+/// assert_eq!("C+4".parse::<Note>(), Err(InvalidNoteName));
+/// ```
+impl FromStr for Note {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tone, octave) = parse_tone(s.trim()).ok_or(Error::InvalidNoteName)?;
+        let midi_key_number = parse_octave(octave)?
+            .checked_add(1)
+            .and_then(|octave| octave.checked_mul(12))
+            .and_then(|semitones| semitones.checked_add(i16::from(tone)))
+            .ok_or(Error::OutOfMIDIRange)?;
+
+        u8::try_from(midi_key_number)
+            .ok()
+            .filter(|midi_key_number| *midi_key_number < 128)
+            .map(Note::from)
+            .ok_or(Error::OutOfMIDIRange)
+    }
+}
+
+/// Support for subtracting notes, which gives the signed distance in semitones from the second note
+/// to the first one.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::Note::*;
+///
+/// assert_eq!(E4 - C4, 4);
+/// assert_eq!(C4 - E4, -4);
+/// assert_eq!(G9 - CMinus1, 127);
+/// ```
+impl Sub<Note> for Note {
+    type Output = i8;
+
+    fn sub(self, other: Note) -> Self::Output {
+        // MIDI key numbers are below 128, so their difference always fits in an `i8`
+        self.midi_key_number() as i8 - other.midi_key_number() as i8
+    }
+}
+
 /// Support for creating a `Note` item from its MIDI key number as `u8`.
 impl From<u8> for Note {
     fn from(value: u8) -> Self {
@@ -427,9 +889,358 @@ impl From<u8> for Note {
 }
 
 /// Flag for telling whether a note with accidentals should be called flat ("b") or sharp ("#").
+///
+/// This is kept for compatibility and convenience, while spelling notes precisely (with double
+/// accidentals, or after a key) is done through `Accidental`, `SpelledNote` and `Spelling`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum FlatOrSharp {
     /// Flat notes take the name of the natural tone above.
     Flat,
     /// Sharp notes take the name of the natural tone below.
     Sharp,
 }
+
+/// The accidentals that alter the natural tones when spelling notes.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Accidental {
+    /// Lowers a natural tone by two semitones ("bb").
+    DoubleFlat,
+    /// Lowers a natural tone by one semitone ("b").
+    Flat,
+    /// Leaves a natural tone as is.
+    Natural,
+    /// Raises a natural tone by one semitone ("#").
+    Sharp,
+    /// Raises a natural tone by two semitones ("x").
+    DoubleSharp,
+}
+
+/// Convenient methods for working with accidentals.
+impl Accidental {
+    /// Get how many semitones an accidental raises a natural tone (or lowers it, if negative).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::Accidental;
+    ///
+    /// assert_eq!(Accidental::DoubleFlat.semitones(), -2);
+    /// assert_eq!(Accidental::Sharp.semitones(), 1);
+    /// ```
+    pub fn semitones(self) -> i8 {
+        match self {
+            Accidental::DoubleFlat => -2,
+            Accidental::Flat => -1,
+            Accidental::Natural => 0,
+            Accidental::Sharp => 1,
+            Accidental::DoubleSharp => 2,
+        }
+    }
+
+    /// Get the accidental that raises a natural tone by some semitones (or lowers it, if
+    /// negative), if there is any.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::Accidental;
+    ///
+    /// assert_eq!(Accidental::from_semitones(2), Some(Accidental::DoubleSharp));
+    /// assert_eq!(Accidental::from_semitones(-3), None);
+    /// ```
+    pub fn from_semitones(semitones: i8) -> Option<Self> {
+        Some(match semitones {
+            -2 => Accidental::DoubleFlat,
+            -1 => Accidental::Flat,
+            0 => Accidental::Natural,
+            1 => Accidental::Sharp,
+            2 => Accidental::DoubleSharp,
+            _ => return None,
+        })
+    }
+
+    /// Get the symbol that an accidental is written with, as either ASCII characters or Unicode
+    /// glyphs. Naturals are not written.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::note::{Accidental, AccidentalStyle};
+    ///
+    /// assert_eq!(Accidental::DoubleFlat.symbol(AccidentalStyle::Ascii), "bb");
+    /// assert_eq!(Accidental::DoubleFlat.symbol(AccidentalStyle::Unicode), "𝄫");
+    /// assert_eq!(Accidental::Natural.symbol(AccidentalStyle::Unicode), "");
+    /// ```
+    pub fn symbol(self, style: AccidentalStyle) -> &'static str {
+        match (self, style) {
+            (Accidental::DoubleFlat, AccidentalStyle::Ascii) => "bb",
+            (Accidental::DoubleFlat, AccidentalStyle::Unicode) => "𝄫",
+            (Accidental::Flat, AccidentalStyle::Ascii) => "b",
+            (Accidental::Flat, AccidentalStyle::Unicode) => "♭",
+            (Accidental::Natural, _) => "",
+            (Accidental::Sharp, AccidentalStyle::Ascii) => "#",
+            (Accidental::Sharp, AccidentalStyle::Unicode) => "♯",
+            (Accidental::DoubleSharp, AccidentalStyle::Ascii) => "x",
+            (Accidental::DoubleSharp, AccidentalStyle::Unicode) => "𝄪",
+        }
+    }
+}
+
+/// Text representation of accidentals, as written after tone names. Naturals are not written.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::note::Accidental;
+///
+/// assert_eq!(Accidental::DoubleSharp.to_string(), "x");
+/// assert_eq!(Accidental::Flat.to_string(), "b");
+/// assert_eq!(Accidental::Natural.to_string(), "");
+/// ```
+impl Display for Accidental {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.symbol(AccidentalStyle::Ascii))
+    }
+}
+
+/// How to write accidentals in the names of notes and chords.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AccidentalStyle {
+    /// Plain ASCII characters, i.e. "#", "b", "x" and "bb".
+    Ascii,
+    /// Unicode glyphs, i.e. "♯", "♭", "𝄪" and "𝄫".
+    Unicode,
+}
+
+/// Write a symbol whose accidentals are spelled as a "b" for flats and a "#" for sharps (such as
+/// the "m7b5" quality or the "#11" tension) into any `core::fmt::Write` implementor, with its
+/// accidentals written in some style.
+pub(crate) fn write_symbol<W: Write>(
+    f: &mut W,
+    symbol: &str,
+    style: AccidentalStyle,
+) -> core::fmt::Result {
+    symbol.chars().try_for_each(|c| match c {
+        'b' => f.write_str(Accidental::Flat.symbol(style)),
+        '#' => f.write_str(Accidental::Sharp.symbol(style)),
+        c => f.write_char(c),
+    })
+}
+
+/// Support for using the accidental flag wherever an accidental is expected.
+impl From<FlatOrSharp> for Accidental {
+    fn from(accidental: FlatOrSharp) -> Self {
+        match accidental {
+            FlatOrSharp::Flat => Accidental::Flat,
+            FlatOrSharp::Sharp => Accidental::Sharp,
+        }
+    }
+}
+
+/// The MIDI messages that carry notes and the controls most commonly found next to them. Channels
+/// go from 0 to 15, and data values from 0 to 127 (except for pitch bends).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MidiMessage {
+    /// A note starts sounding.
+    NoteOn {
+        channel: u8,
+        note: Note,
+        velocity: u8,
+    },
+    /// A note stops sounding.
+    NoteOff {
+        channel: u8,
+        note: Note,
+        velocity: u8,
+    },
+    /// A controller (e.g. the sustain pedal, which is number 64) changes its value.
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    /// The instrument sound changes.
+    ProgramChange { channel: u8, program: u8 },
+    /// The pitch wheel moves, from 0 to 16383, where 8192 is the center (no bend).
+    PitchBend { channel: u8, value: u16 },
+}
+
+/// Convenient methods for working with MIDI messages.
+impl MidiMessage {
+    /// Build a message for a note starting to sound on a channel.
+    pub fn note_on(channel: u8, note: Note, velocity: u8) -> Self {
+        MidiMessage::NoteOn {
+            channel,
+            note,
+            velocity,
+        }
+    }
+
+    /// Build a message for a note stopping to sound on a channel, with no release velocity.
+    pub fn note_off(channel: u8, note: Note) -> Self {
+        MidiMessage::NoteOff {
+            channel,
+            note,
+            velocity: 0,
+        }
+    }
+
+    /// Get the channel that a message is sent on.
+    pub fn channel(&self) -> u8 {
+        match *self {
+            MidiMessage::NoteOn { channel, .. }
+            | MidiMessage::NoteOff { channel, .. }
+            | MidiMessage::ControlChange { channel, .. }
+            | MidiMessage::ProgramChange { channel, .. }
+            | MidiMessage::PitchBend { channel, .. } => channel,
+        }
+    }
+
+    /// Get the note that a message is about, if any.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(MidiMessage::note_on(0, C4, 100).note(), Some(C4));
+    /// assert_eq!(MidiMessage::ProgramChange { channel: 0, program: 5 }.note(), None);
+    /// ```
+    pub fn note(&self) -> Option<Note> {
+        match *self {
+            MidiMessage::NoteOn { note, .. } | MidiMessage::NoteOff { note, .. } => Some(note),
+            _ => None,
+        }
+    }
+
+    /// Encode a message as the bytes sent through a MIDI wire: the status byte followed by one or
+    /// two data bytes. Values out of their range are truncated.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(MidiMessage::note_on(1, C4, 100).to_bytes(), [0x91, 60, 100]);
+    /// assert_eq!(MidiMessage::PitchBend { channel: 0, value: 8192 }.to_bytes(), [0xE0, 0, 64]);
+    /// assert_eq!(MidiMessage::ProgramChange { channel: 9, program: 5 }.to_bytes(), [0xC9, 5]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let status = |kind: u8| kind | (self.channel() & 0x0F);
+
+        match *self {
+            MidiMessage::NoteOff { note, velocity, .. } => {
+                vec![status(0x80), note.midi_key_number(), velocity & 0x7F]
+            }
+            MidiMessage::NoteOn { note, velocity, .. } => {
+                vec![status(0x90), note.midi_key_number(), velocity & 0x7F]
+            }
+            MidiMessage::ControlChange {
+                controller, value, ..
+            } => vec![status(0xB0), controller & 0x7F, value & 0x7F],
+            MidiMessage::ProgramChange { program, .. } => vec![status(0xC0), program & 0x7F],
+            MidiMessage::PitchBend { value, .. } => vec![
+                status(0xE0),
+                (value & 0x7F) as u8,
+                ((value >> 7) & 0x7F) as u8,
+            ],
+        }
+    }
+}
+
+/// Support for decoding MIDI messages from the bytes sent through a MIDI wire. Note on messages
+/// with zero velocity are read as note off messages, as done by most MIDI devices.
+///
+/// # Examples
+/// ```rust
+/// use core::convert::TryFrom;
+/// use whatthechord::{error::Error, prelude::{*, Note::*}};
+///
+/// let message = MidiMessage::try_from(&[0x90, 64, 90][..]);
+/// assert_eq!(message, Ok(MidiMessage::note_on(0, E4, 90)));
+///
+/// let message = MidiMessage::try_from(&[0x93, 64, 0][..]);
+/// assert_eq!(message, Ok(MidiMessage::note_off(3, E4)));
+///
+/// let message = MidiMessage::try_from(&[0xB0, 64, 127][..]);
+/// assert_eq!(message.unwrap().note(), None);
+///
+/// // Messages of other kinds, incomplete messages and data bytes out of range are rejected
+/// assert_eq!(MidiMessage::try_from(&[0xF8][..]), Err(Error::InvalidMidiMessage));
+/// assert_eq!(MidiMessage::try_from(&[0x90, 64][..]), Err(Error::InvalidMidiMessage));
+/// assert_eq!(MidiMessage::try_from(&[0x90, 200, 1][..]), Err(Error::InvalidMidiMessage));
+/// ```
+impl TryFrom<&[u8]> for MidiMessage {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let (status, data) = bytes.split_first().ok_or(Error::InvalidMidiMessage)?;
+        if data.iter().any(|byte| *byte > 0x7F) {
+            return Err(Error::InvalidMidiMessage);
+        }
+        let channel = status & 0x0F;
+        let data = |index: usize| data.get(index).copied().ok_or(Error::InvalidMidiMessage);
+
+        Ok(match status & 0xF0 {
+            0x80 => MidiMessage::NoteOff {
+                channel,
+                note: Note::from(data(0)?),
+                velocity: data(1)?,
+            },
+            0x90 if data(1)? == 0 => MidiMessage::note_off(channel, Note::from(data(0)?)),
+            0x90 => MidiMessage::note_on(channel, Note::from(data(0)?), data(1)?),
+            0xB0 => MidiMessage::ControlChange {
+                channel,
+                controller: data(0)?,
+                value: data(1)?,
+            },
+            0xC0 => MidiMessage::ProgramChange {
+                channel,
+                program: data(0)?,
+            },
+            0xE0 => MidiMessage::PitchBend {
+                channel,
+                value: u16::from(data(0)?) | u16::from(data(1)?) << 7,
+            },
+            _ => return Err(Error::InvalidMidiMessage),
+        })
+    }
+}
+
+/// Support for getting the note out of note on and note off messages.
+///
+/// # Examples
+/// ```rust
+/// use core::convert::TryFrom;
+/// use whatthechord::{error::Error, prelude::{*, Note::*}};
+///
+/// assert_eq!(Note::try_from(MidiMessage::note_on(0, A4, 64)), Ok(A4));
+///
+/// let message = MidiMessage::ProgramChange { channel: 0, program: 1 };
+/// assert_eq!(Note::try_from(message), Err(Error::InvalidMidiMessage));
+/// ```
+impl TryFrom<MidiMessage> for Note {
+    type Error = Error;
+
+    fn try_from(message: MidiMessage) -> Result<Self, Self::Error> {
+        message.note().ok_or(Error::InvalidMidiMessage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Error::*;
+    use crate::prelude::{Note::*, *};
+
+    #[test]
+    fn test_parse_huge_octaves() {
+        assert_eq!("C2730".parse::<Note>(), Err(OutOfMIDIRange));
+        assert_eq!("C-2730".parse::<Note>(), Err(OutOfMIDIRange));
+        assert_eq!("C99999999".parse::<Note>(), Err(OutOfMIDIRange));
+        assert_eq!("C2730".parse::<SpelledNote>(), Err(OutOfMIDIRange));
+    }
+
+    #[test]
+    fn test_parse_signed_octaves() {
+        assert_eq!("C+4".parse::<Note>(), Err(InvalidNoteName));
+        assert_eq!("C-0".parse::<Note>(), Err(InvalidNoteName));
+        assert_eq!("C--1".parse::<Note>(), Err(InvalidNoteName));
+        assert_eq!("C-".parse::<Note>(), Err(InvalidNoteName));
+        assert_eq!("C-1".parse::<Note>(), Ok(CMinus1));
+        assert_eq!("C0".parse::<Note>(), Ok(C0));
+    }
+}
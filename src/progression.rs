@@ -0,0 +1,530 @@
+use crate::error::Error;
+use crate::prelude::*;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Write};
+use core::ops::Range;
+use core::str::FromStr;
+
+/// Symbols that are accepted in between the chords of a progression, for readability. Dashes are
+/// also accepted, but only on their own, as they can be part of chord symbols too (e.g. "C-7").
+const SEPARATORS: [char; 2] = ['|', '→'];
+
+/// The chords in a pattern, as their root (in half tones from the root of the first chord, with
+/// alternatives) and whether their third is major or minor.
+type Template = &'static [(&'static [u8], Third)];
+
+/// The chords that make up each of the well-known patterns.
+const PATTERNS: [(ProgressionPattern, Template); 4] = [
+    (
+        ProgressionPattern::TwelveBarBlues,
+        &[
+            (&[0], Third::Any),
+            (&[0, 5], Third::Any),
+            (&[0], Third::Any),
+            (&[0], Third::Any),
+            (&[5], Third::Any),
+            (&[5], Third::Any),
+            (&[0], Third::Any),
+            (&[0], Third::Any),
+            (&[7], Third::Any),
+            (&[5, 7], Third::Any),
+            (&[0], Third::Any),
+            (&[0, 7], Third::Any),
+        ],
+    ),
+    (
+        ProgressionPattern::TwoFiveOne,
+        &[
+            (&[0], Third::Minor),
+            (&[5], Third::Major),
+            (&[10], Third::Any),
+        ],
+    ),
+    (
+        ProgressionPattern::Axis,
+        &[
+            (&[0], Third::Major),
+            (&[7], Third::Major),
+            (&[9], Third::Minor),
+            (&[5], Third::Major),
+        ],
+    ),
+    (
+        ProgressionPattern::Andalusian,
+        &[
+            (&[0], Third::Minor),
+            (&[10], Third::Major),
+            (&[8], Third::Major),
+            (&[7], Third::Major),
+        ],
+    ),
+];
+
+/// Each of the chords in a progression, which are either written as roman numerals (and thus
+/// depend on the key that the progression is played in) or as chords on their own.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProgressionChord {
+    /// A chord written as a roman numeral, e.g. "ii7".
+    Numeral(RomanNumeral),
+    /// A chord written as a chord symbol, e.g. "Am".
+    Chord(Chord),
+}
+
+/// Convenient methods for working with the chords in a progression.
+impl ProgressionChord {
+    /// Build the chord that this stands for in a key.
+    pub fn realize(&self, key: &Key) -> Result<Chord, Error> {
+        match self {
+            ProgressionChord::Numeral(numeral) => numeral.realize(key),
+            ProgressionChord::Chord(chord) => Ok(chord.clone()),
+        }
+    }
+}
+
+/// Text representations of the chords in a progression, i.e. their roman numeral or chord
+/// symbol.
+impl Display for ProgressionChord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProgressionChord::Numeral(numeral) => numeral.fmt(f),
+            ProgressionChord::Chord(chord) => chord.fmt(f),
+        }
+    }
+}
+
+/// Support for parsing the chords in a progression, trying to read them as roman numerals first,
+/// and as chord symbols otherwise.
+impl FromStr for ProgressionChord {
+    type Err = Error;
+
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        match symbol.parse::<RomanNumeral>() {
+            Ok(numeral) => Ok(ProgressionChord::Numeral(numeral)),
+            Err(_) => Chord::from_symbol(symbol).map(ProgressionChord::Chord),
+        }
+    }
+}
+
+/// A sequence of chords, as written in lead sheets or harmony textbooks, e.g. "ii7 - V7 - Imaj7"
+/// or "Am F C G".
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ChordProgression {
+    chords: Vec<ProgressionChord>,
+    key: Option<Key>,
+}
+
+/// Convenient methods for working with chord progressions.
+impl ChordProgression {
+    /// Build a chord progression from the chords in it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// let numeral = RomanNumeral::new(1, None, TriadQuality::Major, 0).unwrap();
+    /// let progression = ChordProgression::new(vec![ProgressionChord::Numeral(numeral)]);
+    /// assert_eq!(progression.to_string(), "I");
+    /// ```
+    pub fn new(chords: Vec<ProgressionChord>) -> Self {
+        Self { chords, key: None }
+    }
+
+    /// Tell the key that a progression is played in, which is needed for changing it to another
+    /// key through `in_key`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = "Am F C G".parse::<ChordProgression>().unwrap();
+    /// assert_eq!(progression.key(), None);
+    ///
+    /// let progression = progression.with_key(Key::major(C4));
+    /// assert_eq!(progression.key(), Some(&Key::major(C4)));
+    /// ```
+    pub fn with_key(mut self, key: Key) -> Self {
+        self.key = Some(key);
+
+        self
+    }
+
+    /// Build a chord progression out of chords that are played as they are, no matter the key.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = ChordProgression::from_chords(&[
+    ///     Chord::from_notes(&[A3, C4, E4]),
+    ///     Chord::from_notes(&[F3, A3, C4]),
+    /// ]);
+    /// assert_eq!(progression.to_string(), "Am - F");
+    /// ```
+    pub fn from_chords(chords: &[Chord]) -> Self {
+        Self::new(
+            chords
+                .iter()
+                .cloned()
+                .map(ProgressionChord::Chord)
+                .collect(),
+        )
+    }
+
+    /// Get the chords in a progression.
+    pub fn chords(&self) -> &[ProgressionChord] {
+        &self.chords
+    }
+
+    /// Get the key that a progression is played in, if known.
+    pub fn key(&self) -> Option<&Key> {
+        self.key.as_ref()
+    }
+
+    /// Get the number of chords in a progression.
+    pub fn len(&self) -> usize {
+        self.chords.len()
+    }
+
+    /// Tell whether a progression has no chords at all.
+    pub fn is_empty(&self) -> bool {
+        self.chords.is_empty()
+    }
+
+    /// Build the sequence of chords that a progression stands for when played in a key. Chords
+    /// written as chord symbols are played as they are.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = "ii7 - V7 - Imaj7".parse::<ChordProgression>().unwrap();
+    ///
+    /// let chords = progression.realize(&Key::major(C4)).unwrap();
+    /// assert_eq!(chords[0], Chord::from_notes(&[D4, F4, A4, C5]));
+    ///
+    /// let names = progression
+    ///     .realize(&Key::major(ASharp3))
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(|chord| chord.name(FlatOrSharp::Flat).unwrap())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["Cm7", "F7", "BbM7"]);
+    /// ```
+    pub fn realize(&self, key: &Key) -> Result<Vec<Chord>, Error> {
+        self.chords.iter().map(|chord| chord.realize(key)).collect()
+    }
+
+    /// Transpose a progression by some number of half tones, up or down. Chords written as chord
+    /// symbols are transposed along with their slash basses, and so is the key of the progression,
+    /// if known. Roman numerals are left as they are, as they are relative to the key.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = "Am F C/E G".parse::<ChordProgression>().unwrap();
+    /// assert_eq!(progression.transposed(2).unwrap().to_string(), "Bm - G - D/F# - A");
+    ///
+    /// let progression = progression.with_key(Key::major(C4)).transposed(-3).unwrap();
+    /// assert_eq!(progression.key(), Some(&Key::major(A3)));
+    /// ```
+    pub fn transposed(&self, half_tones: i8) -> Result<Self, Error> {
+        let chords = self
+            .chords
+            .iter()
+            .map(|chord| match chord {
+                ProgressionChord::Numeral(numeral) => {
+                    Ok(ProgressionChord::Numeral(numeral.clone()))
+                }
+                ProgressionChord::Chord(chord) => {
+                    chord.transposed(half_tones).map(ProgressionChord::Chord)
+                }
+            })
+            .collect::<Result<Vec<ProgressionChord>, Error>>()?;
+        let key = self
+            .key
+            .map(|key| {
+                key.tonic()
+                    .transposed(half_tones)
+                    .map(|tonic| Key::new(tonic, key.mode()))
+            })
+            .transpose()?;
+
+        Ok(Self { chords, key })
+    }
+
+    /// Change the key of a progression, so that every chord keeps its function in the new key.
+    /// Chords written as chord symbols are transposed by the distance between the tonics of both
+    /// keys, going whichever way is shorter, while roman numerals are left as they are.
+    ///
+    /// Progressions whose key is not known are deemed to be in the new key already.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = "I - vi - IV - G7/B - C"
+    ///     .parse::<ChordProgression>()
+    ///     .unwrap()
+    ///     .with_key(Key::major(C4));
+    ///
+    /// let progression = progression.in_key(&Key::major(E4)).unwrap();
+    /// assert_eq!(progression.to_string(), "I - vi - IV - B7/D# - E");
+    /// assert_eq!(progression.key(), Some(&Key::major(E4)));
+    /// ```
+    pub fn in_key(&self, key: &Key) -> Result<Self, Error> {
+        let mut progression = match self.key {
+            Some(current) => {
+                let distance =
+                    (key.tonic().pitch_class() + 12 - current.tonic().pitch_class()) % 12;
+                let half_tones = if distance > 6 {
+                    distance as i8 - 12
+                } else {
+                    distance as i8
+                };
+
+                self.transposed(half_tones)?
+            }
+            None => self.clone(),
+        };
+        progression.key = Some(*key);
+
+        Ok(progression)
+    }
+
+    /// Write a progression as ChordPro, the text format used by song sheet tools, with every
+    /// chord in brackets followed by the lyrics that are sung along with it, if any.
+    ///
+    /// If the key of the progression is known, a `{key}` directive is written first, and chords
+    /// written as roman numerals are realized and named after the accidental of the key.
+    /// Otherwise, chords are written as they were given.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = "I - V - vi - IV"
+    ///     .parse::<ChordProgression>()
+    ///     .unwrap()
+    ///     .with_key(Key::major(F4));
+    /// let lyrics = ["Hello, ", "is it ", "me you're ", "looking for?"];
+    /// assert_eq!(
+    ///     progression.to_chordpro(&lyrics).unwrap(),
+    ///     "{key: F}\n[F]Hello, [C]is it [Dm]me you're [Bb]looking for?\n"
+    /// );
+    ///
+    /// // Chords with no lyrics are separated by spaces
+    /// let progression = "Am F C G".parse::<ChordProgression>().unwrap();
+    /// assert_eq!(progression.to_chordpro(&[]).unwrap(), "[Am] [F] [C] [G]\n");
+    /// ```
+    pub fn to_chordpro(&self, lyrics: &[&str]) -> Result<String, Error> {
+        // Writing into a `String` never fails
+        let mut chordpro = String::new();
+        if let Some(key) = self.key {
+            chordpro.push_str("{key: ");
+            key.tonic()
+                .write_pitch_name(&mut chordpro, key.accidental())
+                .unwrap();
+            if key.mode() == KeyMode::Minor {
+                chordpro.push('m');
+            }
+            chordpro.push_str("}\n");
+        }
+
+        let lyric = |index: usize| lyrics.get(index).copied().unwrap_or_default();
+        for (index, chord) in self.chords.iter().enumerate() {
+            // Chords with no lyrics in between are separated by a space
+            if index > 0 && lyric(index - 1).is_empty() {
+                chordpro.push(' ');
+            }
+
+            let name = match self.key {
+                Some(key) => chord.realize(&key)?.name(key).unwrap_or_default(),
+                None => chord.to_string(),
+            };
+            write!(chordpro, "[{}]{}", name, lyric(index)).unwrap();
+        }
+        chordpro.push('\n');
+
+        Ok(chordpro)
+    }
+
+    /// Find the stretches of a progression that follow any of the well-known patterns, when played
+    /// in a key. Each chord is taken as a bar, and patterns are found no matter the key that they
+    /// are played in (e.g. ii-V-I leading into chords other than the tonic).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = "Em7 A7 Dmaj7 G7 C".parse::<ChordProgression>().unwrap();
+    /// let patterns = progression.patterns(&Key::major(C4)).unwrap();
+    /// assert_eq!(patterns.len(), 1);
+    /// assert_eq!(patterns[0].pattern(), ProgressionPattern::TwoFiveOne);
+    /// assert_eq!(patterns[0].bars(), 0..3);
+    ///
+    /// let progression = "vi - V - IV - III".parse::<ChordProgression>().unwrap();
+    /// let patterns = progression.patterns(&Key::major(C4)).unwrap();
+    /// assert_eq!(patterns[0].pattern(), ProgressionPattern::Andalusian);
+    ///
+    /// let progression = "A7 D7 A7 A7 D7 D7 A7 A7 E7 D7 A7 E7"
+    ///     .parse::<ChordProgression>()
+    ///     .unwrap();
+    /// let patterns = progression.patterns(&Key::major(A4)).unwrap();
+    /// assert_eq!(patterns[0].pattern(), ProgressionPattern::TwelveBarBlues);
+    /// assert_eq!(patterns[0].bars(), 0..12);
+    /// ```
+    pub fn patterns(&self, key: &Key) -> Result<Vec<PatternMatch>, Error> {
+        let chords = self.realize(key)?;
+        let mut matches = Vec::new();
+
+        for start in 0..chords.len() {
+            for (pattern, template) in PATTERNS.iter() {
+                let bars = start..start + template.len();
+                if chords
+                    .get(bars.clone())
+                    .is_some_and(|chords| follows(chords, template))
+                {
+                    matches.push(PatternMatch {
+                        pattern: *pattern,
+                        bars,
+                    });
+                }
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Describe the chords in a progression as roman numerals in the context of a key, telling
+    /// apart secondary dominants, as done by `roman_numerals`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let progression = "C A7 Dm G7 C".parse::<ChordProgression>().unwrap();
+    /// let numerals = progression
+    ///     .roman_numerals(&Key::major(C4))
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(|numeral| numeral.as_ref().unwrap().to_string())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(numerals, ["I", "V7/ii", "ii", "V7", "I"]);
+    /// ```
+    pub fn roman_numerals(&self, key: &Key) -> Result<Vec<Option<RomanNumeral>>, Error> {
+        Ok(roman_numerals(&self.realize(key)?, key))
+    }
+}
+
+/// Text representations of chord progressions, with their chords separated by dashes.
+impl Display for ChordProgression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (index, chord) in self.chords.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" - ")?;
+            }
+            chord.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Support for parsing chord progressions through `str::parse`. Chords are separated by
+/// whitespace, and can optionally be separated by "-", "|" or "→" too. Each chord can be written
+/// either as a roman numeral or as a chord symbol.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{error::Error, prelude::*};
+///
+/// let progression = "Am F C G".parse::<ChordProgression>().unwrap();
+/// assert_eq!(progression.len(), 4);
+///
+/// let progression = "| ii7 | V7 | Imaj7 |".parse::<ChordProgression>().unwrap();
+/// assert_eq!(progression.to_string(), "ii7 - V7 - IM7");
+///
+/// assert_eq!("Am Foo".parse::<ChordProgression>(), Err(Error::InvalidChordSymbol));
+/// ```
+impl FromStr for ChordProgression {
+    type Err = Error;
+
+    fn from_str(progression: &str) -> Result<Self, Self::Err> {
+        progression
+            .split_whitespace()
+            .flat_map(|word| word.split(SEPARATORS))
+            .filter(|symbol| !symbol.is_empty() && *symbol != "-")
+            .map(str::parse)
+            .collect::<Result<Vec<ProgressionChord>, Error>>()
+            .map(Self::new)
+    }
+}
+
+/// Well-known chord progressions that show up in many songs.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ProgressionPattern {
+    /// The 12-bar blues: I-I-I-I-IV-IV-I-I-V-IV-I-I, also with the IV in the second bar or the V
+    /// in the tenth and twelfth bars.
+    TwelveBarBlues,
+    /// The ii-V-I, as found everywhere in jazz, also in minor (iiø-V-i).
+    TwoFiveOne,
+    /// The I-V-vi-IV, as found in countless pop songs.
+    Axis,
+    /// The Andalusian cadence: i-bVII-bVI-V.
+    Andalusian,
+}
+
+/// A stretch of a progression that follows one of the well-known patterns.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatternMatch {
+    pattern: ProgressionPattern,
+    bars: Range<usize>,
+}
+
+/// Convenient methods for working with the patterns found in progressions.
+impl PatternMatch {
+    /// Get the pattern that was found.
+    pub fn pattern(&self) -> ProgressionPattern {
+        self.pattern
+    }
+
+    /// Get the positions of the chords in the progression that follow the pattern, taking each
+    /// chord as a bar.
+    pub fn bars(&self) -> Range<usize> {
+        self.bars.clone()
+    }
+}
+
+/// Whether the third of the chords in a pattern is major or minor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Third {
+    Major,
+    Minor,
+    Any,
+}
+
+/// Tell whether a sequence of chords follows a pattern, in any key.
+fn follows(chords: &[Chord], pattern: Template) -> bool {
+    let first = match chords.first().and_then(Chord::root) {
+        Some(root) => root.pitch_class(),
+        None => return false,
+    };
+
+    chords.len() == pattern.len()
+        && chords.iter().zip(pattern).all(|(chord, (roots, third))| {
+            let root = match chord.root() {
+                Some(root) => root.pitch_class(),
+                None => return false,
+            };
+            let has_third = |half_tones: u8| chord.contains_pitch_class((root + half_tones) % 12);
+            let third = match third {
+                Third::Major => has_third(4),
+                Third::Minor => has_third(3),
+                Third::Any => true,
+            };
+
+            third && roots.contains(&((root + 12 - first) % 12))
+        })
+}
@@ -0,0 +1,697 @@
+use crate::error::Error;
+use crate::prelude::*;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Write};
+use core::str::FromStr;
+
+/// Upper case roman numerals for each of the seven degrees of a key.
+const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+/// Figured bass symbols telling the inversion of triads, from root position onwards.
+const TRIAD_FIGURES: [&str; 3] = ["", "6", "64"];
+
+/// Figured bass symbols telling the inversion of seventh chords, from root position onwards.
+const SEVENTH_FIGURES: [&str; 4] = ["7", "65", "43", "42"];
+
+/// Every chord type that can be written as a roman numeral.
+const CHORD_TYPES: [ChordType; 12] = [
+    ChordType::Triad(TriadQuality::Major),
+    ChordType::Triad(TriadQuality::Minor),
+    ChordType::Triad(TriadQuality::Diminished),
+    ChordType::Triad(TriadQuality::Augmented),
+    ChordType::Tetrad(TetradQuality::SeventhDominant),
+    ChordType::Tetrad(TetradQuality::SeventhMajor),
+    ChordType::Tetrad(TetradQuality::SeventhMinor),
+    ChordType::Tetrad(TetradQuality::SeventhMinorMajor),
+    ChordType::Tetrad(TetradQuality::SeventhHalfDiminished),
+    ChordType::Tetrad(TetradQuality::SeventhDiminished),
+    ChordType::Tetrad(TetradQuality::SeventhAugmented),
+    ChordType::Tetrad(TetradQuality::SeventhAugmentedMajor),
+];
+
+/// The description of a chord in terms of the degree of a key that it is built upon, as used in
+/// harmonic analysis, e.g. "ii7", "V65" or "bVII".
+///
+/// Degrees are counted on the scale of the key itself, so chords built on notes that do not belong
+/// to the key (such as the subtonic in major keys, or the leading tone in minor keys) take a flat
+/// or sharp.
+///
+/// Roman numerals can also be applied to another one (e.g. "V7/ii", the dominant of the
+/// supertonic), in which case their degree is counted on the key that the other one tonicizes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RomanNumeral {
+    degree: u8,
+    accidental: Option<FlatOrSharp>,
+    chord_type: ChordType,
+    inversion: u8,
+    secondary: Option<Box<RomanNumeral>>,
+}
+
+/// Convenient methods for working with roman numerals.
+impl RomanNumeral {
+    /// Build a roman numeral from the degree of the key (1-7) that the chord is built upon,
+    /// whether that degree is flattened or sharpened, the type of the chord and its inversion (0
+    /// for root position, 1 for first inversion, etc).
+    ///
+    /// Nothing is returned if the degree is not between 1 and 7, if the chord type cannot be
+    /// written as a roman numeral, or if it has no such inversion.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// let numeral = RomanNumeral::new(5, None, TetradQuality::SeventhDominant, 1).unwrap();
+    /// assert_eq!(numeral.to_string(), "V65");
+    ///
+    /// let numeral = RomanNumeral::new(7, Some(FlatOrSharp::Flat), TriadQuality::Major, 0);
+    /// assert_eq!(numeral.unwrap().to_string(), "bVII");
+    ///
+    /// assert_eq!(RomanNumeral::new(8, None, TriadQuality::Major, 0), None);
+    /// assert_eq!(RomanNumeral::new(1, None, TriadQuality::Major, 3), None);
+    /// ```
+    pub fn new(
+        degree: u8,
+        accidental: Option<FlatOrSharp>,
+        chord_type: impl Into<ChordType>,
+        inversion: u8,
+    ) -> Option<Self> {
+        let chord_type = chord_type.into();
+        let (_, _, figures) = notation(&chord_type)?;
+        if !(1..=7).contains(&degree) || usize::from(inversion) >= figures.len() {
+            return None;
+        }
+
+        Some(Self {
+            degree,
+            accidental,
+            chord_type,
+            inversion,
+            secondary: None,
+        })
+    }
+
+    /// Apply a roman numeral to another one, so that its degree is counted on the key tonicized by
+    /// the other one, which becomes major or minor depending on its quality.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// let dominant = RomanNumeral::new(5, None, TetradQuality::SeventhDominant, 0).unwrap();
+    /// let supertonic = RomanNumeral::new(2, None, TriadQuality::Minor, 0).unwrap();
+    /// assert_eq!(dominant.applied_to(supertonic).to_string(), "V7/ii");
+    /// ```
+    pub fn applied_to(mut self, target: RomanNumeral) -> Self {
+        self.secondary = Some(Box::new(target));
+
+        self
+    }
+
+    /// Get the degree of the key (1-7) that the chord is built upon.
+    pub fn degree(&self) -> u8 {
+        self.degree
+    }
+
+    /// Get whether the degree that the chord is built upon is flattened or sharpened.
+    pub fn accidental(&self) -> Option<FlatOrSharp> {
+        self.accidental
+    }
+
+    /// Get the type of the chord.
+    pub fn chord_type(&self) -> &ChordType {
+        &self.chord_type
+    }
+
+    /// Get the inversion of the chord: 0 for root position, 1 for first inversion and so on.
+    pub fn inversion(&self) -> u8 {
+        self.inversion
+    }
+
+    /// Get the roman numeral that this one is applied to, if any.
+    pub fn secondary(&self) -> Option<&RomanNumeral> {
+        self.secondary.as_deref()
+    }
+
+    /// Build the chord that a roman numeral stands for in a key, in the inversion told by the
+    /// roman numeral. The root position of the chord starts within the octave above the tonic of
+    /// the key.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Key::major(C4);
+    ///
+    /// let numeral = "ii7".parse::<RomanNumeral>().unwrap();
+    /// assert_eq!(numeral.realize(&c_major), Ok(Chord::from_notes(&[D4, F4, A4, C5])));
+    ///
+    /// // The dominant of the supertonic, in first inversion
+    /// let numeral = "V65/ii".parse::<RomanNumeral>().unwrap();
+    /// let chord = numeral.realize(&c_major).unwrap();
+    /// assert_eq!(chord.name(FlatOrSharp::Sharp).unwrap(), "A7/C#");
+    ///
+    /// // The subtonic in a major key
+    /// let numeral = "bVII".parse::<RomanNumeral>().unwrap();
+    /// assert_eq!(numeral.realize(&c_major), Ok(Chord::from_notes(&[ASharp4, D5, F5])));
+    /// ```
+    pub fn realize(&self, key: &Key) -> Result<Chord, Error> {
+        let root = self.root_in(&self.key_in(key)?)?;
+
+        let mut chord = Chord::build(root, self.chord_type.clone())?;
+        for _ in 0..self.inversion {
+            chord = chord.invert_up()?;
+        }
+
+        Ok(chord)
+    }
+
+    /// Tell whether the chord that a roman numeral stands for is borrowed from the parallel key
+    /// (also known as modal mixture), e.g. "iv" or "bVI" in a major key, and if so, get that key.
+    ///
+    /// A chord is deemed borrowed if it does not belong to the key, but belongs to its parallel
+    /// one. The leading tone is taken as part of minor keys, so that their major dominant is not
+    /// deemed borrowed. Roman numerals that are applied to another one are never borrowed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let c_major = Key::major(C4);
+    ///
+    /// let numeral = "bVI".parse::<RomanNumeral>().unwrap();
+    /// assert_eq!(numeral.borrowed_from(&c_major), Some(Key::minor(C4)));
+    ///
+    /// let numeral = Chord::from_notes(&[F3, GSharp3, C4]).roman_numeral(&c_major).unwrap();
+    /// assert_eq!(numeral.to_string(), "iv");
+    /// assert_eq!(numeral.borrowed_from(&c_major), Some(Key::minor(C4)));
+    ///
+    /// // Diatonic chords are not borrowed, and neither is the dominant of minor keys
+    /// let numeral = "IV".parse::<RomanNumeral>().unwrap();
+    /// assert_eq!(numeral.borrowed_from(&c_major), None);
+    /// let numeral = "V7".parse::<RomanNumeral>().unwrap();
+    /// assert_eq!(numeral.borrowed_from(&Key::minor(A3)), None);
+    ///
+    /// // A Picardy third ends a minor key on a major tonic
+    /// let numeral = "I".parse::<RomanNumeral>().unwrap();
+    /// assert_eq!(numeral.borrowed_from(&Key::minor(A3)), Some(Key::major(A3)));
+    /// ```
+    pub fn borrowed_from(&self, key: &Key) -> Option<Key> {
+        if self.secondary.is_some() {
+            return None;
+        }
+
+        let chord = self.realize(key).ok()?;
+        let parallel = key.parallel();
+
+        if !fits_in(&chord, key) && fits_in(&chord, &parallel) {
+            Some(parallel)
+        } else {
+            None
+        }
+    }
+
+    /// Find the root of the chord that a roman numeral stands for in a key, ignoring any roman
+    /// numeral that it is applied to.
+    fn root_in(&self, key: &Key) -> Result<Note, Error> {
+        let pitch_class = key.pitch_classes()[usize::from(self.degree - 1)];
+        let pitch_class = match self.accidental {
+            Some(FlatOrSharp::Flat) => pitch_class + 11,
+            Some(FlatOrSharp::Sharp) => pitch_class + 1,
+            None => pitch_class,
+        };
+        let offset = (pitch_class + 12 - key.tonic().pitch_class()) % 12;
+
+        key.tonic().transposed(offset as i8)
+    }
+
+    /// Find the key on which the degree of a roman numeral is counted, going through every roman
+    /// numeral that it is applied to.
+    fn key_in(&self, key: &Key) -> Result<Key, Error> {
+        match &self.secondary {
+            Some(target) => {
+                let (is_upper, _, _) =
+                    notation(&target.chord_type).ok_or(Error::UnbuildableChord)?;
+                let mode = if is_upper {
+                    KeyMode::Major
+                } else {
+                    KeyMode::Minor
+                };
+
+                Ok(Key::new(target.root_in(&target.key_in(key)?)?, mode))
+            }
+            None => Ok(*key),
+        }
+    }
+}
+
+/// Text representations of roman numerals. Chords with a minor third are written in lower case,
+/// followed by "°" if diminished, "ø" if half diminished or "+" if augmented, and "M" for major
+/// sevenths. Inversions are written as figured bass symbols ("6" and "64" for triads, "65", "43"
+/// and "42" for seventh chords).
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// let numeral = RomanNumeral::new(2, None, TetradQuality::SeventhMinor, 0).unwrap();
+/// assert_eq!(format!("{}", numeral), "ii7");
+///
+/// let numeral = RomanNumeral::new(7, None, TetradQuality::SeventhHalfDiminished, 2).unwrap();
+/// assert_eq!(format!("{}", numeral), "viiø43");
+///
+/// let numeral = RomanNumeral::new(1, None, TriadQuality::Major, 2).unwrap();
+/// assert_eq!(format!("{}", numeral), "I64");
+/// ```
+impl Display for RomanNumeral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // Roman numerals are only ever built for chord types that have a notation
+        let (is_upper, marker, figures) = notation(&self.chord_type).ok_or(core::fmt::Error)?;
+
+        match self.accidental {
+            Some(FlatOrSharp::Flat) => f.write_char('b')?,
+            Some(FlatOrSharp::Sharp) => f.write_char('#')?,
+            None => (),
+        }
+        for numeral in NUMERALS[usize::from(self.degree - 1)].chars() {
+            f.write_char(if is_upper {
+                numeral
+            } else {
+                numeral.to_ascii_lowercase()
+            })?;
+        }
+
+        f.write_str(marker)?;
+        f.write_str(figures[usize::from(self.inversion)])?;
+
+        match &self.secondary {
+            Some(target) => write!(f, "/{}", target),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Support for parsing roman numerals through `str::parse`, as written by their `Display`
+/// implementation. Diminished chords can also be written with a plain "o", e.g. "viio7", and major
+/// sevenths with "maj", e.g. "Imaj7".
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{error::Error, prelude::*};
+///
+/// let numeral = "V7/ii".parse::<RomanNumeral>().unwrap();
+/// assert_eq!(numeral.degree(), 5);
+/// assert_eq!(numeral.chord_type(), &ChordType::Tetrad(TetradQuality::SeventhDominant));
+/// assert_eq!(numeral.secondary().unwrap().degree(), 2);
+///
+/// let numeral = "bVI6".parse::<RomanNumeral>().unwrap();
+/// assert_eq!(numeral.accidental(), Some(FlatOrSharp::Flat));
+/// assert_eq!(numeral.inversion(), 1);
+///
+/// assert_eq!("viio7".parse(), "vii°7".parse::<RomanNumeral>());
+/// assert_eq!("Imaj7".parse(), "IM7".parse::<RomanNumeral>());
+///
+/// // Numerals are either upper or lower case, and only go up to VII
+/// assert_eq!("Vi".parse::<RomanNumeral>(), Err(Error::InvalidRomanNumeral));
+/// assert_eq!("VIII".parse::<RomanNumeral>(), Err(Error::InvalidRomanNumeral));
+/// ```
+impl FromStr for RomanNumeral {
+    type Err = Error;
+
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        let symbol = symbol.trim();
+        let (own, target) = match symbol.split_once('/') {
+            Some((own, target)) => (own, Some(target.parse::<RomanNumeral>()?)),
+            None => (symbol, None),
+        };
+
+        let (accidental, own) = match own.chars().next() {
+            Some('b') => (Some(FlatOrSharp::Flat), &own[1..]),
+            Some('#') => (Some(FlatOrSharp::Sharp), &own[1..]),
+            _ => (None, own),
+        };
+
+        // The numeral itself, all in the same case
+        let length = own
+            .find(|c| !matches!(c, 'I' | 'V' | 'i' | 'v'))
+            .unwrap_or(own.len());
+        let (numeral, rest) = own.split_at(length);
+        let is_upper = numeral.chars().all(|c| c.is_ascii_uppercase());
+        let is_lower = numeral.chars().all(|c| c.is_ascii_lowercase());
+        let degree = NUMERALS
+            .iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(numeral))
+            .filter(|_| is_upper || is_lower)
+            .ok_or(Error::InvalidRomanNumeral)?;
+
+        // The quality marker, followed by the figures telling the inversion
+        let (marker, figure) = rest.split_at(
+            rest.find(|c: char| c.is_ascii_digit())
+                .unwrap_or(rest.len()),
+        );
+        let marker = match marker {
+            "o" => "°",
+            "maj" => "M",
+            "+maj" => "+M",
+            marker => marker,
+        };
+        let (figures, inversion): (&[&str], usize) =
+            match TRIAD_FIGURES.iter().position(|f| *f == figure) {
+                Some(inversion) => (&TRIAD_FIGURES, inversion),
+                None => SEVENTH_FIGURES
+                    .iter()
+                    .position(|f| *f == figure)
+                    .map(|inversion| (&SEVENTH_FIGURES[..], inversion))
+                    .ok_or(Error::InvalidRomanNumeral)?,
+            };
+
+        let chord_type = CHORD_TYPES
+            .iter()
+            .find(|chord_type| notation(chord_type) == Some((is_upper, marker, figures)))
+            .ok_or(Error::InvalidRomanNumeral)?;
+
+        let numeral = RomanNumeral::new(
+            degree as u8 + 1,
+            accidental,
+            chord_type.clone(),
+            inversion as u8,
+        )
+        .ok_or(Error::InvalidRomanNumeral)?;
+
+        Ok(match target {
+            Some(target) => numeral.applied_to(target),
+            None => numeral,
+        })
+    }
+}
+
+/// Chromatic chords that are not described by the degree that they are built upon, but by their
+/// own labels: the Neapolitan sixth and the three kinds of augmented sixth chords.
+///
+/// These are spelled as other chords (e.g. the German sixth of C is enharmonic to Ab7), so they
+/// can only be told apart in the context of a key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChromaticChord {
+    /// A major triad built on the lowered supertonic, usually in first inversion, e.g. F Ab Db in
+    /// C.
+    Neapolitan,
+    /// The lowered submediant in the bass, with the tonic and the raised subdominant above, e.g.
+    /// Ab C F# in C.
+    ItalianSixth,
+    /// An Italian sixth with the supertonic added, e.g. Ab C D F# in C.
+    FrenchSixth,
+    /// An Italian sixth with the lowered mediant added, e.g. Ab C Eb F# in C.
+    GermanSixth,
+}
+
+/// Convenient methods for working with chromatic chords.
+impl ChromaticChord {
+    /// Tell which chromatic chord, if any, a chord stands for in a key.
+    pub(crate) fn recognize(chord: &Chord, key: &Key) -> Option<Self> {
+        let bass = chord.bass()?.pitch_class();
+        let tonic = key.tonic().pitch_class();
+        let mask = chord.notes().iter().fold(0u16, |mask, note| {
+            mask | 1 << ((note.pitch_class() + 12 - tonic) % 12)
+        });
+        let bass = (bass + 12 - tonic) % 12;
+
+        match (mask, bass) {
+            (0b0001_0010_0010, 1 | 5 | 8) => Some(Self::Neapolitan),
+            (0b0001_0100_0001, 8) => Some(Self::ItalianSixth),
+            (0b0001_0100_0101, 8) => Some(Self::FrenchSixth),
+            (0b0001_0100_1001, 8) => Some(Self::GermanSixth),
+            _ => None,
+        }
+    }
+}
+
+/// Text representations of chromatic chords, as used in harmonic analysis.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// assert_eq!(ChromaticChord::Neapolitan.to_string(), "N6");
+/// assert_eq!(ChromaticChord::GermanSixth.to_string(), "Ger+6");
+/// ```
+impl Display for ChromaticChord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            ChromaticChord::Neapolitan => "N6",
+            ChromaticChord::ItalianSixth => "It+6",
+            ChromaticChord::FrenchSixth => "Fr+6",
+            ChromaticChord::GermanSixth => "Ger+6",
+        })
+    }
+}
+
+/// The coarse role that a chord plays in a key, as told by functional harmony.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HarmonicFunction {
+    /// The chord built on the tonic, which gives a sense of rest, e.g. I.
+    Tonic,
+    /// Chords that share most of their notes with the tonic chord and can stand in for it, e.g. vi
+    /// and iii in major keys, or VI and III in minor keys.
+    TonicSubstitute,
+    /// Chords that lead away from the tonic towards the dominant, e.g. ii and IV, but also the
+    /// Neapolitan and augmented sixth chords.
+    Subdominant,
+    /// Chords that build up tension that resolves into the tonic, e.g. V and vii°.
+    Dominant,
+}
+
+/// Convenient methods for working with harmonic functions.
+impl HarmonicFunction {
+    /// Tell the harmonic function of a chord in a key, if any.
+    pub(crate) fn of(chord: &Chord, key: &Key) -> Option<Self> {
+        if chord.chromatic_chord(key).is_some() {
+            return Some(Self::Subdominant);
+        }
+
+        let numeral = chord.roman_numeral(key)?;
+        if !fits_in(chord, key) && numeral.borrowed_from(key).is_none() {
+            return None;
+        }
+
+        let root = chord.root()?.pitch_class();
+        match (root + 12 - key.tonic().pitch_class()) % 12 {
+            0 => Some(Self::Tonic),
+            3 | 4 | 8 | 9 => Some(Self::TonicSubstitute),
+            1 | 2 | 5 | 10 => Some(Self::Subdominant),
+            7 | 11 => Some(Self::Dominant),
+            _ => None,
+        }
+    }
+}
+
+/// The kinds of cadences, i.e. the chord progressions that close a musical phrase.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Cadence {
+    /// A dominant chord resolving into the tonic, e.g. V-I.
+    Authentic,
+    /// The subdominant resolving into the tonic, e.g. IV-I.
+    Plagal,
+    /// Any chord moving into the dominant, e.g. ii-V, which leaves the phrase open.
+    Half,
+    /// A dominant chord resolving into the submediant instead of the tonic, e.g. V-vi.
+    Deceptive,
+}
+
+/// Tell which kind of cadence, if any, two chords make in a key when the second one follows the
+/// first one.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let c_major = Key::major(C4);
+/// let tonic = Chord::from_notes(&[C4, E4, G4]);
+/// let subdominant = Chord::from_notes(&[F3, A3, C4]);
+/// let dominant = Chord::from_notes(&[G3, B3, D4, F4]);
+/// let submediant = Chord::from_notes(&[A3, C4, E4]);
+///
+/// assert_eq!(cadence_between(&dominant, &tonic, &c_major), Some(Cadence::Authentic));
+/// assert_eq!(cadence_between(&subdominant, &tonic, &c_major), Some(Cadence::Plagal));
+/// assert_eq!(cadence_between(&subdominant, &dominant, &c_major), Some(Cadence::Half));
+/// assert_eq!(cadence_between(&dominant, &submediant, &c_major), Some(Cadence::Deceptive));
+/// assert_eq!(cadence_between(&tonic, &subdominant, &c_major), None);
+///
+/// // The leading tone chord is a dominant too
+/// let leading_tone = Chord::from_notes(&[B3, D4, F4]);
+/// assert_eq!(cadence_between(&leading_tone, &tonic, &c_major), Some(Cadence::Authentic));
+/// ```
+pub fn cadence_between(first: &Chord, second: &Chord, key: &Key) -> Option<Cadence> {
+    let tonic = key.tonic().pitch_class();
+    let degree = |chord: &Chord| {
+        let function = chord.function(key)?;
+        let root = chord.root()?.pitch_class();
+
+        Some((function, (root + 12 - tonic) % 12))
+    };
+    let (first, second) = (degree(first)?, degree(second)?);
+    let submediant = match key.mode() {
+        KeyMode::Major => 9,
+        KeyMode::Minor => 8,
+    };
+
+    match (first, second) {
+        ((HarmonicFunction::Dominant, _), (HarmonicFunction::Tonic, _)) => Some(Cadence::Authentic),
+        ((HarmonicFunction::Dominant, _), (HarmonicFunction::TonicSubstitute, root))
+            if root == submediant =>
+        {
+            Some(Cadence::Deceptive)
+        }
+        ((HarmonicFunction::Subdominant, 5), (HarmonicFunction::Tonic, _)) => Some(Cadence::Plagal),
+        ((first, _), (HarmonicFunction::Dominant, 7)) if first != HarmonicFunction::Dominant => {
+            Some(Cadence::Half)
+        }
+        _ => None,
+    }
+}
+
+/// Get how a chord type is written as a roman numeral: whether in upper case, the symbol for its
+/// quality, and the figures for each of its inversions. Nothing is returned for chord types that
+/// have no such notation.
+fn notation(chord_type: &ChordType) -> Option<(bool, &'static str, &'static [&'static str])> {
+    use TetradQuality::*;
+
+    Some(match chord_type {
+        ChordType::Triad(TriadQuality::Major) => (true, "", &TRIAD_FIGURES),
+        ChordType::Triad(TriadQuality::Minor) => (false, "", &TRIAD_FIGURES),
+        ChordType::Triad(TriadQuality::Diminished) => (false, "°", &TRIAD_FIGURES),
+        ChordType::Triad(TriadQuality::Augmented) => (true, "+", &TRIAD_FIGURES),
+        ChordType::Tetrad(SeventhDominant) => (true, "", &SEVENTH_FIGURES),
+        ChordType::Tetrad(SeventhMajor) => (true, "M", &SEVENTH_FIGURES),
+        ChordType::Tetrad(SeventhMinor) => (false, "", &SEVENTH_FIGURES),
+        ChordType::Tetrad(SeventhMinorMajor) => (false, "M", &SEVENTH_FIGURES),
+        ChordType::Tetrad(SeventhHalfDiminished) => (false, "ø", &SEVENTH_FIGURES),
+        ChordType::Tetrad(SeventhDiminished) => (false, "°", &SEVENTH_FIGURES),
+        ChordType::Tetrad(SeventhAugmented) => (true, "+", &SEVENTH_FIGURES),
+        ChordType::Tetrad(SeventhAugmentedMajor) => (true, "+M", &SEVENTH_FIGURES),
+        _ => return None,
+    })
+}
+
+/// Describe a sequence of chords as roman numerals in the context of a key, as done one by one by
+/// `Chord::roman_numeral`, but looking ahead at the chord that comes next to tell when a chord
+/// works as a secondary dominant. Those are chords that do not belong to the key, are a major
+/// triad or a dominant seventh, and resolve a fifth down into a major or minor chord other than
+/// the tonic. Secondary dominants are written as applied to the chord that they resolve into, e.g.
+/// "V7/ii".
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let progression = [
+///     Chord::from_notes(&[C4, E4, G4]),
+///     Chord::from_notes(&[A3, CSharp4, E4, G4]),
+///     Chord::from_notes(&[D4, F4, A4]),
+///     Chord::from_notes(&[G3, B3, D4, F4]),
+///     Chord::from_notes(&[C4, E4, G4]),
+/// ];
+/// let numerals = roman_numerals(&progression, &Key::major(C4))
+///     .iter()
+///     .map(|numeral| numeral.as_ref().unwrap().to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(numerals, ["I", "V7/ii", "ii", "V7", "I"]);
+/// ```
+pub fn roman_numerals(chords: &[Chord], key: &Key) -> Vec<Option<RomanNumeral>> {
+    let mut numerals = chords
+        .iter()
+        .map(|chord| chord.roman_numeral(key))
+        .collect::<Vec<Option<RomanNumeral>>>();
+
+    for (index, chord) in chords.iter().enumerate() {
+        let next = match (chords.get(index + 1), numerals.get(index + 1)) {
+            (Some(next), Some(Some(numeral))) => (next, numeral),
+            _ => continue,
+        };
+        if let Some(secondary) = secondary_dominant(chord, next, key) {
+            numerals[index] = Some(secondary);
+        }
+    }
+
+    numerals
+}
+
+/// Tell whether a chord works as the secondary dominant of the chord that comes next, and if so,
+/// get the roman numeral for it.
+fn secondary_dominant(
+    chord: &Chord,
+    (next, next_numeral): (&Chord, &RomanNumeral),
+    key: &Key,
+) -> Option<RomanNumeral> {
+    let is_dominant = matches!(
+        chord.chord_type(),
+        ChordType::Triad(TriadQuality::Major) | ChordType::Tetrad(TetradQuality::SeventhDominant)
+    );
+    let root = chord.root()?.pitch_class();
+    let resolves = (root + 5) % 12 == next.root()?.pitch_class();
+    let is_tonic = next_numeral.degree == 1 && next_numeral.accidental.is_none();
+    if !is_dominant || !resolves || is_tonic || chord.is_diatonic(key) {
+        return None;
+    }
+
+    // The chord being tonicized, as a plain major or minor triad
+    let (is_upper, marker, _) = notation(&next_numeral.chord_type)?;
+    let target_quality = match (is_upper, marker) {
+        (true, _) => TriadQuality::Major,
+        (false, "" | "M") => TriadQuality::Minor,
+        _ => return None,
+    };
+    let target = RomanNumeral::new(
+        next_numeral.degree,
+        next_numeral.accidental,
+        target_quality,
+        0,
+    )?;
+
+    RomanNumeral::new(
+        5,
+        None,
+        chord.chord_type().clone(),
+        chord.inversion().unwrap_or_default(),
+    )
+    .map(|numeral| numeral.applied_to(target))
+}
+
+/// Tell whether all the notes in a chord belong to a key, taking the leading tone as part of minor
+/// keys.
+fn fits_in(chord: &Chord, key: &Key) -> bool {
+    let mut pitch_classes = key.pitch_classes().to_vec();
+    if key.mode() == KeyMode::Minor {
+        pitch_classes.push((key.tonic().pitch_class() + 11) % 12);
+    }
+
+    chord
+        .notes()
+        .iter()
+        .all(|note| pitch_classes.contains(&note.pitch_class()))
+}
+
+/// Find out the degree (1-7) of a key that a pitch class is built upon, together with whether it
+/// needs to be flattened or sharpened. Chromatic pitch classes are written as the flat of the
+/// degree above, except for those right below the dominant or the tonic (and, in minor keys, the
+/// subtonic), which are written as the sharp of the degree below (e.g. #IV, or #vii° in minor).
+pub(crate) fn degree_of(key: &Key, pitch_class: u8) -> (u8, Option<FlatOrSharp>) {
+    let pitch_classes = key.pitch_classes();
+    if let Some(position) = pitch_classes.iter().position(|pc| *pc == pitch_class) {
+        return (position as u8 + 1, None);
+    }
+
+    let above = pitch_classes
+        .iter()
+        .position(|pc| *pc == (pitch_class + 1) % 12)
+        .unwrap_or_default();
+    let sharpens = match key.mode() {
+        KeyMode::Major => above == 4,
+        KeyMode::Minor => matches!(above, 0 | 4 | 6),
+    };
+
+    if sharpens {
+        ((above + 6) as u8 % 7 + 1, Some(FlatOrSharp::Sharp))
+    } else {
+        (above as u8 + 1, Some(FlatOrSharp::Flat))
+    }
+}
@@ -0,0 +1,363 @@
+use crate::prelude::*;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// The kinds of scales that rotations of other scales can be identified as. The major and natural
+/// minor scales are left out, as their rotations are named after the church modes instead.
+const ROTATABLE_KINDS: [ScaleKind; 17] = [
+    ScaleKind::Ionian,
+    ScaleKind::Dorian,
+    ScaleKind::Phrygian,
+    ScaleKind::Lydian,
+    ScaleKind::Mixolydian,
+    ScaleKind::Aeolian,
+    ScaleKind::Locrian,
+    ScaleKind::HarmonicMinor,
+    ScaleKind::MelodicMinor,
+    ScaleKind::MajorPentatonic,
+    ScaleKind::MinorPentatonic,
+    ScaleKind::Blues,
+    ScaleKind::WholeTone,
+    ScaleKind::LydianDominant,
+    ScaleKind::Altered,
+    ScaleKind::HalfWholeDiminished,
+    ScaleKind::WholeHalfDiminished,
+];
+
+/// The kinds of scales that are usually played over chords when improvising, as told by
+/// chord-scale theory.
+pub(crate) const CHORD_SCALE_KINDS: [ScaleKind; 17] = [
+    ScaleKind::Ionian,
+    ScaleKind::Dorian,
+    ScaleKind::Phrygian,
+    ScaleKind::Lydian,
+    ScaleKind::Mixolydian,
+    ScaleKind::Aeolian,
+    ScaleKind::Locrian,
+    ScaleKind::HarmonicMinor,
+    ScaleKind::MelodicMinor,
+    ScaleKind::LydianDominant,
+    ScaleKind::Altered,
+    ScaleKind::HalfWholeDiminished,
+    ScaleKind::WholeHalfDiminished,
+    ScaleKind::WholeTone,
+    ScaleKind::MajorPentatonic,
+    ScaleKind::MinorPentatonic,
+    ScaleKind::Blues,
+];
+
+/// A sequence of notes going up from a tonic note, following the pattern of some kind of scale.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Scale {
+    tonic: Note,
+    kind: ScaleKind,
+}
+
+/// Convenient methods for working with scales.
+impl Scale {
+    /// Build a scale from its tonic and kind.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let scale = Scale::new(A3, ScaleKind::HarmonicMinor);
+    /// assert_eq!(scale.tonic(), A3);
+    /// assert_eq!(scale.kind(), ScaleKind::HarmonicMinor);
+    /// ```
+    pub fn new(tonic: Note, kind: ScaleKind) -> Self {
+        Self { tonic, kind }
+    }
+
+    /// Get the tonic of a scale.
+    pub fn tonic(&self) -> Note {
+        self.tonic
+    }
+
+    /// Get the kind of a scale.
+    pub fn kind(&self) -> ScaleKind {
+        self.kind
+    }
+
+    /// Get the notes in a scale, going up one octave from the tonic (not included twice). Notes
+    /// that would fall outside of the MIDI range are left out.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let scale = Scale::new(C4, ScaleKind::Major);
+    /// assert_eq!(scale.notes(), vec![C4, D4, E4, F4, G4, A4, B4]);
+    ///
+    /// let scale = Scale::new(A3, ScaleKind::MinorPentatonic);
+    /// assert_eq!(scale.notes(), vec![A3, C4, D4, E4, G4]);
+    /// ```
+    pub fn notes(&self) -> Vec<Note> {
+        self.kind
+            .semitones_from_tonic()
+            .iter()
+            .filter_map(|semitones| self.tonic.transposed(*semitones as i8).ok())
+            .collect()
+    }
+
+    /// Get the pitch classes (0-11, where 0 is C) of the notes in a scale, starting from the
+    /// tonic.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let scale = Scale::new(D4, ScaleKind::WholeTone);
+    /// assert_eq!(scale.pitch_classes(), vec![2, 4, 6, 8, 10, 0]);
+    /// ```
+    pub fn pitch_classes(&self) -> Vec<u8> {
+        let tonic = self.tonic.pitch_class();
+
+        self.kind
+            .semitones_from_tonic()
+            .iter()
+            .map(|semitones| (tonic + semitones) % 12)
+            .collect()
+    }
+
+    /// Get the mode of a scale that starts on one of its degrees (counting from 0 for the tonic),
+    /// that is, the scale that has the same notes but starts on a different one. The degree wraps
+    /// around the scale.
+    ///
+    /// Nothing is returned if the new tonic falls outside of the MIDI range, or if the resulting
+    /// pattern of intervals does not match any known kind of scale.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // D Dorian has the same notes as C major
+    /// let scale = Scale::new(C4, ScaleKind::Major);
+    /// assert_eq!(scale.mode(1), Some(Scale::new(D4, ScaleKind::Dorian)));
+    /// assert_eq!(scale.mode(6), Some(Scale::new(B4, ScaleKind::Locrian)));
+    ///
+    /// // A minor pentatonic has the same notes as C major pentatonic
+    /// let scale = Scale::new(C4, ScaleKind::MajorPentatonic);
+    /// assert_eq!(scale.mode(4), Some(Scale::new(A4, ScaleKind::MinorPentatonic)));
+    ///
+    /// // The modes of the harmonic minor scale have no names of their own (yet)
+    /// assert_eq!(Scale::new(A3, ScaleKind::HarmonicMinor).mode(1), None);
+    /// ```
+    pub fn mode(&self, degree: usize) -> Option<Scale> {
+        let semitones = self.kind.semitones_from_tonic();
+        let degree = degree % semitones.len();
+        if degree == 0 {
+            return Some(*self);
+        }
+
+        let offset = semitones[degree];
+        let rotated = semitones[degree..]
+            .iter()
+            .chain(&semitones[..degree])
+            .map(|semitones| (semitones + 12 - offset) % 12)
+            .collect::<Vec<u8>>();
+        let kind = ROTATABLE_KINDS
+            .iter()
+            .find(|kind| kind.semitones_from_tonic() == rotated.as_slice())?;
+
+        Some(Scale::new(self.tonic.transposed(offset as i8).ok()?, *kind))
+    }
+
+    /// Build the seven triads made of stacked thirds on top of every degree of a seven-note scale,
+    /// each of them next to its degree (from 1 for the tonic to 7).
+    ///
+    /// Scales with other than seven notes have no diatonic chords, and chords with notes that
+    /// would fall outside of the MIDI range are left out.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let triads = Scale::new(C4, ScaleKind::Major).diatonic_triads();
+    /// let names = triads
+    ///     .iter()
+    ///     .map(|(_, chord)| chord.name(FlatOrSharp::Sharp).unwrap())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["C", "Dm", "Em", "F", "G", "Am", "Bdim"]);
+    /// assert_eq!(triads[4], (5, Chord::from_notes(&[G4, B4, D5])));
+    ///
+    /// assert!(Scale::new(C4, ScaleKind::Blues).diatonic_triads().is_empty());
+    /// ```
+    pub fn diatonic_triads(&self) -> Vec<(u8, Chord)> {
+        self.diatonic_chords(3)
+    }
+
+    /// Build the seven seventh chords made of stacked thirds on top of every degree of a
+    /// seven-note scale, each of them next to its degree (from 1 for the tonic to 7).
+    ///
+    /// Scales with other than seven notes have no diatonic chords, and chords with notes that
+    /// would fall outside of the MIDI range are left out.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let sevenths = Scale::new(A3, ScaleKind::HarmonicMinor).diatonic_sevenths();
+    /// let names = sevenths
+    ///     .iter()
+    ///     .map(|(_, chord)| chord.name(FlatOrSharp::Sharp).unwrap())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["AmM7", "Bm7b5", "CM7#5", "Dm7", "E7", "FM7", "G#dim7"]);
+    /// ```
+    pub fn diatonic_sevenths(&self) -> Vec<(u8, Chord)> {
+        self.diatonic_chords(4)
+    }
+
+    /// Build the chords made of a number of stacked thirds on top of every degree of a seven-note
+    /// scale.
+    fn diatonic_chords(&self, size: usize) -> Vec<(u8, Chord)> {
+        let semitones = self.kind.semitones_from_tonic();
+        if semitones.len() != 7 {
+            return Vec::new();
+        }
+
+        (0..7)
+            .filter_map(|degree| {
+                let notes = (0..size)
+                    .map(|third| {
+                        let step = degree + 2 * third;
+                        let octave = 12 * (step / 7) as u8;
+                        self.tonic
+                            .transposed((semitones[step % 7] + octave) as i8)
+                            .ok()
+                    })
+                    .collect::<Option<Vec<Note>>>()?;
+
+                Some((degree as u8 + 1, Chord::from_notes(&notes)))
+            })
+            .collect()
+    }
+
+    /// Tells whether a note belongs to a scale, regardless of its octave.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let blues = Scale::new(C4, ScaleKind::Blues);
+    /// assert_eq!(blues.contains(FSharp2), true);
+    /// assert_eq!(blues.contains(E5), false);
+    /// ```
+    pub fn contains(&self, note: Note) -> bool {
+        self.pitch_classes().contains(&note.pitch_class())
+    }
+}
+
+/// Text representations of scales, e.g. "D Dorian", using sharps for accidentals.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// assert_eq!(format!("{}", Scale::new(D4, ScaleKind::Dorian)), "D Dorian");
+/// assert_eq!(format!("{}", Scale::new(FSharp2, ScaleKind::HarmonicMinor)), "F# harmonic minor");
+/// ```
+impl Display for Scale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.tonic.write_pitch_name(f, FlatOrSharp::Sharp)?;
+
+        write!(f, " {}", self.kind)
+    }
+}
+
+/// The different kinds of scales, each of them having its own pattern of intervals.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScaleKind {
+    Major,
+    NaturalMinor,
+    HarmonicMinor,
+    /// The ascending form of the melodic minor scale.
+    MelodicMinor,
+    MajorPentatonic,
+    MinorPentatonic,
+    /// The minor pentatonic scale plus the flatted fifth.
+    Blues,
+    WholeTone,
+    /// The first church mode, with the same intervals as the major scale.
+    Ionian,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    /// The sixth church mode, with the same intervals as the natural minor scale.
+    Aeolian,
+    Locrian,
+    /// The fourth mode of the melodic minor scale, i.e. the Lydian mode with a minor seventh.
+    LydianDominant,
+    /// The seventh mode of the melodic minor scale, also known as the super Locrian scale.
+    Altered,
+    /// The octatonic scale alternating half and whole steps, as played over dominant chords.
+    HalfWholeDiminished,
+    /// The octatonic scale alternating whole and half steps, as played over diminished chords.
+    WholeHalfDiminished,
+}
+
+/// Convenient methods for working with kinds of scales.
+impl ScaleKind {
+    /// Get the semitone offsets of the notes in a scale of this kind, relative to its tonic.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(ScaleKind::Major.semitones_from_tonic(), &[0, 2, 4, 5, 7, 9, 11]);
+    /// assert_eq!(ScaleKind::Blues.semitones_from_tonic(), &[0, 3, 5, 6, 7, 10]);
+    /// ```
+    pub fn semitones_from_tonic(&self) -> &'static [u8] {
+        use ScaleKind::*;
+
+        match self {
+            Major | Ionian => &[0, 2, 4, 5, 7, 9, 11],
+            NaturalMinor | Aeolian => &[0, 2, 3, 5, 7, 8, 10],
+            HarmonicMinor => &[0, 2, 3, 5, 7, 8, 11],
+            MelodicMinor => &[0, 2, 3, 5, 7, 9, 11],
+            MajorPentatonic => &[0, 2, 4, 7, 9],
+            MinorPentatonic => &[0, 3, 5, 7, 10],
+            Blues => &[0, 3, 5, 6, 7, 10],
+            WholeTone => &[0, 2, 4, 6, 8, 10],
+            Dorian => &[0, 2, 3, 5, 7, 9, 10],
+            Phrygian => &[0, 1, 3, 5, 7, 8, 10],
+            Lydian => &[0, 2, 4, 6, 7, 9, 11],
+            Mixolydian => &[0, 2, 4, 5, 7, 9, 10],
+            Locrian => &[0, 1, 3, 5, 6, 8, 10],
+            LydianDominant => &[0, 2, 4, 6, 7, 9, 10],
+            Altered => &[0, 1, 3, 4, 6, 8, 10],
+            HalfWholeDiminished => &[0, 1, 3, 4, 6, 7, 9, 10],
+            WholeHalfDiminished => &[0, 2, 3, 5, 6, 8, 9, 11],
+        }
+    }
+}
+
+/// Text representations of kinds of scales. Church modes are capitalized, as usual.
+impl Display for ScaleKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use ScaleKind::*;
+
+        f.write_str(match self {
+            Major => "major",
+            NaturalMinor => "natural minor",
+            HarmonicMinor => "harmonic minor",
+            MelodicMinor => "melodic minor",
+            MajorPentatonic => "major pentatonic",
+            MinorPentatonic => "minor pentatonic",
+            Blues => "blues",
+            WholeTone => "whole tone",
+            Ionian => "Ionian",
+            Dorian => "Dorian",
+            Phrygian => "Phrygian",
+            Lydian => "Lydian",
+            Mixolydian => "Mixolydian",
+            Aeolian => "Aeolian",
+            Locrian => "Locrian",
+            LydianDominant => "Lydian dominant",
+            Altered => "altered",
+            HalfWholeDiminished => "half-whole diminished",
+            WholeHalfDiminished => "whole-half diminished",
+        })
+    }
+}
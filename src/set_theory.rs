@@ -0,0 +1,410 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+/// Forte numbers of every set class with three to nine pitch classes, next to their prime forms
+/// as computed by `prime_form()`. These differ from the ones listed by Allen Forte himself for a
+/// handful of set classes (e.g. 6-31), as he resolved ties in compactness differently.
+const FORTE_NUMBERS: [(&str, &[u8]); 208] = [
+    ("3-1", &[0, 1, 2]),
+    ("3-2", &[0, 1, 3]),
+    ("3-3", &[0, 1, 4]),
+    ("3-4", &[0, 1, 5]),
+    ("3-5", &[0, 1, 6]),
+    ("3-6", &[0, 2, 4]),
+    ("3-7", &[0, 2, 5]),
+    ("3-8", &[0, 2, 6]),
+    ("3-9", &[0, 2, 7]),
+    ("3-10", &[0, 3, 6]),
+    ("3-11", &[0, 3, 7]),
+    ("3-12", &[0, 4, 8]),
+    ("4-1", &[0, 1, 2, 3]),
+    ("4-2", &[0, 1, 2, 4]),
+    ("4-3", &[0, 1, 3, 4]),
+    ("4-4", &[0, 1, 2, 5]),
+    ("4-5", &[0, 1, 2, 6]),
+    ("4-6", &[0, 1, 2, 7]),
+    ("4-7", &[0, 1, 4, 5]),
+    ("4-8", &[0, 1, 5, 6]),
+    ("4-9", &[0, 1, 6, 7]),
+    ("4-10", &[0, 2, 3, 5]),
+    ("4-11", &[0, 1, 3, 5]),
+    ("4-12", &[0, 2, 3, 6]),
+    ("4-13", &[0, 1, 3, 6]),
+    ("4-14", &[0, 2, 3, 7]),
+    ("4-Z15", &[0, 1, 4, 6]),
+    ("4-16", &[0, 1, 5, 7]),
+    ("4-17", &[0, 3, 4, 7]),
+    ("4-18", &[0, 1, 4, 7]),
+    ("4-19", &[0, 1, 4, 8]),
+    ("4-20", &[0, 1, 5, 8]),
+    ("4-21", &[0, 2, 4, 6]),
+    ("4-22", &[0, 2, 4, 7]),
+    ("4-23", &[0, 2, 5, 7]),
+    ("4-24", &[0, 2, 4, 8]),
+    ("4-25", &[0, 2, 6, 8]),
+    ("4-26", &[0, 3, 5, 8]),
+    ("4-27", &[0, 2, 5, 8]),
+    ("4-28", &[0, 3, 6, 9]),
+    ("4-Z29", &[0, 1, 3, 7]),
+    ("5-1", &[0, 1, 2, 3, 4]),
+    ("5-2", &[0, 1, 2, 3, 5]),
+    ("5-3", &[0, 1, 2, 4, 5]),
+    ("5-4", &[0, 1, 2, 3, 6]),
+    ("5-5", &[0, 1, 2, 3, 7]),
+    ("5-6", &[0, 1, 2, 5, 6]),
+    ("5-7", &[0, 1, 2, 6, 7]),
+    ("5-8", &[0, 2, 3, 4, 6]),
+    ("5-9", &[0, 1, 2, 4, 6]),
+    ("5-10", &[0, 1, 3, 4, 6]),
+    ("5-11", &[0, 2, 3, 4, 7]),
+    ("5-Z12", &[0, 1, 3, 5, 6]),
+    ("5-13", &[0, 1, 2, 4, 8]),
+    ("5-14", &[0, 1, 2, 5, 7]),
+    ("5-15", &[0, 1, 2, 6, 8]),
+    ("5-16", &[0, 1, 3, 4, 7]),
+    ("5-Z17", &[0, 1, 3, 4, 8]),
+    ("5-Z18", &[0, 1, 4, 5, 7]),
+    ("5-19", &[0, 1, 3, 6, 7]),
+    ("5-20", &[0, 1, 5, 6, 8]),
+    ("5-21", &[0, 1, 4, 5, 8]),
+    ("5-22", &[0, 1, 4, 7, 8]),
+    ("5-23", &[0, 2, 3, 5, 7]),
+    ("5-24", &[0, 1, 3, 5, 7]),
+    ("5-25", &[0, 2, 3, 5, 8]),
+    ("5-26", &[0, 2, 4, 5, 8]),
+    ("5-27", &[0, 1, 3, 5, 8]),
+    ("5-28", &[0, 2, 3, 6, 8]),
+    ("5-29", &[0, 1, 3, 6, 8]),
+    ("5-30", &[0, 1, 4, 6, 8]),
+    ("5-31", &[0, 1, 3, 6, 9]),
+    ("5-32", &[0, 1, 4, 6, 9]),
+    ("5-33", &[0, 2, 4, 6, 8]),
+    ("5-34", &[0, 2, 4, 6, 9]),
+    ("5-35", &[0, 2, 4, 7, 9]),
+    ("5-Z36", &[0, 1, 2, 4, 7]),
+    ("5-Z37", &[0, 3, 4, 5, 8]),
+    ("5-Z38", &[0, 1, 2, 5, 8]),
+    ("6-1", &[0, 1, 2, 3, 4, 5]),
+    ("6-2", &[0, 1, 2, 3, 4, 6]),
+    ("6-Z3", &[0, 1, 2, 3, 5, 6]),
+    ("6-Z4", &[0, 1, 2, 4, 5, 6]),
+    ("6-5", &[0, 1, 2, 3, 6, 7]),
+    ("6-Z6", &[0, 1, 2, 5, 6, 7]),
+    ("6-7", &[0, 1, 2, 6, 7, 8]),
+    ("6-8", &[0, 2, 3, 4, 5, 7]),
+    ("6-9", &[0, 1, 2, 3, 5, 7]),
+    ("6-Z10", &[0, 1, 3, 4, 5, 7]),
+    ("6-Z11", &[0, 1, 2, 4, 5, 7]),
+    ("6-Z12", &[0, 1, 2, 4, 6, 7]),
+    ("6-Z13", &[0, 1, 3, 4, 6, 7]),
+    ("6-14", &[0, 1, 3, 4, 5, 8]),
+    ("6-15", &[0, 1, 2, 4, 5, 8]),
+    ("6-16", &[0, 1, 4, 5, 6, 8]),
+    ("6-Z17", &[0, 1, 2, 4, 7, 8]),
+    ("6-18", &[0, 1, 2, 5, 7, 8]),
+    ("6-Z19", &[0, 1, 3, 4, 7, 8]),
+    ("6-20", &[0, 1, 4, 5, 8, 9]),
+    ("6-21", &[0, 2, 3, 4, 6, 8]),
+    ("6-22", &[0, 1, 2, 4, 6, 8]),
+    ("6-Z23", &[0, 2, 3, 5, 6, 8]),
+    ("6-Z24", &[0, 1, 3, 4, 6, 8]),
+    ("6-Z25", &[0, 1, 3, 5, 6, 8]),
+    ("6-Z26", &[0, 1, 3, 5, 7, 8]),
+    ("6-27", &[0, 1, 3, 4, 6, 9]),
+    ("6-Z28", &[0, 1, 3, 5, 6, 9]),
+    ("6-Z29", &[0, 2, 3, 6, 7, 9]),
+    ("6-30", &[0, 1, 3, 6, 7, 9]),
+    ("6-31", &[0, 1, 4, 5, 7, 9]),
+    ("6-32", &[0, 2, 4, 5, 7, 9]),
+    ("6-33", &[0, 2, 3, 5, 7, 9]),
+    ("6-34", &[0, 1, 3, 5, 7, 9]),
+    ("6-35", &[0, 2, 4, 6, 8, 10]),
+    ("6-Z36", &[0, 1, 2, 3, 4, 7]),
+    ("6-Z37", &[0, 1, 2, 3, 4, 8]),
+    ("6-Z38", &[0, 1, 2, 3, 7, 8]),
+    ("6-Z39", &[0, 2, 3, 4, 5, 8]),
+    ("6-Z40", &[0, 1, 2, 3, 5, 8]),
+    ("6-Z41", &[0, 1, 2, 3, 6, 8]),
+    ("6-Z42", &[0, 1, 2, 3, 6, 9]),
+    ("6-Z43", &[0, 1, 2, 5, 6, 8]),
+    ("6-Z44", &[0, 1, 2, 5, 6, 9]),
+    ("6-Z45", &[0, 2, 3, 4, 6, 9]),
+    ("6-Z46", &[0, 1, 2, 4, 6, 9]),
+    ("6-Z47", &[0, 1, 2, 4, 7, 9]),
+    ("6-Z48", &[0, 1, 2, 5, 7, 9]),
+    ("6-Z49", &[0, 1, 3, 4, 7, 9]),
+    ("6-Z50", &[0, 1, 4, 6, 7, 9]),
+    ("7-1", &[0, 1, 2, 3, 4, 5, 6]),
+    ("7-2", &[0, 1, 2, 3, 4, 5, 7]),
+    ("7-3", &[0, 1, 2, 3, 4, 5, 8]),
+    ("7-4", &[0, 1, 2, 3, 4, 6, 7]),
+    ("7-5", &[0, 1, 2, 3, 5, 6, 7]),
+    ("7-6", &[0, 1, 2, 3, 4, 7, 8]),
+    ("7-7", &[0, 1, 2, 3, 6, 7, 8]),
+    ("7-8", &[0, 2, 3, 4, 5, 6, 8]),
+    ("7-9", &[0, 1, 2, 3, 4, 6, 8]),
+    ("7-10", &[0, 1, 2, 3, 4, 6, 9]),
+    ("7-11", &[0, 1, 3, 4, 5, 6, 8]),
+    ("7-Z12", &[0, 1, 2, 3, 4, 7, 9]),
+    ("7-13", &[0, 1, 2, 4, 5, 6, 8]),
+    ("7-14", &[0, 1, 2, 3, 5, 7, 8]),
+    ("7-15", &[0, 1, 2, 4, 6, 7, 8]),
+    ("7-16", &[0, 1, 2, 3, 5, 6, 9]),
+    ("7-Z17", &[0, 1, 2, 4, 5, 6, 9]),
+    ("7-Z18", &[0, 1, 4, 5, 6, 7, 9]),
+    ("7-19", &[0, 1, 2, 3, 6, 7, 9]),
+    ("7-20", &[0, 1, 2, 5, 6, 7, 9]),
+    ("7-21", &[0, 1, 2, 4, 5, 8, 9]),
+    ("7-22", &[0, 1, 2, 5, 6, 8, 9]),
+    ("7-23", &[0, 2, 3, 4, 5, 7, 9]),
+    ("7-24", &[0, 1, 2, 3, 5, 7, 9]),
+    ("7-25", &[0, 2, 3, 4, 6, 7, 9]),
+    ("7-26", &[0, 1, 3, 4, 5, 7, 9]),
+    ("7-27", &[0, 1, 2, 4, 5, 7, 9]),
+    ("7-28", &[0, 1, 3, 5, 6, 7, 9]),
+    ("7-29", &[0, 1, 2, 4, 6, 7, 9]),
+    ("7-30", &[0, 1, 2, 4, 6, 8, 9]),
+    ("7-31", &[0, 1, 3, 4, 6, 7, 9]),
+    ("7-32", &[0, 1, 3, 4, 6, 8, 9]),
+    ("7-33", &[0, 1, 2, 4, 6, 8, 10]),
+    ("7-34", &[0, 1, 3, 4, 6, 8, 10]),
+    ("7-35", &[0, 1, 3, 5, 6, 8, 10]),
+    ("7-Z36", &[0, 1, 2, 3, 5, 6, 8]),
+    ("7-Z37", &[0, 1, 3, 4, 5, 7, 8]),
+    ("7-Z38", &[0, 1, 2, 4, 5, 7, 8]),
+    ("8-1", &[0, 1, 2, 3, 4, 5, 6, 7]),
+    ("8-2", &[0, 1, 2, 3, 4, 5, 6, 8]),
+    ("8-3", &[0, 1, 2, 3, 4, 5, 6, 9]),
+    ("8-4", &[0, 1, 2, 3, 4, 5, 7, 8]),
+    ("8-5", &[0, 1, 2, 3, 4, 6, 7, 8]),
+    ("8-6", &[0, 1, 2, 3, 5, 6, 7, 8]),
+    ("8-7", &[0, 1, 2, 3, 4, 5, 8, 9]),
+    ("8-8", &[0, 1, 2, 3, 4, 7, 8, 9]),
+    ("8-9", &[0, 1, 2, 3, 6, 7, 8, 9]),
+    ("8-10", &[0, 2, 3, 4, 5, 6, 7, 9]),
+    ("8-11", &[0, 1, 2, 3, 4, 5, 7, 9]),
+    ("8-12", &[0, 1, 3, 4, 5, 6, 7, 9]),
+    ("8-13", &[0, 1, 2, 3, 4, 6, 7, 9]),
+    ("8-14", &[0, 1, 2, 4, 5, 6, 7, 9]),
+    ("8-Z15", &[0, 1, 2, 3, 4, 6, 8, 9]),
+    ("8-16", &[0, 1, 2, 3, 5, 7, 8, 9]),
+    ("8-17", &[0, 1, 3, 4, 5, 6, 8, 9]),
+    ("8-18", &[0, 1, 2, 3, 5, 6, 8, 9]),
+    ("8-19", &[0, 1, 2, 4, 5, 6, 8, 9]),
+    ("8-20", &[0, 1, 2, 4, 5, 7, 8, 9]),
+    ("8-21", &[0, 1, 2, 3, 4, 6, 8, 10]),
+    ("8-22", &[0, 1, 2, 3, 5, 6, 8, 10]),
+    ("8-23", &[0, 1, 2, 3, 5, 7, 8, 10]),
+    ("8-24", &[0, 1, 2, 4, 5, 6, 8, 10]),
+    ("8-25", &[0, 1, 2, 4, 6, 7, 8, 10]),
+    ("8-26", &[0, 1, 3, 4, 5, 7, 8, 10]),
+    ("8-27", &[0, 1, 2, 4, 5, 7, 8, 10]),
+    ("8-28", &[0, 1, 3, 4, 6, 7, 9, 10]),
+    ("8-Z29", &[0, 1, 2, 3, 5, 6, 7, 9]),
+    ("9-1", &[0, 1, 2, 3, 4, 5, 6, 7, 8]),
+    ("9-2", &[0, 1, 2, 3, 4, 5, 6, 7, 9]),
+    ("9-3", &[0, 1, 2, 3, 4, 5, 6, 8, 9]),
+    ("9-4", &[0, 1, 2, 3, 4, 5, 7, 8, 9]),
+    ("9-5", &[0, 1, 2, 3, 4, 6, 7, 8, 9]),
+    ("9-6", &[0, 1, 2, 3, 4, 5, 6, 8, 10]),
+    ("9-7", &[0, 1, 2, 3, 4, 5, 7, 8, 10]),
+    ("9-8", &[0, 1, 2, 3, 4, 6, 7, 8, 10]),
+    ("9-9", &[0, 1, 2, 3, 5, 6, 7, 8, 10]),
+    ("9-10", &[0, 1, 2, 3, 4, 6, 7, 9, 10]),
+    ("9-11", &[0, 1, 2, 3, 5, 6, 7, 9, 10]),
+    ("9-12", &[0, 1, 2, 4, 5, 6, 8, 9, 10]),
+];
+
+/// Sort a set of pitch classes, dropping any repetitions. Anything above 11 is taken modulo 12.
+fn sorted(pitch_classes: &[u8]) -> Vec<u8> {
+    pitch_classes
+        .iter()
+        .map(|pitch_class| pitch_class % 12)
+        .collect::<BTreeSet<u8>>()
+        .into_iter()
+        .collect()
+}
+
+/// Transpose an ordered set of pitch classes so that it starts at 0.
+fn zeroed(pitch_classes: &[u8]) -> Vec<u8> {
+    let first = pitch_classes.first().cloned().unwrap_or_default();
+
+    pitch_classes
+        .iter()
+        .map(|pitch_class| (pitch_class + 12 - first) % 12)
+        .collect()
+}
+
+/// Tells whether an ordered set of pitch classes starting at 0 is more packed to the left than
+/// another one of the same size, that is, whether the distance from its first to its last pitch
+/// class is smaller, or else the distance from its first to its second to last, and so on.
+fn is_more_packed(a: &[u8], b: &[u8]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+/// The different ways of telling that two sets of pitch classes belong to the same set class.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Equivalence {
+    /// Sets are equivalent if one is a transposition of the other (Tn), e.g. all major triads.
+    Transposition,
+    /// Sets are equivalent if one is a transposition of the other or of its inversion (TnI), e.g.
+    /// all major and minor triads.
+    TranspositionInversion,
+}
+
+/// Compute the normal form of a set of pitch classes, that is, its most compact ordering, as
+/// defined by John Rahn: the rotation of the sorted pitch classes spanning the smallest
+/// interval, with ties resolved by looking at the intervals from the first pitch class to the
+/// second to last, and so on. Remaining ties are resolved in favor of the lowest first pitch class.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// // C major in any order
+/// assert_eq!(normal_form(&[7, 0, 4]), vec![0, 4, 7]);
+///
+/// // G major wraps around the octave
+/// assert_eq!(normal_form(&[2, 7, 11]), vec![7, 11, 2]);
+///
+/// // Repetitions do not count
+/// assert_eq!(normal_form(&[0, 4, 7, 0]), vec![0, 4, 7]);
+/// assert_eq!(normal_form(&[]), vec![]);
+/// ```
+pub fn normal_form(pitch_classes: &[u8]) -> Vec<u8> {
+    let sorted = sorted(pitch_classes);
+
+    let mut best = sorted.clone();
+    for rotation in 1..sorted.len() {
+        let rotated = sorted[rotation..]
+            .iter()
+            .chain(&sorted[..rotation])
+            .cloned()
+            .collect::<Vec<u8>>();
+        if is_more_packed(&zeroed(&rotated), &zeroed(&best)) {
+            best = rotated;
+        }
+    }
+
+    best
+}
+
+/// Compute the prime form of a set of pitch classes, that is, the normal form (transposed to start
+/// at 0) of either the set or its inversion, whichever is more packed to the left. All the sets
+/// that are related by transposition or inversion share the same prime form.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// // Major and minor triads are inversions of each other
+/// assert_eq!(prime_form(&[0, 4, 7]), vec![0, 3, 7]);
+/// assert_eq!(prime_form(&[9, 0, 4]), vec![0, 3, 7]);
+///
+/// // Dominant and half-diminished sevenths too
+/// assert_eq!(prime_form(&[7, 11, 2, 5]), vec![0, 2, 5, 8]);
+/// assert_eq!(prime_form(&[11, 2, 5, 9]), vec![0, 2, 5, 8]);
+/// ```
+pub fn prime_form(pitch_classes: &[u8]) -> Vec<u8> {
+    let inverted = pitch_classes
+        .iter()
+        .map(|pitch_class| (12 - pitch_class % 12) % 12)
+        .collect::<Vec<u8>>();
+    let original = zeroed(&normal_form(pitch_classes));
+    let inverted = zeroed(&normal_form(&inverted));
+
+    if is_more_packed(&inverted, &original) {
+        inverted
+    } else {
+        original
+    }
+}
+
+/// Get the set class that a set of pitch classes belongs to, as the representative of the class
+/// that all equivalent sets share: the normal form transposed to start at 0 when only transposition
+/// is considered, or the prime form when inversion is considered too.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// // Major and minor triads are only equivalent when considering inversion
+/// assert_eq!(set_class(&[2, 6, 9], Equivalence::Transposition), vec![0, 4, 7]);
+/// assert_eq!(set_class(&[9, 0, 4], Equivalence::Transposition), vec![0, 3, 7]);
+/// assert_eq!(set_class(&[2, 6, 9], Equivalence::TranspositionInversion), vec![0, 3, 7]);
+/// ```
+pub fn set_class(pitch_classes: &[u8], equivalence: Equivalence) -> Vec<u8> {
+    match equivalence {
+        Equivalence::Transposition => zeroed(&normal_form(pitch_classes)),
+        Equivalence::TranspositionInversion => prime_form(pitch_classes),
+    }
+}
+
+/// Get the Forte number of the set class that a set of pitch classes belongs to, e.g. "3-11" for
+/// major and minor triads, or "4-Z15" for the all-interval tetrachords. Sets of fewer than three
+/// or more than nine pitch classes have no Forte number.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// assert_eq!(forte_number(&[0, 4, 7]), Some("3-11"));
+/// assert_eq!(forte_number(&[0, 2, 4, 5, 7, 9, 11]), Some("7-35"));
+/// assert_eq!(forte_number(&[0, 1, 4, 6]), Some("4-Z15"));
+/// assert_eq!(forte_number(&[0, 7]), None);
+/// ```
+pub fn forte_number(pitch_classes: &[u8]) -> Option<&'static str> {
+    let prime = prime_form(pitch_classes);
+
+    FORTE_NUMBERS
+        .iter()
+        .find(|(_, prime_form)| *prime_form == prime.as_slice())
+        .map(|(number, _)| *number)
+}
+
+/// Compute the interval vector of a set of pitch classes, that is, how many times each interval
+/// class (from the minor second or major seventh to the tritone) appears between any two pitch
+/// classes in the set.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// // A major triad has one minor third, one major third and one perfect fourth or fifth
+/// assert_eq!(interval_vector(&[0, 4, 7]), [0, 0, 1, 1, 1, 0]);
+///
+/// // The all-interval tetrachords have every interval class exactly once
+/// assert_eq!(interval_vector(&[0, 1, 4, 6]), [1, 1, 1, 1, 1, 1]);
+/// ```
+pub fn interval_vector(pitch_classes: &[u8]) -> [u8; 6] {
+    let sorted = sorted(pitch_classes);
+
+    let mut vector = [0; 6];
+    for (index, a) in sorted.iter().enumerate() {
+        for b in &sorted[index + 1..] {
+            let distance = b - a;
+            let class = distance.min(12 - distance);
+            vector[usize::from(class) - 1] += 1;
+        }
+    }
+
+    vector
+}
+
+/// Tells whether two sets of pitch classes are Z-related, that is, whether they share the same
+/// interval vector despite belonging to different set classes (not being related by transposition
+/// or inversion).
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// // The two all-interval tetrachords, 4-Z15 and 4-Z29
+/// assert!(z_related(&[0, 1, 4, 6], &[0, 1, 3, 7]));
+///
+/// // Major and minor triads belong to the same set class
+/// assert!(!z_related(&[0, 4, 7], &[0, 3, 7]));
+/// ```
+pub fn z_related(a: &[u8], b: &[u8]) -> bool {
+    interval_vector(a) == interval_vector(b) && prime_form(a) != prime_form(b)
+}
@@ -0,0 +1,487 @@
+use crate::error::Error;
+use crate::prelude::*;
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter, Write};
+use core::str::FromStr;
+
+/// The letter names of the natural tones, from C to B.
+pub(crate) const LETTERS: [char; 7] = ['C', 'D', 'E', 'F', 'G', 'A', 'B'];
+/// Pitch classes of the natural tones, from C to B.
+const NATURALS: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+/// How many letters above the root the notes that make chord types are spelled, indexed by their
+/// distance in semitones to the root (e.g. a major third is spelled 2 letters above the root).
+const CHORD_TONE_STEPS: [u8; 12] = [0, 1, 1, 2, 2, 3, 4, 4, 4, 5, 6, 6];
+/// How many letters above the root the extensions of chords and the notes added to them are
+/// spelled, indexed by their distance in semitones to the root (e.g. 3 semitones make a sharp
+/// ninth, which is spelled 1 letter above the root).
+const EXTENSION_STEPS: [u8; 12] = [0, 1, 1, 1, 2, 3, 3, 4, 5, 5, 6, 6];
+
+/// How to choose the letter names of notes with accidentals (e.g. "G#" or "Ab") when naming notes
+/// and chords.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let chord = Chord::from_notes(&[ASharp3, D4, F4, GSharp4]);
+///
+/// // Using the same accidental for every note misspells some of them
+/// assert_eq!(chord.name(Spelling::Accidental(FlatOrSharp::Sharp)).unwrap(), "A#7");
+///
+/// // Spelling after the key lets the chord tones take their proper letters
+/// let spelling = Spelling::Key(Key::major(C4));
+/// assert_eq!(chord.name(spelling).unwrap(), "Bb7");
+/// assert_eq!(chord.note_names(spelling), vec!["Bb3", "D4", "F4", "Ab4"]);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Spelling {
+    /// Every note with accidentals is spelled the same way, regardless of the context.
+    Accidental(FlatOrSharp),
+    /// Notes are spelled after the key they are played in, and the notes in a chord after their
+    /// distance to its root (e.g. the third of E major is G#, and that of F minor is Ab).
+    Key(Key),
+}
+
+/// Convenient methods for spelling notes.
+impl Spelling {
+    /// Spell a note on its own.
+    pub(crate) fn spell(&self, note: Note) -> Spelled {
+        match self {
+            Spelling::Accidental(accidental) => Spelled::with_accidental(note, *accidental),
+            Spelling::Key(key) => spell_in_key(key, note),
+        }
+    }
+
+    /// Spell the root of a chord of some type. Roots out of the key are spelled the way that
+    /// keeps the whole chord closest to the key signature (e.g. Bb7 rather than A#7 in C major).
+    pub(crate) fn spell_root(&self, root: Note, chord_type: &ChordType) -> Spelled {
+        let key = match self {
+            Spelling::Key(key) if !key.contains(root) => key,
+            _ => return self.spell(root),
+        };
+
+        let alterations = key_alterations(key);
+        let opposite = match key.accidental() {
+            FlatOrSharp::Flat => FlatOrSharp::Sharp,
+            FlatOrSharp::Sharp => FlatOrSharp::Flat,
+        };
+        let candidates = [
+            Spelled::with_accidental(root, key.accidental()),
+            Spelled::with_accidental(root, opposite),
+        ];
+
+        // Unwrap is OK because there are always two candidates
+        candidates
+            .iter()
+            .copied()
+            .min_by_key(|candidate| {
+                chord_type
+                    .semitones_from_root()
+                    .iter()
+                    .map(|semitones| {
+                        let pitch_class = (candidate.pitch_class() + semitones) % 12;
+                        let tone = chord_tone(*candidate, chord_type, pitch_class);
+
+                        (tone.alteration - alterations[usize::from(tone.letter)]).abs()
+                    })
+                    .sum::<i8>()
+            })
+            .unwrap()
+    }
+
+    /// Spell a note in a chord, after its distance to the root of the chord.
+    pub(crate) fn spell_in_chord(&self, chord: &Chord, note: Note) -> Spelled {
+        if let Spelling::Accidental(accidental) = self {
+            return Spelled::with_accidental(note, *accidental);
+        }
+
+        match (chord.chord_type(), chord.root()) {
+            // Polychords spell each note after the first of the stacked chords that has it
+            (ChordType::Complex(chords), _) => chords
+                .iter()
+                .find(|chord| chord.contains_pitch_class(note.pitch_class()))
+                .map_or_else(
+                    || self.spell(note),
+                    |chord| self.spell_in_chord(chord, note),
+                ),
+            (chord_type, Some(root)) => {
+                let root = self.spell_root(root, chord_type);
+                let tone = chord_tone(root, chord_type, note.pitch_class());
+
+                // Triple accidentals are not supported, so such notes are spelled on their own
+                if tone.alteration.abs() > 2 {
+                    self.spell(note)
+                } else {
+                    tone
+                }
+            }
+            (_, None) => self.spell(note),
+        }
+    }
+}
+
+/// Support for spelling all the notes with accidentals in the same way.
+impl From<FlatOrSharp> for Spelling {
+    fn from(accidental: FlatOrSharp) -> Self {
+        Spelling::Accidental(accidental)
+    }
+}
+
+/// Support for spelling notes after a key.
+impl From<Key> for Spelling {
+    fn from(key: Key) -> Self {
+        Spelling::Key(key)
+    }
+}
+
+/// Support for spelling notes after a key.
+impl From<&Key> for Spelling {
+    fn from(key: &Key) -> Self {
+        Spelling::Key(*key)
+    }
+}
+
+/// A letter name (0-6, from C to B) together with the alteration of its natural tone in semitones
+/// (e.g. -1 for flat).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Spelled {
+    letter: u8,
+    alteration: i8,
+}
+
+impl Spelled {
+    /// Spell a pitch class (0-11) with a letter, altering its natural tone as needed.
+    fn new(letter: u8, pitch_class: u8) -> Self {
+        let natural = NATURALS[usize::from(letter)];
+        let alteration = (i16::from(pitch_class) - i16::from(natural) + 6).rem_euclid(12) - 6;
+
+        Self {
+            letter,
+            // Unwrap is OK because the alteration is always in the -6 to 5 range
+            alteration: i8::try_from(alteration).unwrap(),
+        }
+    }
+
+    /// Spell a note with the letter of the natural tone below (for sharps) or above (for flats).
+    fn with_accidental(note: Note, accidental: FlatOrSharp) -> Self {
+        let transpose_half_tones = match (note.is_sharp(), accidental) {
+            (true, FlatOrSharp::Flat) => 1,
+            _ => 0,
+        };
+        let tone = note.tone_name(transpose_half_tones);
+        // Unwrap is OK because tone names are always the letter of a natural tone
+        let letter = LETTERS.iter().position(|letter| *letter == tone).unwrap();
+
+        Self::new(letter as u8, note.pitch_class())
+    }
+
+    /// Get the pitch class (0-11) of a spelled note.
+    fn pitch_class(self) -> u8 {
+        let pitch_class =
+            i16::from(NATURALS[usize::from(self.letter)]) + i16::from(self.alteration);
+
+        // Unwrap is OK because `rem_euclid` always yields a number in the 0-11 range
+        u8::try_from(pitch_class.rem_euclid(12)).unwrap()
+    }
+
+    /// Get the accidental of a spelled note. Spellings never need more than double accidentals.
+    fn accidental(self) -> Accidental {
+        Accidental::from_semitones(self.alteration).unwrap_or(Accidental::Natural)
+    }
+
+    /// Spell a note like this, placing it in the octave of its natural tone, which differs from
+    /// that of the note for spellings such as "B#" or "Cb".
+    pub(crate) fn with_octave(self, note: Note) -> SpelledNote {
+        let natural = i16::from(note.midi_key_number()) - i16::from(self.alteration);
+
+        SpelledNote {
+            letter: self.letter,
+            accidental: self.accidental(),
+            // Unwrap is OK because the natural tone is never more than an octave away from the note
+            octave: i8::try_from(natural.div_euclid(12) - 1).unwrap(),
+        }
+    }
+
+    /// Write the name of a spelled note without its octave (e.g. "Bb") into any `core::fmt::Write`
+    /// implementor, without allocating.
+    pub(crate) fn write<W: Write>(
+        self,
+        f: &mut W,
+        accidentals: AccidentalStyle,
+        convention: &dyn NamingConvention,
+    ) -> core::fmt::Result {
+        convention.write_pitch(
+            f,
+            LETTERS[usize::from(self.letter)],
+            self.accidental(),
+            accidentals,
+        )
+    }
+}
+
+/// A note spelled with a specific letter name and accidental, as written in scores (e.g. "E#4"
+/// rather than "F4"). Octaves follow the letter rather than the sound, so "Cb4" sounds like "B3".
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SpelledNote {
+    letter: u8,
+    accidental: Accidental,
+    octave: i8,
+}
+
+/// Convenient methods for working with spelled notes.
+impl SpelledNote {
+    /// Build a spelled note from its letter (one of "A", "B", "C", "D", "E", "F" or "G"), its
+    /// accidental and its octave in scientific pitch notation.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// let e_sharp = SpelledNote::new('E', Accidental::Sharp, 4).unwrap();
+    /// assert_eq!(e_sharp.note(), F4);
+    /// assert_eq!(e_sharp.to_string(), "E#4");
+    ///
+    /// let c_flat = SpelledNote::new('C', Accidental::Flat, 4).unwrap();
+    /// assert_eq!(c_flat.note(), B3);
+    ///
+    /// assert_eq!(SpelledNote::new('H', Accidental::Natural, 4), Err(InvalidNoteName));
+    /// assert_eq!(SpelledNote::new('G', Accidental::Sharp, 9), Err(OutOfMIDIRange));
+    /// assert_eq!(SpelledNote::new('C', Accidental::Flat, -1), Err(OutOfMIDIRange));
+    /// ```
+    pub fn new(letter: char, accidental: Accidental, octave: i8) -> Result<Self, Error> {
+        let letter = LETTERS
+            .iter()
+            .position(|candidate| *candidate == letter.to_ascii_uppercase())
+            .ok_or(Error::InvalidNoteName)?;
+        let midi_key_number = (i16::from(octave) + 1) * 12
+            + i16::from(NATURALS[letter])
+            + i16::from(accidental.semitones());
+
+        if (0..128).contains(&midi_key_number) {
+            Ok(Self {
+                letter: letter as u8,
+                accidental,
+                octave,
+            })
+        } else {
+            Err(Error::OutOfMIDIRange)
+        }
+    }
+
+    /// Get the letter name (one of "A", "B", "C", "D", "E", "F" or "G") of a spelled note.
+    pub fn letter(&self) -> char {
+        LETTERS[usize::from(self.letter)]
+    }
+
+    /// Get the accidental of a spelled note.
+    pub fn accidental(&self) -> Accidental {
+        self.accidental
+    }
+
+    /// Get the octave of the letter of a spelled note, in scientific pitch notation.
+    pub fn octave(&self) -> i8 {
+        self.octave
+    }
+
+    /// Get the name of a spelled note, writing its accidental with either ASCII characters or
+    /// Unicode glyphs, naming its natural tone after some convention, and telling its octave in
+    /// some notation.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// use AccidentalStyle::*;
+    /// use OctaveNotation::*;
+    ///
+    /// let note = "Bbb3".parse::<SpelledNote>().unwrap();
+    /// assert_eq!(note.name(Ascii, &Letters, Scientific), "Bbb3");
+    /// assert_eq!(note.name(Unicode, &Letters, Scientific), "B𝄫3");
+    /// assert_eq!(note.name(Unicode, &Solfege, Scientific), "Si𝄫3");
+    /// assert_eq!(note.name(Unicode, &Letters, Helmholtz), "b𝄫");
+    /// ```
+    pub fn name(
+        &self,
+        accidentals: AccidentalStyle,
+        convention: &dyn NamingConvention,
+        octaves: OctaveNotation,
+    ) -> String {
+        let mut name = String::new();
+        // Writing into a `String` never fails
+        self.write(&mut name, accidentals, convention, octaves)
+            .unwrap();
+
+        name
+    }
+
+    /// Write the name of a spelled note into any `core::fmt::Write` implementor, without
+    /// allocating.
+    pub(crate) fn write<W: Write>(
+        &self,
+        f: &mut W,
+        accidentals: AccidentalStyle,
+        convention: &dyn NamingConvention,
+        octaves: OctaveNotation,
+    ) -> core::fmt::Result {
+        octaves.write_note(f, self.octave, |f| {
+            self.write_pitch(f, accidentals, convention)
+        })
+    }
+
+    /// Write the name of a spelled note without its octave into any `core::fmt::Write`
+    /// implementor, without allocating.
+    pub(crate) fn write_pitch(
+        &self,
+        f: &mut dyn Write,
+        accidentals: AccidentalStyle,
+        convention: &dyn NamingConvention,
+    ) -> core::fmt::Result {
+        convention.write_pitch(
+            f,
+            LETTERS[usize::from(self.letter)],
+            self.accidental,
+            accidentals,
+        )
+    }
+
+    /// Get the note that sounds when playing a spelled note.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!("Fx4".parse::<SpelledNote>().unwrap().note(), G4);
+    /// assert_eq!("Bbb3".parse::<SpelledNote>().unwrap().note(), A3);
+    /// ```
+    pub fn note(&self) -> Note {
+        let midi_key_number = (i16::from(self.octave) + 1) * 12
+            + i16::from(NATURALS[usize::from(self.letter)])
+            + i16::from(self.accidental.semitones());
+
+        // Spelled notes are checked to be in the MIDI range when built
+        Note::from(midi_key_number as u8)
+    }
+}
+
+/// Support for getting the note that sounds when playing a spelled note.
+impl From<SpelledNote> for Note {
+    fn from(spelled: SpelledNote) -> Self {
+        spelled.note()
+    }
+}
+
+/// Text representation of spelled notes, in scientific pitch notation (e.g. "Fx4" or "Bbb3").
+impl Display for SpelledNote {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write(
+            f,
+            AccidentalStyle::Ascii,
+            &Letters,
+            OctaveNotation::Scientific,
+        )
+    }
+}
+
+/// Support for parsing spelled notes written in scientific pitch notation, keeping their letters.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::{error::Error::*, prelude::*};
+///
+/// let note = "Fx4".parse::<SpelledNote>().unwrap();
+/// assert_eq!(note.letter(), 'F');
+/// assert_eq!(note.accidental(), Accidental::DoubleSharp);
+/// assert_eq!(note.octave(), 4);
+///
+/// // Parsing is the inverse of writing
+/// assert_eq!("B#3".parse::<SpelledNote>().unwrap().to_string(), "B#3");
+///
+/// assert_eq!("H4".parse::<SpelledNote>(), Err(InvalidNoteName));
+/// ```
+impl FromStr for SpelledNote {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let note = s.parse::<Note>()?;
+        let tone = s
+            .trim()
+            .chars()
+            .next()
+            .map(|tone| tone.to_ascii_uppercase());
+        // Unwrap is OK because notes that parse always start with the letter of a natural tone
+        let letter = LETTERS
+            .iter()
+            .position(|letter| Some(*letter) == tone)
+            .unwrap();
+
+        Ok(Spelled::new(letter as u8, note.pitch_class()).with_octave(note))
+    }
+}
+
+/// Spell a note after a key, using the letters of the scale degrees for the notes in the key.
+fn spell_in_key(key: &Key, note: Note) -> Spelled {
+    let tonic = Spelled::with_accidental(key.tonic(), key.accidental());
+    let pitch_class = note.pitch_class();
+    let offset = (pitch_class + 12 - tonic.pitch_class()) % 12;
+
+    let degree = key
+        .pitch_classes()
+        .iter()
+        .position(|in_key| *in_key == pitch_class)
+        .or(match (key.mode(), offset) {
+            // The raised sixth and seventh degrees of minor keys keep their letters
+            (KeyMode::Minor, 9) => Some(5),
+            (KeyMode::Minor, 11) => Some(6),
+            _ => None,
+        });
+
+    match degree {
+        Some(degree) => Spelled::new((tonic.letter + degree as u8) % 7, pitch_class),
+        None => Spelled::with_accidental(note, key.accidental()),
+    }
+}
+
+/// Get the alterations that the signature of a key applies to every letter, from C to B.
+fn key_alterations(key: &Key) -> [i8; 7] {
+    let tonic = Spelled::with_accidental(key.tonic(), key.accidental());
+
+    let mut alterations = [0i8; 7];
+    for (degree, pitch_class) in key.pitch_classes().iter().enumerate() {
+        let spelled = Spelled::new((tonic.letter + degree as u8) % 7, *pitch_class);
+        alterations[usize::from(spelled.letter)] = spelled.alteration;
+    }
+
+    alterations
+}
+
+/// Spell a pitch class (0-11) as a note in a chord whose root is already spelled, taking the
+/// letter that matches its role in the chord (e.g. the seventh of a diminished seventh chord is
+/// spelled as a seventh, even if it sounds like a major sixth).
+fn chord_tone(root: Spelled, chord_type: &ChordType, pitch_class: u8) -> Spelled {
+    use DyadQuality::*;
+
+    let offset = (pitch_class + 12 - root.pitch_class()) % 12;
+    let steps = match (chord_type, offset) {
+        (_, 0) => 0,
+        // Dyads are spelled after the number of their interval
+        (
+            ChordType::Dyad(
+                Augmented(number) | Diminished(number) | Major(number) | Minor(number)
+                | Perfect(number),
+            ),
+            _,
+        ) => (number + 6) % 7,
+        _ => match chord_type
+            .semitones_from_root()
+            .iter()
+            .find(|semitones| *semitones % 12 == offset)
+        {
+            Some(9) if *chord_type == ChordType::Tetrad(TetradQuality::SeventhDiminished) => 6,
+            Some(semitones) if *semitones < 12 => CHORD_TONE_STEPS[usize::from(offset)],
+            _ => EXTENSION_STEPS[usize::from(offset)],
+        },
+    };
+
+    Spelled::new((root.letter + steps) % 7, pitch_class)
+}
@@ -0,0 +1,480 @@
+use crate::error::Error;
+use crate::interval::Interval;
+use crate::note::Note;
+use core::ops::{Add, Mul, Neg, Sub};
+use libm::{log2f, powf};
+
+/// How many parts every semitone is divided into by the MIDI Tuning Standard.
+const SEMITONE_FRACTIONS: u32 = 16384;
+
+/// The bytes that the MIDI Tuning Standard uses for leaving the tuning of a note untouched.
+const NO_CHANGE: [u8; 3] = [0x7F, 0x7F, 0x7F];
+
+/// The length of the name of a tuning program in a bulk tuning dump.
+const PROGRAM_NAME_LENGTH: usize = 16;
+
+/// The value of a MIDI pitch wheel when it is centered (no bend).
+pub(crate) const PITCH_BEND_CENTER: u16 = 8192;
+
+/// The concert pitch used unless told otherwise, i.e. the frequency in Hertz of A4.
+pub const STANDARD_CONCERT_PITCH: f32 = 440f32;
+
+/// The frequency ratios of every note of 5-limit just intonation to its tonic, from the unison
+/// up to the major seventh, as numerators and denominators.
+pub(crate) const FIVE_LIMIT_RATIOS: [(u8, u8); 12] = [
+    (1, 1),
+    (16, 15),
+    (9, 8),
+    (6, 5),
+    (5, 4),
+    (4, 3),
+    (45, 32),
+    (3, 2),
+    (8, 5),
+    (5, 3),
+    (9, 5),
+    (15, 8),
+];
+
+/// How far apart in cents every pitch class, starting with C, is from equal temperament in
+/// Pythagorean tuning, with its fifths spanning from Eb to G#.
+pub(crate) const PYTHAGOREAN_DEVIATIONS: [f32; 12] = [
+    0f32, 13.685, 3.91, -5.865, 7.82, -1.955, 11.73, 1.955, 15.64, 5.865, -3.91, 9.775,
+];
+
+/// How far apart in cents every pitch class, starting with C, is from equal temperament in
+/// quarter-comma meantone, with its fifths spanning from Eb to G#.
+pub(crate) const QUARTER_COMMA_MEANTONE_DEVIATIONS: [f32; 12] = [
+    0f32, -23.951, -6.843, 10.265, -13.686, 3.422, -20.529, -3.422, -27.373, -10.265, 6.843,
+    -17.108,
+];
+
+/// How far apart in cents every pitch class, starting with C, is from equal temperament in
+/// Werckmeister III.
+pub(crate) const WERCKMEISTER_III_DEVIATIONS: [f32; 12] = [
+    0f32, -9.775, -7.82, -5.865, -9.775, -1.955, -11.73, -3.91, -7.82, -11.73, -3.91, -7.82,
+];
+
+/// A distance between pitches measured in cents, i.e. hundredths of a semitone in 12-tone equal
+/// temperament, which can be told apart in frequency ratios, semitones or pitch bend.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::*;
+///
+/// // A pure fifth is a bit wider than seven semitones
+/// let fifth = Cents::from_ratio(1.5);
+/// assert!((fifth.value() - 701.955).abs() < 0.001);
+/// assert!((fifth - Cents::from(Interval::new(7))).value() < 2.);
+///
+/// // Cents add up as frequency ratios multiply
+/// let octave = fifth + Cents::from_ratio(4. / 3.);
+/// assert!((octave.ratio() - 2.).abs() < 1e-5);
+/// assert_eq!(-Cents::from_semitones(1.), Cents::new(-100.));
+/// assert_eq!(Cents::new(25.) * 4., Cents::from_semitones(1.));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Cents {
+    cents: f32,
+}
+
+/// Convenient methods for working with cents.
+impl Cents {
+    /// Build a distance out of a number of cents.
+    pub fn new(cents: f32) -> Self {
+        Self { cents }
+    }
+
+    /// Build a distance out of a (maybe fractional) number of semitones.
+    pub fn from_semitones(semitones: f32) -> Self {
+        Self::new(semitones * 100f32)
+    }
+
+    /// Build the distance between two pitches out of the ratio between their frequencies.
+    pub fn from_ratio(ratio: f32) -> Self {
+        Self::new(1200f32 * log2f(ratio))
+    }
+
+    /// Build the distance from a frequency in Hertz up to another one, which is negative if the
+    /// second one is lower.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let cents = Cents::between(A4.frequency(), 445.);
+    /// assert!((cents.value() - 19.56).abs() < 0.01);
+    /// ```
+    pub fn between(from: f32, to: f32) -> Self {
+        Self::from_ratio(to / from)
+    }
+
+    /// Build the distance that a MIDI pitch wheel bends notes, out of its value (from 0 to 16383,
+    /// where 8192 is the center) and its range in semitones.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::*;
+    ///
+    /// assert_eq!(Cents::from_pitch_bend(8192, 2), Cents::new(0.));
+    /// assert_eq!(Cents::from_pitch_bend(0, 2), Cents::new(-200.));
+    /// assert_eq!(Cents::from_pitch_bend(12288, 12), Cents::new(600.));
+    /// ```
+    pub fn from_pitch_bend(value: u16, range: u8) -> Self {
+        let bend = (f32::from(value) - f32::from(PITCH_BEND_CENTER)) / f32::from(PITCH_BEND_CENTER);
+
+        Self::from_semitones(bend * f32::from(range))
+    }
+
+    /// Get the number of cents of a distance.
+    pub fn value(self) -> f32 {
+        self.cents
+    }
+
+    /// Get the (maybe fractional) number of semitones of a distance.
+    pub fn semitones(self) -> f32 {
+        self.cents / 100f32
+    }
+
+    /// Get the ratio between the frequencies of two pitches that are some distance apart.
+    pub fn ratio(self) -> f32 {
+        powf(2f32, self.cents / 1200f32)
+    }
+}
+
+/// Intervals span a whole number of semitones, i.e. of hundreds of cents.
+impl From<Interval> for Cents {
+    fn from(interval: Interval) -> Self {
+        Self::from_semitones(f32::from(interval.semitones()))
+    }
+}
+
+/// Support for adding up distances.
+impl Add for Cents {
+    type Output = Cents;
+
+    fn add(self, other: Cents) -> Self::Output {
+        Cents::new(self.cents + other.cents)
+    }
+}
+
+/// Support for subtracting distances.
+impl Sub for Cents {
+    type Output = Cents;
+
+    fn sub(self, other: Cents) -> Self::Output {
+        Cents::new(self.cents - other.cents)
+    }
+}
+
+/// Support for turning distances up into down, and the other way around.
+impl Neg for Cents {
+    type Output = Cents;
+
+    fn neg(self) -> Self::Output {
+        Cents::new(-self.cents)
+    }
+}
+
+/// Support for scaling distances.
+impl Mul<f32> for Cents {
+    type Output = Cents;
+
+    fn mul(self, factor: f32) -> Self::Output {
+        Cents::new(self.cents * factor)
+    }
+}
+
+/// The systems that tell which frequency every note is tuned to.
+///
+/// Historical temperaments are laid out from C, and have A4 tuned to some concert pitch in Hertz.
+///
+/// # Examples
+/// ```rust
+/// use whatthechord::prelude::{*, Note::*};
+///
+/// let ratio = |tuning: &Tuning, low: Note, high: Note| {
+///     high.frequency_with(tuning) / low.frequency_with(tuning)
+/// };
+///
+/// // Pythagorean tuning has pure fifths, but wide thirds
+/// let pythagorean = Tuning::Pythagorean { concert_pitch: 440. };
+/// assert!((ratio(&pythagorean, C4, G4) - 3. / 2.).abs() < 1e-5);
+/// assert!((ratio(&pythagorean, C4, E4) - 81. / 64.).abs() < 1e-5);
+///
+/// // Quarter-comma meantone trades that for pure major thirds
+/// let meantone = Tuning::QuarterCommaMeantone { concert_pitch: 440. };
+/// assert!((ratio(&meantone, C4, E4) - 5. / 4.).abs() < 1e-5);
+///
+/// // Werckmeister III narrows the fifths of C-G-D-A and B-F#, keeping the rest pure
+/// let werckmeister = Tuning::WerckmeisterIII { concert_pitch: 415. };
+/// assert!(ratio(&werckmeister, C4, G4) < 3. / 2.);
+/// assert!((ratio(&werckmeister, E4, B4) - 3. / 2.).abs() < 1e-5);
+/// assert_eq!(A4.frequency_with(&werckmeister), 415.);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Tuning {
+    /// 12-tone equal temperament, with A4 tuned to some concert pitch in Hertz (e.g. 442, or 415
+    /// for baroque music).
+    EqualTemperament { concert_pitch: f32 },
+    /// 5-limit just intonation, where every note is tuned to a pure frequency ratio (made out of
+    /// the primes 2, 3 and 5) to a tonic, which is itself tuned to its frequency in standard equal
+    /// temperament.
+    Just { tonic: Note },
+    /// Pythagorean tuning, where all the fifths but one (the "wolf" fifth, from G# to Eb) are pure.
+    Pythagorean { concert_pitch: f32 },
+    /// Quarter-comma meantone, where all the major thirds but four are pure, at the expense of
+    /// slightly narrow fifths.
+    QuarterCommaMeantone { concert_pitch: f32 },
+    /// Werckmeister III, a well temperament that makes every key playable while keeping the most
+    /// common ones closer to pure.
+    WerckmeisterIII { concert_pitch: f32 },
+}
+
+/// Notes are tuned in 12-tone equal temperament, with A4 at 440Hz, unless told otherwise.
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning::EqualTemperament {
+            concert_pitch: STANDARD_CONCERT_PITCH,
+        }
+    }
+}
+
+/// Convenient methods for building tunings.
+impl Tuning {
+    /// Build a 12-tone equal temperament tuning with A4 tuned to some concert pitch in Hertz.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let baroque = Tuning::equal_temperament(415.);
+    /// assert_eq!(A4.frequency_with(&baroque), 415.);
+    /// assert_eq!(Note::from_frequency_with(415., &baroque), (A4, 0.));
+    /// ```
+    pub fn equal_temperament(concert_pitch: f32) -> Self {
+        Tuning::EqualTemperament { concert_pitch }
+    }
+
+    /// Get the frequency in Hertz that A4 is tuned to.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// assert_eq!(Tuning::default().concert_pitch(), 440.);
+    /// assert_eq!(Tuning::Just { tonic: A2 }.concert_pitch(), 440.);
+    ///
+    /// // A4 is a pure major sixth above C4
+    /// assert_eq!(Tuning::Just { tonic: C4 }.concert_pitch(), 436.04257);
+    /// ```
+    pub fn concert_pitch(&self) -> f32 {
+        match *self {
+            Tuning::EqualTemperament { concert_pitch }
+            | Tuning::Pythagorean { concert_pitch }
+            | Tuning::QuarterCommaMeantone { concert_pitch }
+            | Tuning::WerckmeisterIII { concert_pitch } => concert_pitch,
+            Tuning::Just { .. } => Note::A4.frequency_with(self),
+        }
+    }
+}
+
+/// The pitch of every key of a MIDI instrument, as retuned through the MIDI Tuning Standard (MTS).
+///
+/// Pitches are kept in the resolution used by MTS, i.e. 1/16384 of a semitone, and they start
+/// being those of 12-tone equal temperament.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TuningTable {
+    pitches: [u32; 128],
+}
+
+/// Tuning tables start in 12-tone equal temperament.
+impl Default for TuningTable {
+    fn default() -> Self {
+        let mut pitches = [0; 128];
+        for (key, pitch) in pitches.iter_mut().enumerate() {
+            *pitch = key as u32 * SEMITONE_FRACTIONS;
+        }
+
+        Self { pitches }
+    }
+}
+
+/// Convenient methods for retuning notes.
+impl TuningTable {
+    /// Build a tuning table in 12-tone equal temperament.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a tuning table out of a MIDI Tuning Standard SysEx message, applied on top of 12-tone
+    /// equal temperament. See `apply_sysex` for the supported messages.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// // A bulk tuning dump moving every note a quarter of a semitone up
+    /// let mut sysex = vec![0xF0, 0x7E, 0x00, 0x08, 0x01, 0x00];
+    /// sysex.extend_from_slice(b"Quarter up      ");
+    /// for key in 0..128 {
+    ///     sysex.extend_from_slice(&[key, 0x20, 0x00]);
+    /// }
+    /// sysex.extend_from_slice(&[0x00, 0xF7]);
+    ///
+    /// let tuning = TuningTable::from_sysex(&sysex).unwrap();
+    /// assert_eq!(tuning.pitch(C4), 60.25);
+    /// assert_eq!(tuning.pitch(A4), 69.25);
+    /// ```
+    pub fn from_sysex(bytes: &[u8]) -> Result<Self, Error> {
+        let mut tuning = Self::new();
+        tuning.apply_sysex(bytes)?;
+
+        Ok(tuning)
+    }
+
+    /// Retune notes as told by a MIDI Tuning Standard SysEx message, starting with 0xF0 and ending
+    /// with 0xF7.
+    ///
+    /// Bulk tuning dumps, which retune all the notes at once, and single note tuning changes, with
+    /// or without a bank number, are supported. Programs and banks are not told apart, so every
+    /// message applies to this table. The checksum of bulk tuning dumps is not verified, as plenty
+    /// of instruments get it wrong.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// // Move A4 half a semitone up
+    /// let mut tuning = TuningTable::new();
+    /// tuning
+    ///     .apply_sysex(&[0xF0, 0x7F, 0x7F, 0x08, 0x02, 0x00, 0x01, 69, 69, 0x40, 0x00, 0xF7])
+    ///     .unwrap();
+    /// assert_eq!(tuning.pitch(A4), 69.5);
+    /// assert_eq!(tuning.pitch(GSharp4), 68.);
+    ///
+    /// // Messages other than MTS ones are refused
+    /// assert_eq!(tuning.apply_sysex(&[0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]), Err(InvalidMidiMessage));
+    /// ```
+    pub fn apply_sysex(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let body = bytes
+            .strip_prefix(&[0xF0])
+            .and_then(|bytes| bytes.strip_suffix(&[0xF7]))
+            .filter(|body| body.iter().all(|byte| *byte < 0x80))
+            .ok_or(Error::InvalidMidiMessage)?;
+
+        match body {
+            // Bulk tuning dump, with a name and a checksum around the tuning of every note
+            [0x7E, _device, 0x08, 0x01, _program, data @ ..]
+                if data.len() == PROGRAM_NAME_LENGTH + 128 * 3 + 1 =>
+            {
+                let data = &data[PROGRAM_NAME_LENGTH..data.len() - 1];
+                for (key, tuning) in data.chunks(3).enumerate() {
+                    self.apply_tuning(key, tuning);
+                }
+            }
+            // Single note tuning change, without and with a bank number
+            [0x7E | 0x7F, _device, 0x08, 0x02, _program, count, data @ ..]
+            | [0x7E | 0x7F, _device, 0x08, 0x07, _, _program, count, data @ ..]
+                if data.len() == usize::from(*count) * 4 =>
+            {
+                for change in data.chunks(4) {
+                    self.apply_tuning(usize::from(change[0]), &change[1..]);
+                }
+            }
+            _ => return Err(Error::InvalidMidiMessage),
+        }
+
+        Ok(())
+    }
+
+    /// Retune a note to a pitch, given as a fractional MIDI key number (e.g. 60.5 for a quarter
+    /// tone above middle C).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::{error::Error::*, prelude::{*, Note::*}};
+    ///
+    /// let mut tuning = TuningTable::new();
+    /// tuning.retune(E4, 63.86).unwrap();
+    /// assert!((tuning.pitch(E4) - 63.86).abs() < 0.0001);
+    ///
+    /// assert_eq!(tuning.retune(G9, 128.), Err(OutOfMIDIRange));
+    /// ```
+    pub fn retune(&mut self, note: Note, pitch: f32) -> Result<(), Error> {
+        if !(0f32..128f32).contains(&pitch) {
+            return Err(Error::OutOfMIDIRange);
+        }
+        self.pitches[usize::from(note.midi_key_number())] =
+            libm::roundf(pitch * SEMITONE_FRACTIONS as f32) as u32;
+
+        Ok(())
+    }
+
+    /// Get the pitch that a note is tuned to, as a fractional MIDI key number.
+    pub fn pitch(&self, note: Note) -> f32 {
+        self.pitches[usize::from(note.midi_key_number())] as f32 / SEMITONE_FRACTIONS as f32
+    }
+
+    /// Get the frequency in Hertz that a note is tuned to.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tuning = TuningTable::new();
+    /// assert_eq!(tuning.frequency(A4), A4.frequency());
+    ///
+    /// tuning.retune(A4, 69.5).unwrap();
+    /// assert_eq!(tuning.frequency(A4), 452.89297);
+    /// ```
+    pub fn frequency(&self, note: Note) -> f32 {
+        STANDARD_CONCERT_PITCH * powf(2f32, (self.pitch(note) - 69f32) / 12f32)
+    }
+
+    /// Find the note that is tuned the closest to a frequency in Hertz, along with how far apart
+    /// from it the frequency is, in cents. Nothing is found for frequencies that are not positive.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use whatthechord::prelude::{*, Note::*};
+    ///
+    /// let mut tuning = TuningTable::new();
+    /// let (note, cents) = tuning.nearest_note(445.).unwrap();
+    /// assert_eq!(note, A4);
+    /// assert!((cents - 19.56).abs() < 0.01);
+    ///
+    /// // Once retuned, a different key plays that frequency
+    /// tuning.retune(GSharp4, 69.2).unwrap();
+    /// assert_eq!(tuning.nearest_note(445.).unwrap().0, GSharp4);
+    ///
+    /// assert_eq!(tuning.nearest_note(0.), None);
+    /// ```
+    pub fn nearest_note(&self, frequency: f32) -> Option<(Note, f32)> {
+        if !frequency.is_finite() || frequency <= 0f32 {
+            return None;
+        }
+
+        (0..128u8)
+            .map(Note::from)
+            .map(|note| {
+                (
+                    note,
+                    Cents::between(self.frequency(note), frequency).value(),
+                )
+            })
+            .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+    }
+
+    /// Retune the key with some MIDI key number as told by the three bytes used for that in MIDI
+    /// Tuning Standard messages.
+    fn apply_tuning(&mut self, key: usize, tuning: &[u8]) {
+        if tuning == NO_CHANGE {
+            return;
+        }
+        let pitch = tuning
+            .iter()
+            .fold(0, |pitch, byte| pitch << 7 | u32::from(*byte));
+        self.pitches[key] = pitch;
+    }
+}
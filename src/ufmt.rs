@@ -0,0 +1,203 @@
+use crate::prelude::*;
+use core::fmt::Write;
+use ufmt_write::uWrite;
+
+/// Items that can write their musician-friendly name into a `ufmt_write::uWrite` implementor (a
+/// display driver, a serial port, etc.) without allocating, the same way as `Display` does (e.g.
+/// using sharps for accidentals).
+///
+/// This has the very same shape as `ufmt::uDisplay`, which is left out only because the `ufmt`
+/// crate is not a dependency: implementing it for these items boils down to a call to
+/// `uwrite_name`.
+pub trait UWriteName {
+    /// Write the name of this item into a `uWrite` implementor.
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error>;
+}
+
+/// Chords that can write their musician-friendly name into a `uWrite` implementor, as told by some
+/// naming options.
+pub trait UWriteChordName {
+    /// Write the name of this chord into a `uWrite` implementor, as told by some naming options.
+    fn uwrite_chord_name<W: uWrite + ?Sized>(
+        &self,
+        w: &mut W,
+        options: &NameOptions,
+    ) -> Result<(), W::Error>;
+}
+
+/// Write the musician-friendly name of a chord into a `uWrite` implementor, as told by some naming
+/// options. Notes and qualities need no options, so they are written through `UWriteName` instead.
+///
+/// # Examples
+/// ```rust
+/// use ufmt_write::uWrite;
+/// use whatthechord::{prelude::{*, Note::*}, ufmt::{write_name, UWriteName}};
+///
+/// // A tiny stack buffer, like the ones used for driving character displays
+/// struct Buffer {
+///     bytes: [u8; 8],
+///     len: usize,
+/// }
+///
+/// impl uWrite for Buffer {
+///     type Error = ();
+///
+///     fn write_str(&mut self, s: &str) -> Result<(), ()> {
+///         let end = self.len + s.len();
+///         self.bytes.get_mut(self.len..end).ok_or(())?.copy_from_slice(s.as_bytes());
+///         self.len = end;
+///         Ok(())
+///     }
+/// }
+///
+/// let chord = Chord::from_notes(&[C1, DSharp1, G1]);
+/// let mut buffer = Buffer { bytes: [0; 8], len: 0 };
+/// write_name(&mut buffer, &chord, FlatOrSharp::Flat).unwrap();
+/// assert_eq!(&buffer.bytes[..buffer.len], b"Cm");
+///
+/// let chord = FixedChord::<4>::from_notes(&[CSharp1, E1, GSharp1]).unwrap();
+/// let mut buffer = Buffer { bytes: [0; 8], len: 0 };
+/// let options = NameOptions::new(FlatOrSharp::Flat).with_convention(&Solfege);
+/// write_name(&mut buffer, &chord, options).unwrap();
+/// assert_eq!(&buffer.bytes[..buffer.len], b"Rebm");
+///
+/// // Notes and qualities are written just like `Display` does
+/// let mut buffer = Buffer { bytes: [0; 8], len: 0 };
+/// ASharp4.uwrite_name(&mut buffer).unwrap();
+/// TetradQuality::SeventhMinor.uwrite_name(&mut buffer).unwrap();
+/// assert_eq!(&buffer.bytes[..buffer.len], b"A#4m7");
+///
+/// // Errors from the writer are passed through
+/// let mut buffer = Buffer { bytes: [0; 8], len: 7 };
+/// assert_eq!(ASharp4.uwrite_name(&mut buffer), Err(()));
+/// ```
+pub fn write_name<'a, W, C>(
+    w: &mut W,
+    chord: &C,
+    options: impl Into<NameOptions<'a>>,
+) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+    C: UWriteChordName + ?Sized,
+{
+    chord.uwrite_chord_name(w, &options.into())
+}
+
+/// Writing notes through `uWrite`, using sharps for accidentals.
+impl UWriteName for Note {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        adapt(w, |f| self.write_name_with(f, &NameOptions::default()))
+    }
+}
+
+/// Writing chords through `uWrite`, using sharps for accidentals.
+impl UWriteName for Chord {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        self.uwrite_chord_name(w, &NameOptions::default())
+    }
+}
+
+/// Writing chords through `uWrite`, naming inversions using slash notation by default.
+impl UWriteChordName for Chord {
+    fn uwrite_chord_name<W: uWrite + ?Sized>(
+        &self,
+        w: &mut W,
+        options: &NameOptions,
+    ) -> Result<(), W::Error> {
+        adapt(w, |f| self.write_name(f, options))
+    }
+}
+
+/// Writing fixed-size chords through `uWrite`, using sharps for accidentals.
+impl<const N: usize> UWriteName for FixedChord<N> {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        self.uwrite_chord_name(w, &NameOptions::default())
+    }
+}
+
+/// Writing fixed-size chords through `uWrite`.
+impl<const N: usize> UWriteChordName for FixedChord<N> {
+    fn uwrite_chord_name<W: uWrite + ?Sized>(
+        &self,
+        w: &mut W,
+        options: &NameOptions,
+    ) -> Result<(), W::Error> {
+        adapt(w, |f| self.write_name(f, *options))
+    }
+}
+
+/// Writing dyad qualities through `uWrite`.
+impl UWriteName for DyadQuality {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        adapt(w, |f| write!(f, "{}", self))
+    }
+}
+
+/// Writing triad qualities through `uWrite`.
+impl UWriteName for TriadQuality {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        adapt(w, |f| write!(f, "{}", self))
+    }
+}
+
+/// Writing tetrad qualities through `uWrite`.
+impl UWriteName for TetradQuality {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        adapt(w, |f| write!(f, "{}", self))
+    }
+}
+
+/// Writing pentad qualities through `uWrite`.
+impl UWriteName for PentadQuality {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        adapt(w, |f| write!(f, "{}", self))
+    }
+}
+
+/// Writing hexad qualities through `uWrite`.
+impl UWriteName for HexadQuality {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        adapt(w, |f| write!(f, "{}", self))
+    }
+}
+
+/// Writing heptad qualities through `uWrite`.
+impl UWriteName for HeptadQuality {
+    fn uwrite_name<W: uWrite + ?Sized>(&self, w: &mut W) -> Result<(), W::Error> {
+        adapt(w, |f| write!(f, "{}", self))
+    }
+}
+
+/// Bridge between `uWrite` and `core::fmt::Write`, so that the same naming code can be reused for
+/// both, keeping hold of the first error coming from the `uWrite` side.
+struct Adapter<'a, W: uWrite + ?Sized> {
+    writer: &'a mut W,
+    error: Option<W::Error>,
+}
+
+impl<'a, W: uWrite + ?Sized> Write for Adapter<'a, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.writer.write_str(s).map_err(|error| {
+            self.error = Some(error);
+            core::fmt::Error
+        })
+    }
+}
+
+/// Run some `core::fmt::Write` based naming code on top of a `uWrite` implementor.
+fn adapt<W, F>(writer: &mut W, write: F) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+    F: FnOnce(&mut Adapter<W>) -> core::fmt::Result,
+{
+    let mut adapter = Adapter {
+        writer,
+        error: None,
+    };
+
+    // Formatting errors can only come from the underlying writer
+    match (write(&mut adapter), adapter.error) {
+        (Err(_), Some(error)) => Err(error),
+        _ => Ok(()),
+    }
+}